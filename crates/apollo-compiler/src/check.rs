@@ -0,0 +1,636 @@
+//! Checking stored operations against a schema change.
+//!
+//! A [`diff`](crate::diff) tells which changes *can* break clients. Checking
+//! the operations clients actually send tells which changes *do*: an
+//! operation breaks when it was valid against the old schema, but selects a
+//! field, passes an argument or uses an enum value that is no longer valid in
+//! the new schema.
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use apollo_parser::ast;
+
+use crate::{
+    coercion,
+    diagnostics::{self, Diagnostic},
+    schema::{self, InputValueDefinition},
+    validation, Schema, Type,
+};
+
+/// Check the operations in `document` against a change from the `old` to the
+/// `new` schema.
+///
+/// Every operation of the document is checked, with the fragments it uses.
+/// Only the problems introduced by the change are reported: an operation that
+/// was already invalid against the old schema is not reported again for the
+/// same reason.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{check, Schema};
+/// use apollo_parser::Parser;
+///
+/// let old = Parser::new("type Query { user(id: ID): User } type User { name: String }")
+///     .parse()
+///     .document();
+/// let new = Parser::new("type Query { user(id: ID!): User } type User { fullName: String }")
+///     .parse()
+///     .document();
+///
+/// let input = "query GetUser($id: ID) { user(id: $id) { name } }";
+/// let operations = Parser::new(input).parse().document();
+///
+/// let checks = check::check(&Schema::new(&old), &Schema::new(&new), &operations);
+/// assert_eq!(checks[0].name(), Some("GetUser"));
+/// let messages: Vec<_> = checks[0]
+///     .diagnostics()
+///     .iter()
+///     .map(|diagnostic| diagnostic.message())
+///     .collect();
+/// assert_eq!(
+///     messages,
+///     [
+///         "the variable `$id` of type `ID` cannot be used where `ID!` is expected",
+///         "the type `User` has no field `name`",
+///     ]
+/// );
+/// assert_eq!(&input[checks[0].diagnostics()[1].labels()[0].range()], "name");
+/// ```
+pub fn check(old: &Schema, new: &Schema, document: &ast::Document) -> Vec<OperationCheck> {
+    let fragments: HashMap<String, ast::FragmentDefinition> = document
+        .definitions()
+        .filter_map(|def| match def {
+            ast::Definition::FragmentDefinition(fragment) => {
+                let name = fragment.fragment_name()?.name()?.text().to_string();
+                Some((name, fragment))
+            }
+            _ => None,
+        })
+        .collect();
+
+    document
+        .definitions()
+        .filter_map(|def| match def {
+            ast::Definition::OperationDefinition(operation) => Some(operation),
+            _ => None,
+        })
+        .map(|operation| {
+            let before = Check::new(old, &fragments).operation(&operation);
+            let diagnostics = Check::new(new, &fragments)
+                .operation(&operation)
+                .into_iter()
+                .filter(|diagnostic| !before.contains(diagnostic))
+                .collect();
+            OperationCheck {
+                name: schema::name(operation.name()),
+                range: diagnostics::range(&operation),
+                diagnostics,
+            }
+        })
+        .collect()
+}
+
+/// The result of checking one operation against a schema change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationCheck {
+    pub(crate) name: Option<String>,
+    pub(crate) range: Range<usize>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl OperationCheck {
+    /// Get the operation's name, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the operation's byte range in the checked document.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Get the problems the schema change introduces in the operation. Their
+    /// labels point into the checked document, at the operation or at the
+    /// fragments it uses.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics.as_ref()
+    }
+
+    /// Check whether the schema change breaks the operation.
+    pub fn is_broken(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+}
+
+/// A variable defined by the operation being checked.
+struct Variable {
+    ty: Type,
+    has_default: bool,
+}
+
+struct Check<'a> {
+    schema: &'a Schema,
+    fragments: &'a HashMap<String, ast::FragmentDefinition>,
+    variables: HashMap<String, Variable>,
+    visited: HashSet<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Check<'a> {
+    fn new(schema: &'a Schema, fragments: &'a HashMap<String, ast::FragmentDefinition>) -> Self {
+        Self {
+            schema,
+            fragments,
+            variables: HashMap::new(),
+            visited: HashSet::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn operation(mut self, operation: &ast::OperationDefinition) -> Vec<Diagnostic> {
+        let definitions = operation
+            .variable_definitions()
+            .into_iter()
+            .flat_map(|definitions| definitions.variable_definitions());
+        for definition in definitions {
+            let name = definition
+                .variable()
+                .and_then(|variable| schema::name(variable.name()));
+            let ty = definition.ty().and_then(schema::ty);
+            let (name, ty) = match (name, ty) {
+                (Some(name), Some(ty)) => (name, ty),
+                _ => continue,
+            };
+            match self.schema.type_definition(ty.name()) {
+                Some(definition) if definition.is_input() => {}
+                Some(_) => self.diagnostics.push(
+                    Diagnostic::new(format!(
+                        "the variable `${}` must have an input type, found `{}`",
+                        name, ty
+                    ))
+                    .label(diagnostics::range(&definition), "not an input type"),
+                ),
+                None => self.diagnostics.push(
+                    Diagnostic::new(format!("cannot find type `{}` in this schema", ty.name()))
+                        .label(diagnostics::range(&definition), "type not defined"),
+                ),
+            }
+            let has_default = definition
+                .default_value()
+                .and_then(|default| default.value())
+                .map_or(false, |value| !matches!(value, ast::Value::NullValue(_)));
+            self.variables.insert(name, Variable { ty, has_default });
+        }
+        self.directives(operation.directives());
+
+        let root = validation::root_type(self.schema, operation);
+        let root = match root {
+            Some(root) => root.to_string(),
+            None => {
+                let operation_type = match operation.operation_type() {
+                    Some(ty) if ty.mutation_token().is_some() => "mutations",
+                    Some(ty) if ty.subscription_token().is_some() => "subscriptions",
+                    _ => "queries",
+                };
+                self.diagnostics.push(
+                    Diagnostic::new(format!("the schema does not support {}", operation_type))
+                        .label(diagnostics::range(operation), "operation not supported"),
+                );
+                return self.diagnostics;
+            }
+        };
+        if let Some(selection_set) = operation.selection_set() {
+            self.selection_set(&root, &selection_set);
+        }
+        self.diagnostics
+    }
+
+    fn selection_set(&mut self, parent: &str, selection_set: &ast::SelectionSet) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => self.field(parent, &field),
+                ast::Selection::FragmentSpread(spread) => {
+                    self.directives(spread.directives());
+                    let name = match spread.fragment_name().and_then(|name| name.name()) {
+                        Some(name) => name.text().to_string(),
+                        None => continue,
+                    };
+                    // Unknown fragments and cycles are reported by validation,
+                    // whatever the schema.
+                    if !self.visited.insert(name.clone()) {
+                        continue;
+                    }
+                    let fragment = match self.fragments.get(&name) {
+                        Some(fragment) => fragment,
+                        None => continue,
+                    };
+                    self.directives(fragment.directives());
+                    let condition = fragment.type_condition();
+                    let ty = match self.type_condition(condition.as_ref()) {
+                        Some(ty) => ty,
+                        None => continue,
+                    };
+                    if let Some(selection_set) = fragment.selection_set() {
+                        self.selection_set(&ty, &selection_set);
+                    }
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    self.directives(inline.directives());
+                    let ty = match inline.type_condition() {
+                        Some(condition) => match self.type_condition(Some(&condition)) {
+                            Some(ty) => ty,
+                            None => continue,
+                        },
+                        None => parent.to_string(),
+                    };
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.selection_set(&ty, &selection_set);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the type a fragment selects on, if it is a composite type of the
+    /// schema.
+    fn type_condition(&mut self, condition: Option<&ast::TypeCondition>) -> Option<String> {
+        let condition = condition?;
+        let name = schema::name(condition.named_type()?.name())?;
+        match self.schema.type_definition(&name) {
+            Some(ty) if ty.is_composite() => Some(name),
+            Some(ty) => {
+                self.diagnostics.push(
+                    Diagnostic::new(format!(
+                        "fragments cannot select on the {} `{}`",
+                        ty.kind(),
+                        name
+                    ))
+                    .label(
+                        diagnostics::range(condition),
+                        "not an object, interface or union",
+                    ),
+                );
+                None
+            }
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::new(format!("cannot find type `{}` in this schema", name))
+                        .label(diagnostics::range(condition), "type not defined"),
+                );
+                None
+            }
+        }
+    }
+
+    fn field(&mut self, parent: &str, field: &ast::Field) {
+        let name = match field.name() {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+        self.directives(field.directives());
+        let definition = match self.schema.field_definition(parent, &name) {
+            Some(definition) => definition,
+            None => {
+                let range = field.name().map_or_else(
+                    || diagnostics::range(field),
+                    |name| diagnostics::range(&name),
+                );
+                self.diagnostics.push(
+                    Diagnostic::new(format!("the type `{}` has no field `{}`", parent, name))
+                        .label(range, "field not defined"),
+                );
+                return;
+            }
+        };
+        let coordinate = format!("the field `{}.{}`", parent, name);
+        self.arguments(
+            &format!("{}.{}", parent, name),
+            diagnostics::range(field),
+            definition.arguments(),
+            field.arguments(),
+        );
+
+        let ty = definition.ty();
+        let is_composite = self
+            .schema
+            .type_definition(ty.name())
+            .map_or(false, |definition| definition.is_composite());
+        match (field.selection_set(), is_composite) {
+            (Some(selection_set), true) => self.selection_set(ty.name(), &selection_set),
+            (None, true) => self.diagnostics.push(
+                Diagnostic::new(format!(
+                    "{} of type `{}` must have a selection of subfields",
+                    coordinate, ty
+                ))
+                .label(diagnostics::range(field), "missing selection set"),
+            ),
+            (Some(selection_set), false) => self.diagnostics.push(
+                Diagnostic::new(format!(
+                    "{} of type `{}` cannot have a selection of subfields",
+                    coordinate, ty
+                ))
+                .label(
+                    diagnostics::range(&selection_set),
+                    "unexpected selection set",
+                ),
+            ),
+            (None, false) => {}
+        }
+    }
+
+    fn directives(&mut self, directives: Option<ast::Directives>) {
+        for directive in directives
+            .iter()
+            .flat_map(|directives| directives.directives())
+        {
+            let definition = match validation::directives::definition(
+                self.schema,
+                &directive,
+                &mut self.diagnostics,
+            ) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            self.arguments(
+                &format!("@{}", definition.name()),
+                diagnostics::range(&directive),
+                definition.arguments(),
+                directive.arguments(),
+            );
+        }
+    }
+
+    /// Check the arguments given to a field or a directive with the rules
+    /// `validate` uses, checking variables against the operation's variable
+    /// definitions too.
+    fn arguments(
+        &mut self,
+        coordinate: &str,
+        range: Range<usize>,
+        definitions: &[InputValueDefinition],
+        arguments: Option<ast::Arguments>,
+    ) {
+        let schema = self.schema;
+        let variables = &self.variables;
+        validation::arguments::validate(
+            coordinate,
+            range,
+            definitions,
+            arguments,
+            &mut self.diagnostics,
+            |definition, value, diagnostics| {
+                check_value(schema, variables, definition, value, diagnostics)
+            },
+        );
+    }
+}
+
+/// Check that a value given to an argument is valid for its type. A variable
+/// must have a type allowed in that position, and literals must coerce to the
+/// type, e.g. enum values must exist.
+fn check_value(
+    schema: &Schema,
+    variables: &HashMap<String, Variable>,
+    definition: &InputValueDefinition,
+    value: &ast::Value,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let variable = match value {
+        ast::Value::Variable(variable) => variable,
+        _ => {
+            coercion::validate_literal(schema, definition.ty(), value, diagnostics);
+            return;
+        }
+    };
+    let name = match schema::name(variable.name()) {
+        Some(name) => name,
+        None => return,
+    };
+    // Undefined variables are reported by validation, whatever the schema.
+    let variable_definition = match variables.get(&name) {
+        Some(variable) => variable,
+        None => return,
+    };
+    let has_default = variable_definition.has_default || definition.default_value().is_some();
+    if !is_variable_allowed(&variable_definition.ty, definition.ty(), has_default) {
+        diagnostics.push(
+            Diagnostic::new(format!(
+                "the variable `${}` of type `{}` cannot be used where `{}` is expected",
+                name,
+                variable_definition.ty,
+                definition.ty()
+            ))
+            .label(
+                diagnostics::range(variable),
+                format!("expected `{}`", definition.ty()),
+            ),
+        );
+    }
+}
+
+/// All Variable Usages Are Allowed: check that a variable of type `variable`
+/// can be used where `location` is expected. A nullable variable can be used
+/// for a non-null location when it or the location has a default value.
+///
+/// See: https://spec.graphql.org/October2021/#sec-All-Variable-Usages-Are-Allowed
+fn is_variable_allowed(variable: &Type, location: &Type, has_default: bool) -> bool {
+    match location {
+        Type::NonNull(location) if !variable.is_non_null() && has_default => {
+            is_sub_type(variable, location)
+        }
+        _ => is_sub_type(variable, location),
+    }
+}
+
+fn is_sub_type(variable: &Type, location: &Type) -> bool {
+    match (variable, location) {
+        (Type::NonNull(variable), Type::NonNull(location)) => is_sub_type(variable, location),
+        (_, Type::NonNull(_)) => false,
+        (Type::NonNull(variable), location) => is_sub_type(variable, location),
+        (Type::List(variable), Type::List(location)) => is_sub_type(variable, location),
+        (Type::Named(variable), Type::Named(location)) => variable == location,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const OLD: &str = r#"
+type Query { user(id: ID): User search(role: Role, first: Int = 10): [User] }
+type Mutation { rename(id: ID!, name: String!): User }
+type User { id: ID! name: String friends: [User] role: Role }
+enum Role { ADMIN USER GUEST }
+directive @tag(name: String) on FIELD
+"#;
+
+    const NEW: &str = r#"
+type Query { user(id: ID!): User search(role: Role, limit: Int): [User] }
+type User { id: ID! fullName: String friends: User role: String }
+enum Role { ADMIN MEMBER }
+input Filter { role: Role }
+"#;
+
+    /// Each diagnostic's message, and the source text of its first label.
+    type Diagnostics = Vec<(String, String)>;
+
+    fn check_operations(input: &str) -> Vec<(Option<String>, Diagnostics)> {
+        let old = Parser::new(OLD).parse().document();
+        let new = Parser::new(NEW).parse().document();
+        let document = Parser::new(input).parse().document();
+        check(&Schema::new(&old), &Schema::new(&new), &document)
+            .into_iter()
+            .map(|check| {
+                let diagnostics = check
+                    .diagnostics()
+                    .iter()
+                    .map(|diagnostic| {
+                        let range = diagnostic.labels()[0].range();
+                        (diagnostic.message().to_string(), input[range].to_string())
+                    })
+                    .collect();
+                (check.name, diagnostics)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_reports_broken_fields_arguments_and_enum_values() {
+        let input = r#"
+query Unaffected { user(id: "1") { id } }
+query Fields($id: ID) { user(id: $id) { name friends { id } role { x } } }
+query Search { search(role: USER, first: 5) @tag(name: "a") { ...UserFields } }
+mutation Rename { rename(id: "1", name: "a") { id } }
+fragment UserFields on User { name }
+"#;
+        assert_eq!(
+            check_operations(input),
+            [
+                (Some("Unaffected".into()), vec![]),
+                (
+                    Some("Fields".into()),
+                    vec![
+                        (
+                            "the variable `$id` of type `ID` cannot be used where `ID!` is expected"
+                                .into(),
+                            "$id".into()
+                        ),
+                        ("the type `User` has no field `name`".into(), "name".into()),
+                        (
+                            "the field `User.role` of type `String` cannot have a selection of subfields"
+                                .into(),
+                            "{ x }".into()
+                        ),
+                    ]
+                ),
+                (
+                    Some("Search".into()),
+                    vec![
+                        (
+                            "cannot find directive `@tag` in this document".into(),
+                            "@tag(name: \"a\")".into()
+                        ),
+                        (
+                            "the enum `Role` has no value `USER`".into(),
+                            "USER".into()
+                        ),
+                        (
+                            "the field `Query.search` has no argument `first`".into(),
+                            "first: 5".into()
+                        ),
+                        ("the type `User` has no field `name`".into(), "name".into()),
+                    ]
+                ),
+                (
+                    Some("Rename".into()),
+                    vec![(
+                        "the schema does not support mutations".into(),
+                        "mutation Rename { rename(id: \"1\", name: \"a\") { id } }".into()
+                    )]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_ignores_problems_that_existed_before_the_change() {
+        let input = r#"
+query { user { id missing } search(role: OWNER) { id } }
+"#;
+        assert_eq!(
+            check_operations(input),
+            [(
+                None,
+                vec![(
+                    "the required argument `Query.user(id:)` is not provided".into(),
+                    "user { id missing }".into()
+                )]
+            )]
+        );
+    }
+
+    #[test]
+    fn it_reports_directives_like_validation() {
+        let input = r#"
+directive @d(a: Int!) on FIELD
+type Query { user(id: ID): User }
+type User { id: ID! }
+query { user(id: "1") { id @x @d(b: 3, b: 4) } }
+"#;
+        let document = Parser::new(input).parse().document();
+        let schema = Schema::new(&document);
+        let messages = |diagnostics: &[Diagnostic]| -> Vec<String> {
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message().to_string())
+                .collect()
+        };
+        let operation = document
+            .definitions()
+            .find_map(|def| match def {
+                ast::Definition::OperationDefinition(operation) => Some(operation),
+                _ => None,
+            })
+            .unwrap();
+        let fragments = HashMap::new();
+
+        let checked = messages(&Check::new(&schema, &fragments).operation(&operation));
+        assert_eq!(checked, messages(&crate::validate(&schema, &document)));
+        assert_eq!(
+            checked,
+            [
+                "cannot find directive `@x` in this document",
+                "the directive `@d` has no argument `b`",
+                "the argument `b` is provided multiple times",
+                "the required argument `@d(a:)` is not provided",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_allows_nullable_variables_with_defaults() {
+        assert!(is_variable_allowed(
+            &Type::Named("ID".into()),
+            &Type::NonNull(Box::new(Type::Named("ID".into()))),
+            true
+        ));
+        assert!(is_variable_allowed(
+            &Type::NonNull(Box::new(Type::List(Box::new(Type::NonNull(Box::new(
+                Type::Named("ID".into())
+            )))))),
+            &Type::List(Box::new(Type::Named("ID".into()))),
+            false
+        ));
+        assert!(!is_variable_allowed(
+            &Type::List(Box::new(Type::Named("ID".into()))),
+            &Type::List(Box::new(Type::NonNull(Box::new(Type::Named("ID".into()))))),
+            true
+        ));
+    }
+}
@@ -0,0 +1,876 @@
+//! Input coercion: turning literals and JSON variables into [`Value`]s of a
+//! schema's input types.
+//!
+//! Coercion checks the same rules as the "Values of Correct Type" validation
+//! rule, so validation uses it without resolving variables, while execution
+//! uses it to produce the values of arguments and variables.
+//!
+//! See: https://spec.graphql.org/October2021/#sec-Input-Values
+//! and https://spec.graphql.org/October2021/#sec-Coercing-Variable-Values
+use std::{collections::HashMap, ops::Range};
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::{
+    diagnostics::{self, Diagnostic},
+    schema::{self, InputValueDefinition},
+    Schema, Type, TypeDefinition, Value,
+};
+
+/// Coerce a literal `value` to the input type `ty`, replacing variables with
+/// their values in `variables`.
+///
+/// Variables are expected to have been coerced already, for example with
+/// [`coerce_variables`]. A variable missing from `variables` coerces to
+/// `null` in a list, and is treated as not provided in an input object.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{coercion, Schema, Type, Value};
+/// use apollo_parser::{ast::{self, AstNode}, Parser};
+/// use indexmap::IndexMap;
+///
+/// let input = "
+/// enum Size { SMALL LARGE }
+/// query { shirts(sizes: SMALL) }
+/// ";
+/// let document = Parser::new(input).parse().document();
+/// let schema = Schema::new(&document);
+///
+/// let value = document
+///     .syntax()
+///     .descendants()
+///     .find_map(ast::Argument::cast)
+///     .and_then(|arg| arg.value())
+///     .unwrap();
+/// let ty = Type::List(Box::new(Type::Named("Size".into())));
+///
+/// let coerced = coercion::coerce_literal(&schema, &ty, &value, &IndexMap::new());
+/// assert_eq!(coerced, Ok(Value::List(vec![Value::Enum("SMALL".into())])));
+/// ```
+pub fn coerce_literal(
+    schema: &Schema,
+    ty: &Type,
+    value: &ast::Value,
+    variables: &IndexMap<String, Value>,
+) -> Result<Value, Vec<Diagnostic>> {
+    let mut coercion = Coercion::new(schema, Some(variables));
+    let value = coercion.literal(ty, value);
+    coercion.finish(value)
+}
+
+/// Coerce the `arguments` given to a field or a directive, using the
+/// argument definitions in `definitions`.
+///
+/// Arguments that are not provided take their default value, if they have
+/// one, and are left out otherwise. A required argument that is not provided
+/// is an error.
+pub fn coerce_arguments(
+    schema: &Schema,
+    definitions: &[InputValueDefinition],
+    arguments: Option<&ast::Arguments>,
+    variables: &IndexMap<String, Value>,
+) -> Result<IndexMap<String, Value>, Vec<Diagnostic>> {
+    let mut coercion = Coercion::new(schema, Some(variables));
+    let provided: HashMap<String, ast::Argument> = arguments
+        .iter()
+        .flat_map(|args| args.arguments())
+        .filter_map(|arg| Some((arg.name()?.text().to_string(), arg)))
+        .collect();
+
+    let mut coerced = IndexMap::new();
+    for definition in definitions {
+        let argument = provided.get(definition.name());
+        let value = argument
+            .and_then(|arg| arg.value())
+            .filter(|value| coercion.is_provided(value));
+
+        match value {
+            Some(value) => {
+                if let Some(value) = coercion.literal(definition.ty(), &value) {
+                    coerced.insert(definition.name().to_string(), value);
+                }
+            }
+            None => match definition.default_value() {
+                Some(default) => {
+                    coerced.insert(definition.name().to_string(), default.clone());
+                }
+                None if definition.ty().is_non_null() => {
+                    let mut diagnostic = Diagnostic::new(format!(
+                        "the required argument `{}` of type `{}` is not provided",
+                        definition.name(),
+                        definition.ty()
+                    ));
+                    if let Some(arg) = argument {
+                        diagnostic = diagnostic.label(diagnostics::range(arg), "no value provided");
+                    }
+                    coercion.errors.push(diagnostic);
+                }
+                None => (),
+            },
+        }
+    }
+
+    coercion.finish(Some(coerced))
+}
+
+/// Coerce the JSON `values` provided for the variables of `operation`.
+///
+/// Variables that are not provided take their default value, if they have
+/// one, and are left out otherwise. A variable of a non-null type without a
+/// default value must be provided.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{coercion, Schema, Value};
+/// use apollo_parser::{ast, Parser};
+///
+/// let input = "
+/// input Filter { size: Int! tags: [String] }
+/// query Shirts($filter: Filter, $limit: Int = 10) { shirts }
+/// ";
+/// let document = Parser::new(input).parse().document();
+/// let schema = Schema::new(&document);
+/// let operation = document
+///     .definitions()
+///     .find_map(|def| match def {
+///         ast::Definition::OperationDefinition(op) => Some(op),
+///         _ => None,
+///     })
+///     .unwrap();
+///
+/// let values = serde_json::json!({ "filter": { "size": 3, "tags": "cotton" } });
+/// let variables = coercion::coerce_variables(&schema, &operation, values.as_object().unwrap())
+///     .unwrap();
+///
+/// assert_eq!(variables["filter"].to_string(), r#"{ size: 3, tags: ["cotton"] }"#);
+/// assert_eq!(variables["limit"], Value::Int(10));
+///
+/// let values = serde_json::json!({ "filter": { "size": "large" } });
+/// let errors = coercion::coerce_variables(&schema, &operation, values.as_object().unwrap())
+///     .unwrap_err();
+/// assert_eq!(
+///     errors[0].message(),
+///     "invalid value for variable `$filter` at `size`: expected a value of type `Int`, found a string"
+/// );
+/// ```
+pub fn coerce_variables(
+    schema: &Schema,
+    operation: &ast::OperationDefinition,
+    values: &serde_json::Map<String, serde_json::Value>,
+) -> Result<IndexMap<String, Value>, Vec<Diagnostic>> {
+    // Default values cannot contain variables.
+    let no_variables = IndexMap::new();
+    let mut coercion = Coercion::new(schema, Some(&no_variables));
+    let mut coerced = IndexMap::new();
+
+    let definitions = operation
+        .variable_definitions()
+        .into_iter()
+        .flat_map(|vars| vars.variable_definitions());
+    for definition in definitions {
+        let (name, ty) = match (
+            definition.variable().and_then(|var| var.name()),
+            definition.ty().and_then(schema::ty),
+        ) {
+            (Some(name), Some(ty)) => (name.text().to_string(), ty),
+            _ => continue,
+        };
+        let range = diagnostics::range(&definition);
+
+        let is_input = schema
+            .type_definition(ty.name())
+            .map_or(false, TypeDefinition::is_input);
+        if !is_input {
+            coercion.errors.push(
+                Diagnostic::new(format!(
+                    "variable `${}` has type `{}`, which is not an input type",
+                    name, ty
+                ))
+                .label(range, "not an input type"),
+            );
+            continue;
+        }
+
+        match values.get(&name) {
+            Some(value) => {
+                coercion.variable = Some(name.clone());
+                let value = coercion.json(&ty, value);
+                coercion.variable = None;
+                if let Some(value) = value {
+                    coerced.insert(name, value);
+                }
+            }
+            None => match definition
+                .default_value()
+                .and_then(|default| default.value())
+            {
+                Some(default) => {
+                    if let Some(value) = coercion.literal(&ty, &default) {
+                        coerced.insert(name, value);
+                    }
+                }
+                None if ty.is_non_null() => coercion.errors.push(
+                    Diagnostic::new(format!(
+                        "variable `${}` of required type `{}` was not provided",
+                        name, ty
+                    ))
+                    .label(range, "variable defined here"),
+                ),
+                None => (),
+            },
+        }
+    }
+
+    coercion.finish(Some(coerced))
+}
+
+/// Values of Correct Type: check that a literal can be coerced to `ty`,
+/// without resolving the variables it contains.
+pub(crate) fn validate_literal(
+    schema: &Schema,
+    ty: &Type,
+    value: &ast::Value,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut coercion = Coercion::new(schema, None);
+    coercion.literal(ty, value);
+    diagnostics.append(&mut coercion.errors);
+}
+
+struct Coercion<'a> {
+    schema: &'a Schema,
+    /// Values of variables, or `None` when variables are kept as they are.
+    variables: Option<&'a IndexMap<String, Value>>,
+    /// The variable whose JSON value is being coerced.
+    variable: Option<String>,
+    /// Where in the variable's JSON value the coercion is.
+    path: Vec<String>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> Coercion<'a> {
+    fn new(schema: &'a Schema, variables: Option<&'a IndexMap<String, Value>>) -> Self {
+        Self {
+            schema,
+            variables,
+            variable: None,
+            path: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn finish<T>(self, value: Option<T>) -> Result<T, Vec<Diagnostic>> {
+        match value {
+            Some(value) if self.errors.is_empty() => Ok(value),
+            _ => Err(self.errors),
+        }
+    }
+
+    /// A value is provided unless it is a variable without a value.
+    fn is_provided(&self, value: &ast::Value) -> bool {
+        match (value, self.variables) {
+            (ast::Value::Variable(var), Some(variables)) => var
+                .name()
+                .map_or(false, |name| variables.contains_key(name.text().as_ref())),
+            _ => true,
+        }
+    }
+
+    fn literal(&mut self, ty: &Type, value: &ast::Value) -> Option<Value> {
+        match (ty, value) {
+            (_, ast::Value::Variable(var)) => {
+                let name = var.name()?.text().to_string();
+                match self.variables {
+                    Some(variables) => {
+                        let value = variables.get(&name).cloned().unwrap_or(Value::Null);
+                        if value == Value::Null && ty.is_non_null() {
+                            self.mismatch(ty, "null", Some(diagnostics::range(var)));
+                            return None;
+                        }
+                        Some(value)
+                    }
+                    None => Some(Value::Variable(name)),
+                }
+            }
+            (Type::NonNull(_), ast::Value::NullValue(_)) => {
+                self.mismatch(ty, "null", Some(diagnostics::range(value)));
+                None
+            }
+            (_, ast::Value::NullValue(_)) => Some(Value::Null),
+            (Type::NonNull(inner), _) => self.literal(inner, value),
+            (Type::List(inner), ast::Value::ListValue(list)) => {
+                let mut items = Vec::new();
+                let mut is_valid = true;
+                for item in list.values() {
+                    match self.literal(inner, &item) {
+                        Some(item) => items.push(item),
+                        None => is_valid = false,
+                    }
+                }
+                is_valid.then(|| Value::List(items))
+            }
+            // A single item is coerced to a list of one.
+            (Type::List(inner), _) => Some(Value::List(vec![self.literal(inner, value)?])),
+            (Type::Named(name), _) => match self.schema.type_definition(name) {
+                // Types missing from the schema are treated like custom
+                // scalars.
+                Some(TypeDefinition::Scalar(_)) | None => self.scalar_literal(name, ty, value),
+                Some(TypeDefinition::Enum(enum_ty)) => match value {
+                    ast::Value::EnumValue(enum_value) => {
+                        let text = enum_value.text().to_string();
+                        if enum_ty.value(&text).is_none() {
+                            self.error(
+                                format!("the enum `{}` has no value `{}`", name, text),
+                                Some(diagnostics::range(value)),
+                                format!("not a value of `{}`", name),
+                            );
+                            return None;
+                        }
+                        Some(Value::Enum(text))
+                    }
+                    _ => {
+                        self.mismatch(ty, describe(value), Some(diagnostics::range(value)));
+                        None
+                    }
+                },
+                Some(TypeDefinition::InputObject(input)) => match value {
+                    ast::Value::ObjectValue(object) => {
+                        let mut provided: IndexMap<String, (Range<usize>, ast::Value)> =
+                            IndexMap::new();
+                        let mut is_valid = true;
+                        for field in object.object_fields() {
+                            let (name, value) = match (field.name(), field.value()) {
+                                (Some(name), Some(value)) => (name.text().to_string(), value),
+                                _ => continue,
+                            };
+                            let range = diagnostics::range(&field);
+                            if let Some((original, _)) = provided.get(&name) {
+                                self.errors.push(
+                                    Diagnostic::new(format!(
+                                        "the field `{}` is provided multiple times",
+                                        name
+                                    ))
+                                    .label(original.clone(), "previously provided here")
+                                    .label(range, "provided again here"),
+                                );
+                                is_valid = false;
+                                continue;
+                            }
+                            if input.field(&name).is_none() {
+                                self.error(
+                                    format!(
+                                        "the input object `{}` has no field `{}`",
+                                        input.name(),
+                                        name
+                                    ),
+                                    Some(range),
+                                    "unknown field",
+                                );
+                                is_valid = false;
+                                continue;
+                            }
+                            if self.is_provided(&value) {
+                                provided.insert(name, (range, value));
+                            }
+                        }
+
+                        let mut fields = IndexMap::new();
+                        for definition in input.fields() {
+                            match provided.get(definition.name()) {
+                                Some((_, value)) => match self.literal(definition.ty(), value) {
+                                    Some(value) => {
+                                        fields.insert(definition.name().to_string(), value);
+                                    }
+                                    None => is_valid = false,
+                                },
+                                None => {
+                                    if let Some(default) = definition.default_value() {
+                                        fields
+                                            .insert(definition.name().to_string(), default.clone());
+                                    } else if definition.ty().is_non_null() {
+                                        self.error(
+                                            format!(
+                                                "the required field `{}.{}` is not provided",
+                                                input.name(),
+                                                definition.name()
+                                            ),
+                                            Some(diagnostics::range(value)),
+                                            format!("missing `{}`", definition.name()),
+                                        );
+                                        is_valid = false;
+                                    }
+                                }
+                            }
+                        }
+                        is_valid.then(|| Value::Object(fields))
+                    }
+                    _ => {
+                        self.mismatch(ty, describe(value), Some(diagnostics::range(value)));
+                        None
+                    }
+                },
+                // Output types used as input types are reported by other
+                // rules.
+                Some(_) => None,
+            },
+        }
+    }
+
+    fn scalar_literal(&mut self, name: &str, ty: &Type, value: &ast::Value) -> Option<Value> {
+        let coerced = match (name, value) {
+            ("Int" | "ID", ast::Value::IntValue(int)) => {
+                let text = int.int_token()?.text().to_string();
+                if name == "ID" {
+                    Some(Value::String(text))
+                } else {
+                    match text.parse::<i32>() {
+                        Ok(int) => Some(Value::Int(int.into())),
+                        Err(_) => {
+                            self.error(
+                                format!(
+                                    "Int cannot represent non 32-bit signed integer value: {}",
+                                    text
+                                ),
+                                Some(diagnostics::range(value)),
+                                "out of range",
+                            );
+                            return None;
+                        }
+                    }
+                }
+            }
+            ("Float", ast::Value::IntValue(int)) => {
+                Some(Value::Float(int.int_token()?.text().parse().ok()?))
+            }
+            ("Float", ast::Value::FloatValue(float)) => {
+                Some(Value::Float(float.float_token()?.text().parse().ok()?))
+            }
+            ("String" | "ID", ast::Value::StringValue(_))
+            | ("Boolean", ast::Value::BooleanValue(_)) => crate::values::value(value.clone()),
+            ("Int" | "Float" | "String" | "Boolean" | "ID", _) => None,
+            // Custom scalars define their own coercion rules, so any literal
+            // is accepted.
+            _ => return self.any_literal(value),
+        };
+        if coerced.is_none() {
+            self.mismatch(ty, describe(value), Some(diagnostics::range(value)));
+        }
+        coerced
+    }
+
+    /// Lower a literal of a custom scalar, resolving the variables in it.
+    fn any_literal(&mut self, value: &ast::Value) -> Option<Value> {
+        match value {
+            ast::Value::Variable(var) => {
+                let name = var.name()?.text().to_string();
+                Some(match self.variables {
+                    Some(variables) => variables.get(&name).cloned().unwrap_or(Value::Null),
+                    None => Value::Variable(name),
+                })
+            }
+            ast::Value::ListValue(list) => Some(Value::List(
+                list.values()
+                    .map(|item| self.any_literal(&item))
+                    .collect::<Option<_>>()?,
+            )),
+            ast::Value::ObjectValue(object) => Some(Value::Object(
+                object
+                    .object_fields()
+                    .map(|field| {
+                        Some((
+                            field.name()?.text().to_string(),
+                            self.any_literal(&field.value()?)?,
+                        ))
+                    })
+                    .collect::<Option<_>>()?,
+            )),
+            _ => crate::values::value(value.clone()),
+        }
+    }
+
+    fn json(&mut self, ty: &Type, value: &serde_json::Value) -> Option<Value> {
+        use serde_json::Value as Json;
+
+        match (ty, value) {
+            (Type::NonNull(_), Json::Null) => {
+                self.mismatch(ty, "null", None);
+                None
+            }
+            (_, Json::Null) => Some(Value::Null),
+            (Type::NonNull(inner), _) => self.json(inner, value),
+            (Type::List(inner), Json::Array(items)) => {
+                let mut coerced = Vec::new();
+                let mut is_valid = true;
+                for (i, item) in items.iter().enumerate() {
+                    self.path.push(format!("[{}]", i));
+                    match self.json(inner, item) {
+                        Some(item) => coerced.push(item),
+                        None => is_valid = false,
+                    }
+                    self.path.pop();
+                }
+                is_valid.then(|| Value::List(coerced))
+            }
+            (Type::List(inner), _) => Some(Value::List(vec![self.json(inner, value)?])),
+            (Type::Named(name), _) => match self.schema.type_definition(name) {
+                Some(TypeDefinition::Scalar(_)) | None => {
+                    let coerced = match (name.as_str(), value) {
+                        ("Int", Json::Number(number)) => {
+                            let int = number.as_i64().or_else(|| {
+                                number
+                                    .as_f64()
+                                    .filter(|float| float.fract() == 0.0)
+                                    .map(|float| float as i64)
+                            });
+                            match int.filter(|int| i32::try_from(*int).is_ok()) {
+                                Some(int) => Some(Value::Int(int)),
+                                None => {
+                                    self.error(
+                                        format!(
+                                            "Int cannot represent non 32-bit signed integer value: {}",
+                                            number
+                                        ),
+                                        None,
+                                        "",
+                                    );
+                                    return None;
+                                }
+                            }
+                        }
+                        ("Float", Json::Number(number)) => number.as_f64().map(Value::Float),
+                        ("String", Json::String(string)) => Some(Value::String(string.clone())),
+                        ("Boolean", Json::Bool(boolean)) => Some(Value::Boolean(*boolean)),
+                        ("ID", Json::String(string)) => Some(Value::String(string.clone())),
+                        ("ID", Json::Number(number)) if number.is_i64() || number.is_u64() => {
+                            Some(Value::String(number.to_string()))
+                        }
+                        ("Int" | "Float" | "String" | "Boolean" | "ID", _) => None,
+                        _ => return Some(json_to_value(value)),
+                    };
+                    if coerced.is_none() {
+                        self.mismatch(ty, describe_json(value), None);
+                    }
+                    coerced
+                }
+                Some(TypeDefinition::Enum(enum_ty)) => match value {
+                    Json::String(string) if enum_ty.value(string).is_some() => {
+                        Some(Value::Enum(string.clone()))
+                    }
+                    Json::String(string) => {
+                        self.error(
+                            format!("the enum `{}` has no value `{}`", name, string),
+                            None,
+                            "",
+                        );
+                        None
+                    }
+                    _ => {
+                        self.mismatch(ty, describe_json(value), None);
+                        None
+                    }
+                },
+                Some(TypeDefinition::InputObject(input)) => match value {
+                    Json::Object(object) => {
+                        let mut is_valid = true;
+                        for name in object.keys() {
+                            if input.field(name).is_none() {
+                                self.error(
+                                    format!(
+                                        "the input object `{}` has no field `{}`",
+                                        input.name(),
+                                        name
+                                    ),
+                                    None,
+                                    "",
+                                );
+                                is_valid = false;
+                            }
+                        }
+
+                        let mut fields = IndexMap::new();
+                        for definition in input.fields() {
+                            match object.get(definition.name()) {
+                                Some(value) => {
+                                    self.path.push(format!(".{}", definition.name()));
+                                    match self.json(definition.ty(), value) {
+                                        Some(value) => {
+                                            fields.insert(definition.name().to_string(), value);
+                                        }
+                                        None => is_valid = false,
+                                    }
+                                    self.path.pop();
+                                }
+                                None => {
+                                    if let Some(default) = definition.default_value() {
+                                        fields
+                                            .insert(definition.name().to_string(), default.clone());
+                                    } else if definition.ty().is_non_null() {
+                                        self.error(
+                                            format!(
+                                                "the required field `{}.{}` is not provided",
+                                                input.name(),
+                                                definition.name()
+                                            ),
+                                            None,
+                                            "",
+                                        );
+                                        is_valid = false;
+                                    }
+                                }
+                            }
+                        }
+                        is_valid.then(|| Value::Object(fields))
+                    }
+                    _ => {
+                        self.mismatch(ty, describe_json(value), None);
+                        None
+                    }
+                },
+                Some(_) => None,
+            },
+        }
+    }
+
+    fn mismatch(&mut self, ty: &Type, found: &str, range: Option<Range<usize>>) {
+        self.error(
+            format!("expected a value of type `{}`, found {}", ty, found),
+            range,
+            format!("expected `{}`", ty),
+        );
+    }
+
+    /// Record an error. Errors in a variable's JSON value have no range in
+    /// the document, so they name the variable and the path to the value
+    /// instead.
+    fn error(&mut self, message: String, range: Option<Range<usize>>, label: impl Into<String>) {
+        let message = match &self.variable {
+            Some(variable) if self.path.is_empty() => {
+                format!("invalid value for variable `${}`: {}", variable, message)
+            }
+            Some(variable) => format!(
+                "invalid value for variable `${}` at `{}`: {}",
+                variable,
+                self.path.concat().trim_start_matches('.'),
+                message
+            ),
+            None => message,
+        };
+        let diagnostic = Diagnostic::new(message);
+        self.errors.push(match range {
+            Some(range) => diagnostic.label(range, label),
+            None => diagnostic,
+        });
+    }
+}
+
+fn json_to_value(value: &serde_json::Value) -> Value {
+    use serde_json::Value as Json;
+
+    match value {
+        Json::Null => Value::Null,
+        Json::Bool(boolean) => Value::Boolean(*boolean),
+        Json::Number(number) => match number.as_i64() {
+            Some(int) => Value::Int(int),
+            None => Value::Float(number.as_f64().unwrap_or_default()),
+        },
+        Json::String(string) => Value::String(string.clone()),
+        Json::Array(items) => Value::List(items.iter().map(json_to_value).collect()),
+        Json::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(name, value)| (name.clone(), json_to_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn describe(value: &ast::Value) -> &'static str {
+    match value {
+        ast::Value::Variable(_) => "a variable",
+        ast::Value::StringValue(_) => "a string",
+        ast::Value::FloatValue(_) => "a float",
+        ast::Value::IntValue(_) => "an integer",
+        ast::Value::BooleanValue(_) => "a boolean",
+        ast::Value::NullValue(_) => "null",
+        ast::Value::EnumValue(_) => "an enum value",
+        ast::Value::ListValue(_) => "a list",
+        ast::Value::ObjectValue(_) => "an input object",
+    }
+}
+
+fn describe_json(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "a list",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::{ast::AstNode, Parser};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+enum Size { SMALL LARGE }
+scalar JSON
+input Filter {
+  size: Size!
+  limit: Int = 10
+  tags: [String!]
+  ids: [ID]
+  extra: JSON
+}
+type Query { shirts(filter: Filter, first: Int!, after: ID): [String] }
+"#;
+
+    fn operation(document: &ast::Document) -> ast::OperationDefinition {
+        document
+            .definitions()
+            .find_map(|def| match def {
+                ast::Definition::OperationDefinition(op) => Some(op),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn messages(errors: Vec<Diagnostic>) -> Vec<String> {
+        errors.iter().map(|e| e.message().to_string()).collect()
+    }
+
+    #[test]
+    fn it_coerces_json_variables() {
+        let input = format!(
+            "{}{}",
+            SCHEMA, "query Q($filter: Filter!, $first: Int = 5, $float: Float) { a }"
+        );
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+
+        let values = json!({
+            "filter": {
+                "size": "LARGE",
+                "tags": "cotton",
+                "ids": [1, "b"],
+                "extra": { "any": [1.5, null] }
+            },
+            "float": 1
+        });
+        let variables =
+            coerce_variables(&schema, &operation(&document), values.as_object().unwrap()).unwrap();
+
+        assert_eq!(
+            variables["filter"].to_string(),
+            r#"{ size: LARGE, limit: 10, tags: ["cotton"], ids: ["1", "b"], extra: { any: [1.5, null] } }"#
+        );
+        assert_eq!(variables["first"], Value::Int(5));
+        assert_eq!(variables["float"], Value::Float(1.0));
+    }
+
+    #[test]
+    fn it_reports_invalid_json_variables() {
+        let input = format!(
+            "{}{}",
+            SCHEMA, "query Q($filter: Filter!, $first: Int!, $query: Query) { a }"
+        );
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+
+        let values = json!({
+            "filter": { "size": "HUGE", "tags": ["a", null, 1], "unknown": true },
+        });
+        let errors = coerce_variables(&schema, &operation(&document), values.as_object().unwrap())
+            .unwrap_err();
+        assert_eq!(
+            messages(errors),
+            [
+                "invalid value for variable `$filter`: the input object `Filter` has no field `unknown`",
+                "invalid value for variable `$filter` at `size`: the enum `Size` has no value `HUGE`",
+                "invalid value for variable `$filter` at `tags[1]`: expected a value of type `String!`, found null",
+                "invalid value for variable `$filter` at `tags[2]`: expected a value of type `String`, found a number",
+                "variable `$first` of required type `Int!` was not provided",
+                "variable `$query` has type `Query`, which is not an input type",
+            ]
+        );
+
+        let values = json!({ "filter": null, "first": 2147483648i64 });
+        let errors = coerce_variables(&schema, &operation(&document), values.as_object().unwrap())
+            .unwrap_err();
+        assert_eq!(
+            messages(errors),
+            [
+                "invalid value for variable `$filter`: expected a value of type `Filter!`, found null",
+                "invalid value for variable `$first`: Int cannot represent non 32-bit signed integer value: 2147483648",
+                "variable `$query` has type `Query`, which is not an input type",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_coerces_arguments() {
+        let input = format!(
+            "{}{}",
+            SCHEMA,
+            r#"query Q($size: Size, $tag: String) { shirts(filter: { size: $size, tags: [$tag, "b"] }, first: 1.5, after: 3) }"#
+        );
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        let field = document
+            .syntax()
+            .descendants()
+            .find_map(ast::Field::cast)
+            .unwrap();
+        let definitions = schema
+            .type_definition("Query")
+            .unwrap()
+            .field("shirts")
+            .unwrap()
+            .arguments();
+
+        let mut variables = IndexMap::new();
+        variables.insert("size".to_string(), Value::Enum("SMALL".into()));
+        let errors = coerce_arguments(&schema, definitions, field.arguments().as_ref(), &variables)
+            .unwrap_err();
+        assert_eq!(
+            messages(errors),
+            [
+                // `$tag` is not provided, so it coerces to null in the list.
+                "expected a value of type `String!`, found null",
+                "expected a value of type `Int`, found a float",
+            ]
+        );
+
+        variables.insert("tag".to_string(), Value::String("a".into()));
+        let input = input.replace("1.5", "1");
+        let document = Parser::new(&input).parse().document();
+        let field = document
+            .syntax()
+            .descendants()
+            .find_map(ast::Field::cast)
+            .unwrap();
+        let arguments =
+            coerce_arguments(&schema, definitions, field.arguments().as_ref(), &variables).unwrap();
+        assert_eq!(
+            arguments["filter"].to_string(),
+            r#"{ size: SMALL, limit: 10, tags: ["a", "b"] }"#
+        );
+        assert_eq!(arguments["first"], Value::Int(1));
+        assert_eq!(arguments["after"], Value::String("3".into()));
+
+        variables.remove("size");
+        let errors = coerce_arguments(&schema, definitions, field.arguments().as_ref(), &variables)
+            .unwrap_err();
+        assert_eq!(
+            messages(errors),
+            ["the required field `Filter.size` is not provided"]
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! Static analysis of the complexity of operations: their depth, the number
+//! of fields and aliases they select, and an estimate of their cost.
+//!
+//! Servers can reject operations above a limit before executing them, which
+//! protects them against expensive or malicious queries. The analysis expands
+//! fragment spreads wherever they are used, so a fragment spread twice counts
+//! twice.
+//!
+//! ## Cost
+//! The cost of a field is its weight plus the cost of its selection set,
+//! multiplied by the size of the list it returns. By default, fields
+//! returning an object, interface or union weigh 1, and fields returning a
+//! scalar or an enum weigh 0. Weights can be configured per field or per type
+//! with [`Analyzer::field_weight`] and [`Analyzer::type_weight`], or with a
+//! `@cost(weight: Int!)` directive in the schema's SDL, read by
+//! [`Analyzer::cost_directives`]. The size of a list is the value of the
+//! field's `first` or `last` argument, or their default value, or 1 when it
+//! has none.
+use std::collections::{HashMap, HashSet};
+
+use apollo_parser::ast;
+use serde_json::{Map, Value as Json};
+
+use crate::{schema::FieldDefinition, values, Schema, Value};
+
+/// The arguments giving the size of a list field.
+const LIST_SIZE_ARGUMENTS: [&str; 2] = ["first", "last"];
+
+/// The complexity of an operation, computed by an [`Analyzer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Complexity {
+    pub(crate) depth: usize,
+    pub(crate) field_count: usize,
+    pub(crate) alias_count: usize,
+    pub(crate) cost: u64,
+}
+
+impl Complexity {
+    /// Get the depth of the operation: the largest number of nested fields.
+    /// Root fields are at depth 1.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the number of fields selected, including `__typename`.
+    pub fn field_count(&self) -> usize {
+        self.field_count
+    }
+
+    /// Get the number of aliased fields.
+    pub fn alias_count(&self) -> usize {
+        self.alias_count
+    }
+
+    /// Get the estimated cost of the operation.
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+}
+
+/// Computes the [`Complexity`] of operations against a schema.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{complexity::Analyzer, Schema};
+/// use apollo_parser::{ast, Parser};
+///
+/// let sdl = "
+/// directive @cost(weight: Int!) on FIELD_DEFINITION | OBJECT | SCALAR | ENUM
+///
+/// type Query { users(first: Int): [User] }
+/// type User { name: String avatar: String @cost(weight: 5) friends(first: Int): [User] }
+/// ";
+/// let sdl = Parser::new(sdl).parse().document();
+/// let schema = Schema::new(&sdl);
+/// let mut analyzer = Analyzer::new(&schema);
+/// analyzer.cost_directives(&sdl);
+///
+/// let document = Parser::new("{ users(first: 10) { name avatar friends(first: 2) { me: name } } }")
+///     .parse()
+///     .document();
+/// let operation = document
+///     .definitions()
+///     .find_map(|def| match def {
+///         ast::Definition::OperationDefinition(operation) => Some(operation),
+///         _ => None,
+///     })
+///     .unwrap();
+///
+/// let complexity = analyzer.analyze(&document, &operation, &Default::default());
+/// assert_eq!(complexity.depth(), 3);
+/// assert_eq!(complexity.field_count(), 5);
+/// assert_eq!(complexity.alias_count(), 1);
+/// // 10 users, each weighing 1 plus 5 for their avatar and 2 for their friends.
+/// assert_eq!(complexity.cost(), 80);
+/// ```
+pub struct Analyzer<'a> {
+    schema: &'a Schema,
+    field_weights: HashMap<(String, String), u64>,
+    type_weights: HashMap<String, u64>,
+}
+
+impl<'a> Analyzer<'a> {
+    /// Create a new instance of `Analyzer`.
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            field_weights: HashMap::new(),
+            type_weights: HashMap::new(),
+        }
+    }
+
+    /// Set the weight of the field `field` of the type `ty`.
+    pub fn field_weight(&mut self, ty: &str, field: &str, weight: u64) {
+        self.field_weights
+            .insert((ty.to_string(), field.to_string()), weight);
+    }
+
+    /// Set the weight of every field returning the type `ty`, unless the
+    /// field has a weight of its own.
+    pub fn type_weight(&mut self, ty: &str, weight: u64) {
+        self.type_weights.insert(ty.to_string(), weight);
+    }
+
+    /// Read weights from the `@cost(weight: ...)` directives applied to the
+    /// field definitions and type definitions of `document`, including
+    /// extensions.
+    pub fn cost_directives(&mut self, document: &ast::Document) {
+        for def in document.definitions() {
+            let (name, directives, fields) = match def {
+                ast::Definition::ObjectTypeDefinition(def) => {
+                    (def.name(), def.directives(), def.fields_definition())
+                }
+                ast::Definition::ObjectTypeExtension(def) => {
+                    (def.name(), def.directives(), def.fields_definition())
+                }
+                ast::Definition::InterfaceTypeDefinition(def) => {
+                    (def.name(), def.directives(), def.fields_definition())
+                }
+                ast::Definition::InterfaceTypeExtension(def) => {
+                    (def.name(), def.directives(), def.fields_definition())
+                }
+                ast::Definition::ScalarTypeDefinition(def) => (def.name(), def.directives(), None),
+                ast::Definition::ScalarTypeExtension(def) => (def.name(), def.directives(), None),
+                ast::Definition::EnumTypeDefinition(def) => (def.name(), def.directives(), None),
+                ast::Definition::EnumTypeExtension(def) => (def.name(), def.directives(), None),
+                ast::Definition::UnionTypeDefinition(def) => (def.name(), def.directives(), None),
+                ast::Definition::UnionTypeExtension(def) => (def.name(), def.directives(), None),
+                _ => continue,
+            };
+            let name = match name {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            if let Some(weight) = cost(directives) {
+                self.type_weight(&name, weight);
+            }
+            for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+                let weight = cost(field.directives());
+                if let (Some(field), Some(weight)) = (field.name(), weight) {
+                    self.field_weight(&name, &field.text(), weight);
+                }
+            }
+        }
+    }
+
+    /// Compute the complexity of `operation`, a definition of `document`.
+    ///
+    /// `variables` are the JSON values of the operation's variables; they are
+    /// only used for the size of lists. Variables that are not provided take
+    /// their default value.
+    pub fn analyze(
+        &self,
+        document: &ast::Document,
+        operation: &ast::OperationDefinition,
+        variables: &Map<String, Json>,
+    ) -> Complexity {
+        let fragments = document
+            .definitions()
+            .filter_map(|def| match def {
+                ast::Definition::FragmentDefinition(fragment) => {
+                    let name = fragment.fragment_name()?.name()?.text().to_string();
+                    Some((name, fragment))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut variables = variables.clone();
+        for def in operation
+            .variable_definitions()
+            .iter()
+            .flat_map(|defs| defs.variable_definitions())
+        {
+            let name = def.variable().and_then(|variable| variable.name());
+            let default = def
+                .default_value()
+                .and_then(|default| default.value())
+                .and_then(values::value);
+            if let (Some(name), Some(Value::Int(default))) = (name, default) {
+                variables
+                    .entry(name.text().to_string())
+                    .or_insert_with(|| default.into());
+            }
+        }
+
+        let mut analysis = Analysis {
+            analyzer: self,
+            fragments,
+            variables,
+            complexity: Complexity::default(),
+            spreads: HashSet::new(),
+        };
+        let root_type = crate::validation::root_type(self.schema, operation);
+        if let Some(selection_set) = operation.selection_set() {
+            analysis.complexity.cost = analysis.selection_set(&selection_set, root_type, 1);
+        }
+        analysis.complexity
+    }
+}
+
+struct Analysis<'a> {
+    analyzer: &'a Analyzer<'a>,
+    fragments: HashMap<String, ast::FragmentDefinition>,
+    variables: Map<String, Json>,
+    complexity: Complexity,
+    /// The fragments being expanded, to stop at cycles.
+    spreads: HashSet<String>,
+}
+
+impl<'a> Analysis<'a> {
+    /// Analyze the fields of `selection_set`, selected on `parent` at
+    /// `depth`, and return their cost.
+    fn selection_set(
+        &mut self,
+        selection_set: &ast::SelectionSet,
+        parent: Option<&str>,
+        depth: usize,
+    ) -> u64 {
+        let mut cost = 0u64;
+        for selection in selection_set.selections() {
+            let selection_cost = match selection {
+                ast::Selection::Field(field) => self.field(&field, parent, depth),
+                ast::Selection::FragmentSpread(spread) => {
+                    let name = match spread.fragment_name().and_then(|n| n.name()) {
+                        Some(name) => name.text().to_string(),
+                        None => continue,
+                    };
+                    let fragment = match self.fragments.get(&name) {
+                        Some(fragment) => fragment.clone(),
+                        None => continue,
+                    };
+                    if !self.spreads.insert(name.clone()) {
+                        continue;
+                    }
+                    let parent =
+                        type_condition(fragment.type_condition()).or(parent.map(String::from));
+                    let cost = fragment.selection_set().map_or(0, |selection_set| {
+                        self.selection_set(&selection_set, parent.as_deref(), depth)
+                    });
+                    self.spreads.remove(&name);
+                    cost
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    let parent =
+                        type_condition(inline.type_condition()).or(parent.map(String::from));
+                    inline.selection_set().map_or(0, |selection_set| {
+                        self.selection_set(&selection_set, parent.as_deref(), depth)
+                    })
+                }
+            };
+            cost = cost.saturating_add(selection_cost);
+        }
+        cost
+    }
+
+    /// Analyze `field`, selected on `parent` at `depth`, and return its cost.
+    fn field(&mut self, field: &ast::Field, parent: Option<&str>, depth: usize) -> u64 {
+        self.complexity.field_count += 1;
+        self.complexity.depth = self.complexity.depth.max(depth);
+        if field.alias().is_some() {
+            self.complexity.alias_count += 1;
+        }
+
+        let schema = self.analyzer.schema;
+        let name = match field.name() {
+            Some(name) => name.text().to_string(),
+            None => return 0,
+        };
+        let definition = parent.and_then(|parent| schema.field_definition(parent, &name));
+        let ty = definition.map(|definition| definition.ty());
+        let field_weight = parent.and_then(|parent| {
+            self.analyzer
+                .field_weights
+                .get(&(parent.to_string(), name.clone()))
+        });
+        let type_weight = ty.and_then(|ty| self.analyzer.type_weights.get(ty.name()));
+        let weight = match (field_weight, type_weight) {
+            (Some(weight), _) | (None, Some(weight)) => *weight,
+            (None, None) => {
+                let is_composite = ty
+                    .and_then(|ty| schema.type_definition(ty.name()))
+                    .map_or(false, |ty| ty.is_composite());
+                // A field that is not defined is assumed to select objects
+                // if it has a selection set.
+                u64::from(is_composite || (ty.is_none() && field.selection_set().is_some()))
+            }
+        };
+
+        let children = field.selection_set().map_or(0, |selection_set| {
+            self.selection_set(&selection_set, ty.map(|ty| ty.name()), depth + 1)
+        });
+        let size = if ty.map_or(true, |ty| ty.is_list()) {
+            self.list_size(field, definition)
+        } else {
+            1
+        };
+        weight.saturating_add(children).saturating_mul(size)
+    }
+
+    /// Get the size of the list a field returns from its `first` or `last`
+    /// argument, or their default values.
+    fn list_size(&self, field: &ast::Field, definition: Option<&FieldDefinition>) -> u64 {
+        let arguments = field.arguments();
+        let mut sizes: HashMap<String, u64> = HashMap::new();
+        for argument in definition.iter().flat_map(|def| def.arguments()) {
+            if let Some(Value::Int(size)) = argument.default_value() {
+                sizes.insert(
+                    argument.name().to_string(),
+                    u64::try_from(*size).unwrap_or(0),
+                );
+            }
+        }
+        for argument in arguments.iter().flat_map(|arguments| arguments.arguments()) {
+            let name = match argument.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let size = match argument.value().and_then(values::value) {
+                Some(Value::Int(size)) => u64::try_from(size).ok(),
+                Some(Value::Variable(variable)) => {
+                    self.variables.get(&variable).and_then(Json::as_u64)
+                }
+                _ => None,
+            };
+            match size {
+                Some(size) => sizes.insert(name, size),
+                None => sizes.remove(&name),
+            };
+        }
+        LIST_SIZE_ARGUMENTS
+            .iter()
+            .filter_map(|name| sizes.get(*name))
+            .max()
+            .copied()
+            .unwrap_or(1)
+    }
+}
+
+fn type_condition(type_condition: Option<ast::TypeCondition>) -> Option<String> {
+    Some(type_condition?.named_type()?.name()?.text().to_string())
+}
+
+/// Get the weight of a `@cost(weight: ...)` directive.
+fn cost(directives: Option<ast::Directives>) -> Option<u64> {
+    let directive = directives?
+        .directives()
+        .find(|directive| directive.name().map_or(false, |name| name.text() == "cost"))?;
+    let argument = directive.arguments()?.arguments().find(|argument| {
+        argument
+            .name()
+            .map_or(false, |name| name.text() == "weight")
+    })?;
+    match values::value(argument.value()?)? {
+        Value::Int(weight) => u64::try_from(weight).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+directive @cost(weight: Int!) on FIELD_DEFINITION | OBJECT | SCALAR | ENUM | INTERFACE | UNION
+
+type Query {
+  node(id: ID!): Node
+  search(first: Int, last: Int): [Result!]!
+  me: User
+}
+interface Node { id: ID! }
+type User implements Node {
+  id: ID!
+  name: String
+  posts(first: Int = 5): [Post]
+}
+type Post implements Node @cost(weight: 3) {
+  id: ID!
+  body: Body
+}
+scalar Body @cost(weight: 2)
+union Result = User | Post
+extend type User { score: Int @cost(weight: 10) }
+"#;
+
+    fn analyze(query: &str, variables: Json) -> Complexity {
+        let input = format!("{}{}", SCHEMA, query);
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        let mut analyzer = Analyzer::new(&schema);
+        analyzer.cost_directives(&document);
+        let operation = document
+            .definitions()
+            .find_map(|def| match def {
+                ast::Definition::OperationDefinition(operation) => Some(operation),
+                _ => None,
+            })
+            .unwrap();
+        analyzer.analyze(&document, &operation, variables.as_object().unwrap())
+    }
+
+    #[test]
+    fn it_counts_depth_fields_and_aliases() {
+        let complexity = analyze(
+            r#"
+query {
+  me { ...User a: name b: name }
+  node(id: "1") { ... on User { ...User } }
+}
+fragment User on User { id posts { id } }
+"#,
+            json!({}),
+        );
+        assert_eq!(complexity.depth(), 3);
+        assert_eq!(complexity.field_count(), 10);
+        assert_eq!(complexity.alias_count(), 2);
+    }
+
+    #[test]
+    fn it_estimates_costs() {
+        // `me` weighs 1, `posts` returns 5 posts by default, each weighing 3
+        // plus 2 for their body, and `score` weighs 10.
+        assert_eq!(
+            analyze("{ me { name score posts { body } } }", json!({})).cost(),
+            36
+        );
+        assert_eq!(
+            analyze(
+                "query($n: Int = 5) { search(first: $n, last: 2) { __typename } }",
+                json!({ "n": 20 })
+            )
+            .cost(),
+            20
+        );
+        assert_eq!(
+            analyze(
+                "query($n: Int = 5) { search(first: $n) { ... on Post { body } } }",
+                json!({})
+            )
+            .cost(),
+            15
+        );
+        assert_eq!(
+            analyze(
+                "{ me { posts(first: 2) { ...Post } } } fragment Post on Post { body }",
+                json!({})
+            )
+            .cost(),
+            11
+        );
+    }
+
+    #[test]
+    fn it_stops_at_fragment_cycles() {
+        let complexity = analyze(
+            "{ me { ...A } } fragment A on User { name ...B } fragment B on User { id ...A }",
+            json!({}),
+        );
+        assert_eq!(complexity.field_count(), 3);
+        assert_eq!(complexity.depth(), 2);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt, ops::Range};
+
+use apollo_parser::{ast::AstNode, SyntaxKind, SyntaxNode};
+
+/// A `Diagnostic` produced while building a [`Schema`](crate::Schema) or
+/// validating a document against it.
+///
+/// Diagnostics are collected rather than returned early, so a single pass
+/// reports as many problems as it can find. Each diagnostic carries one or
+/// more labelled byte ranges pointing into the source it was produced from.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{validate, Schema};
+/// use apollo_parser::Parser;
+///
+/// let input = "
+/// type Query { name: String }
+///
+/// query { ...missing }
+/// ";
+/// let document = Parser::new(input).parse().document();
+/// let schema = Schema::new(&document);
+///
+/// let diagnostics = validate(&schema, &document);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].message(), "unknown fragment `missing`");
+/// assert_eq!(&input[diagnostics[0].labels()[0].range()], "...missing");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub(crate) message: String,
+    pub(crate) labels: Vec<Label>,
+    pub(crate) help: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a new instance of `Diagnostic`.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            labels: Vec::new(),
+            help: None,
+        }
+    }
+
+    /// Add a label pointing at `range` in the source.
+    pub fn label<S: Into<String>>(self, range: Range<usize>, text: S) -> Self {
+        self.label_in(0, range, text)
+    }
+
+    /// Add a label pointing at `range` in one of several documents.
+    pub(crate) fn label_in<S: Into<String>>(
+        mut self,
+        document: usize,
+        range: Range<usize>,
+        text: S,
+    ) -> Self {
+        self.labels.push(Label {
+            document,
+            range,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// Add a help message suggesting how to fix the problem.
+    pub fn help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Get a reference to the diagnostic's message.
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+
+    /// Get a reference to the diagnostic's labels. The first label points at
+    /// the primary location of the problem.
+    pub fn labels(&self) -> &[Label] {
+        self.labels.as_ref()
+    }
+
+    /// Get a reference to the diagnostic's help message, if any.
+    pub fn help_message(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+}
+
+impl fmt::Debug for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DIAGNOSTIC {:?}", self.message)?;
+        for label in &self.labels {
+            write!(f, " {:?}", label)?;
+        }
+        if let Some(help) = &self.help {
+            write!(f, " help: {:?}", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<apollo_parser::Error> for Diagnostic {
+    fn from(err: apollo_parser::Error) -> Self {
+        let start = err.index();
+        let end = if err.data() == "EOF" {
+            start
+        } else {
+            start + err.data().len()
+        };
+        Diagnostic::new(err.message()).label(start..end, err.data())
+    }
+}
+
+/// A byte range in the source, annotated with a short explanation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Label {
+    pub(crate) document: usize,
+    pub(crate) range: Range<usize>,
+    pub(crate) text: String,
+}
+
+impl Label {
+    /// Get the index of the document the label's range points into, when a
+    /// [`SchemaBuilder`](crate::SchemaBuilder) was given several documents.
+    /// Labels of diagnostics about a single document are always in document
+    /// `0`.
+    pub fn document(&self) -> usize {
+        self.document
+    }
+
+    /// Get the label's byte range in the source.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Get a reference to the label's text.
+    pub fn text(&self) -> &str {
+        self.text.as_ref()
+    }
+}
+
+impl fmt::Debug for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.document != 0 {
+            write!(f, "#{}", self.document)?;
+        }
+        write!(
+            f,
+            "@{}:{} {:?}",
+            self.range.start, self.range.end, self.text
+        )
+    }
+}
+
+/// Byte range of a node in the source, excluding the whitespace, commas and
+/// comments the parser attaches to the end of nodes.
+pub(crate) fn range<N: AstNode>(node: &N) -> Range<usize> {
+    syntax_range(node.syntax())
+}
+
+pub(crate) fn syntax_range(node: &SyntaxNode) -> Range<usize> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !is_trivia(token.kind()));
+
+    match tokens.next() {
+        Some(first) => {
+            let start = first.text_range().start();
+            let end = tokens
+                .last()
+                .map_or(first.text_range().end(), |last| last.text_range().end());
+            start.into()..end.into()
+        }
+        None => {
+            let range = node.text_range();
+            range.start().into()..range.start().into()
+        }
+    }
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+    )
+}
@@ -0,0 +1,948 @@
+//! Comparison of two versions of a schema, to find the changes that break
+//! existing clients before they are deployed.
+//!
+//! Every [`Change`] has a [`Criticality`], following the conventions of
+//! [graphql-inspector]:
+//! - **breaking** changes make valid operations invalid, or change the shape
+//!   of responses, e.g. removing a field or making an argument required;
+//! - **dangerous** changes keep operations valid, but can change how
+//!   existing clients behave, e.g. adding an enum value that a client's
+//!   exhaustive `switch` does not handle, or changing a default value;
+//! - **non-breaking** changes cannot affect existing clients, e.g. adding a
+//!   type or a field.
+//!
+//! Changes are serializable, so registries can store and report them.
+//!
+//! [graphql-inspector]: https://the-guild.dev/graphql/inspector/docs/essentials/diff
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    schema::{FieldDefinition, InputValueDefinition},
+    DirectiveDefinition, Schema, Type, TypeDefinition, Value,
+};
+
+/// Compare the `old` and `new` versions of a schema, and list the changes
+/// between them.
+///
+/// Changes to types and directives built into every schema are not listed.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{diff, Schema};
+/// use apollo_parser::Parser;
+///
+/// let old = Parser::new("type Query { me: String name: String }").parse().document();
+/// let new = Parser::new("type Query { me: String! age: Int }").parse().document();
+///
+/// let changes = diff::diff(&Schema::new(&old), &Schema::new(&new));
+/// let changes: Vec<_> = changes
+///     .iter()
+///     .map(|change| (change.path(), change.criticality()))
+///     .collect();
+/// assert_eq!(
+///     changes,
+///     [
+///         ("Query.me", diff::Criticality::NonBreaking),
+///         ("Query.name", diff::Criticality::Breaking),
+///         ("Query.age", diff::Criticality::NonBreaking),
+///     ]
+/// );
+/// ```
+pub fn diff(old: &Schema, new: &Schema) -> Vec<Change> {
+    let mut diff = Diff {
+        changes: Vec::new(),
+    };
+    diff.schema(old, new);
+    diff.changes
+}
+
+/// A change between two versions of a schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub(crate) kind: ChangeKind,
+    pub(crate) criticality: Criticality,
+    pub(crate) path: String,
+    pub(crate) message: String,
+}
+
+impl Change {
+    /// Get the kind of change.
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// Get how the change affects existing clients.
+    pub fn criticality(&self) -> Criticality {
+        self.criticality
+    }
+
+    /// Get the path of the changed schema element, e.g. `Query.user.id` for
+    /// an argument, `Size.LARGE` for an enum value or `@tag.name` for a
+    /// directive's argument.
+    pub fn path(&self) -> &str {
+        self.path.as_ref()
+    }
+
+    /// Get a description of the change.
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+
+    /// Check whether the change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.criticality, self.message)
+    }
+}
+
+/// How a [`Change`] affects existing clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Criticality {
+    /// Existing operations can become invalid, or get differently shaped
+    /// responses.
+    Breaking,
+    /// Existing operations stay valid, but clients may behave differently.
+    Dangerous,
+    /// Existing clients are not affected.
+    NonBreaking,
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let criticality = match self {
+            Criticality::Breaking => "breaking",
+            Criticality::Dangerous => "dangerous",
+            Criticality::NonBreaking => "non-breaking",
+        };
+        write!(f, "{}", criticality)
+    }
+}
+
+/// The kinds of [`Change`]s, named as in graphql-inspector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeKind {
+    SchemaQueryTypeChanged,
+    SchemaMutationTypeChanged,
+    SchemaSubscriptionTypeChanged,
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    TypeDescriptionChanged,
+    ObjectTypeInterfaceAdded,
+    ObjectTypeInterfaceRemoved,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldDescriptionChanged,
+    FieldDeprecationAdded,
+    FieldDeprecationRemoved,
+    FieldDeprecationReasonChanged,
+    FieldArgumentAdded,
+    FieldArgumentRemoved,
+    FieldArgumentTypeChanged,
+    FieldArgumentDefaultChanged,
+    FieldArgumentDescriptionChanged,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueDescriptionChanged,
+    EnumValueDeprecationAdded,
+    EnumValueDeprecationRemoved,
+    EnumValueDeprecationReasonChanged,
+    InputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    InputFieldDescriptionChanged,
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveDescriptionChanged,
+    DirectiveRepeatableAdded,
+    DirectiveRepeatableRemoved,
+    DirectiveLocationAdded,
+    DirectiveLocationRemoved,
+    DirectiveArgumentAdded,
+    DirectiveArgumentRemoved,
+    DirectiveArgumentTypeChanged,
+    DirectiveArgumentDefaultValueChanged,
+    DirectiveArgumentDescriptionChanged,
+}
+
+/// The changes of input values, which are either arguments of fields,
+/// arguments of directives or fields of input objects.
+struct InputValueKinds {
+    added: ChangeKind,
+    removed: ChangeKind,
+    type_changed: ChangeKind,
+    default_changed: ChangeKind,
+    description_changed: ChangeKind,
+    /// What the input values are, for messages.
+    noun: &'static str,
+    /// The criticality of adding an optional input value.
+    optional_added: Criticality,
+}
+
+const FIELD_ARGUMENTS: InputValueKinds = InputValueKinds {
+    added: ChangeKind::FieldArgumentAdded,
+    removed: ChangeKind::FieldArgumentRemoved,
+    type_changed: ChangeKind::FieldArgumentTypeChanged,
+    default_changed: ChangeKind::FieldArgumentDefaultChanged,
+    description_changed: ChangeKind::FieldArgumentDescriptionChanged,
+    noun: "argument",
+    optional_added: Criticality::Dangerous,
+};
+
+const DIRECTIVE_ARGUMENTS: InputValueKinds = InputValueKinds {
+    added: ChangeKind::DirectiveArgumentAdded,
+    removed: ChangeKind::DirectiveArgumentRemoved,
+    type_changed: ChangeKind::DirectiveArgumentTypeChanged,
+    default_changed: ChangeKind::DirectiveArgumentDefaultValueChanged,
+    description_changed: ChangeKind::DirectiveArgumentDescriptionChanged,
+    noun: "argument",
+    optional_added: Criticality::NonBreaking,
+};
+
+const INPUT_FIELDS: InputValueKinds = InputValueKinds {
+    added: ChangeKind::InputFieldAdded,
+    removed: ChangeKind::InputFieldRemoved,
+    type_changed: ChangeKind::InputFieldTypeChanged,
+    default_changed: ChangeKind::InputFieldDefaultValueChanged,
+    description_changed: ChangeKind::InputFieldDescriptionChanged,
+    noun: "input field",
+    optional_added: Criticality::Dangerous,
+};
+
+struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    fn change(&mut self, kind: ChangeKind, criticality: Criticality, path: &str, message: String) {
+        self.changes.push(Change {
+            kind,
+            criticality,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn schema(&mut self, old: &Schema, new: &Schema) {
+        let roots = [
+            (
+                ChangeKind::SchemaQueryTypeChanged,
+                "query",
+                old.query_type(),
+                new.query_type(),
+            ),
+            (
+                ChangeKind::SchemaMutationTypeChanged,
+                "mutation",
+                old.mutation_type(),
+                new.mutation_type(),
+            ),
+            (
+                ChangeKind::SchemaSubscriptionTypeChanged,
+                "subscription",
+                old.subscription_type(),
+                new.subscription_type(),
+            ),
+        ];
+        for (kind, operation, old_root, new_root) in roots {
+            if old_root == new_root {
+                continue;
+            }
+            // Adding a root operation type only adds to what can be queried.
+            let criticality = match old_root {
+                Some(_) => Criticality::Breaking,
+                None => Criticality::NonBreaking,
+            };
+            self.change(
+                kind,
+                criticality,
+                new_root.or(old_root).unwrap_or_default(),
+                format!(
+                    "the {} root operation type changed from {} to {}",
+                    operation,
+                    old_root.map_or("none".to_string(), |ty| format!("`{}`", ty)),
+                    new_root.map_or("none".to_string(), |ty| format!("`{}`", ty)),
+                ),
+            );
+        }
+
+        for old_ty in old.types().filter(|ty| !ty.is_built_in()) {
+            match new.type_definition(old_ty.name()) {
+                Some(new_ty) => self.type_definition(old_ty, new_ty),
+                None => self.change(
+                    ChangeKind::TypeRemoved,
+                    Criticality::Breaking,
+                    old_ty.name(),
+                    format!("the type `{}` was removed", old_ty.name()),
+                ),
+            }
+        }
+        for new_ty in new.types().filter(|ty| !ty.is_built_in()) {
+            if old.type_definition(new_ty.name()).is_none() {
+                self.change(
+                    ChangeKind::TypeAdded,
+                    Criticality::NonBreaking,
+                    new_ty.name(),
+                    format!("the type `{}` was added", new_ty.name()),
+                );
+            }
+        }
+
+        for old_directive in old.directives().filter(|d| !d.is_built_in()) {
+            match new.directive_definition(old_directive.name()) {
+                Some(new_directive) => self.directive_definition(old_directive, new_directive),
+                None => self.change(
+                    ChangeKind::DirectiveRemoved,
+                    Criticality::Breaking,
+                    &format!("@{}", old_directive.name()),
+                    format!("the directive `@{}` was removed", old_directive.name()),
+                ),
+            }
+        }
+        for new_directive in new.directives().filter(|d| !d.is_built_in()) {
+            if old.directive_definition(new_directive.name()).is_none() {
+                self.change(
+                    ChangeKind::DirectiveAdded,
+                    Criticality::NonBreaking,
+                    &format!("@{}", new_directive.name()),
+                    format!("the directive `@{}` was added", new_directive.name()),
+                );
+            }
+        }
+    }
+
+    fn type_definition(&mut self, old: &TypeDefinition, new: &TypeDefinition) {
+        let name = old.name();
+        if old.kind() != new.kind() {
+            self.change(
+                ChangeKind::TypeKindChanged,
+                Criticality::Breaking,
+                name,
+                format!(
+                    "the type `{}` changed from {} to {}",
+                    name,
+                    old.kind(),
+                    new.kind()
+                ),
+            );
+            return;
+        }
+        self.description(
+            ChangeKind::TypeDescriptionChanged,
+            name,
+            &format!("the type `{}`", name),
+            old.description(),
+            new.description(),
+        );
+
+        match (old, new) {
+            (TypeDefinition::Object(old), TypeDefinition::Object(new)) => {
+                self.members(
+                    (ChangeKind::ObjectTypeInterfaceAdded, Criticality::Dangerous),
+                    (
+                        ChangeKind::ObjectTypeInterfaceRemoved,
+                        Criticality::Breaking,
+                    ),
+                    name,
+                    "the interface",
+                    old.implements_interfaces(),
+                    new.implements_interfaces(),
+                );
+                self.fields(name, old.fields(), new.fields());
+            }
+            (TypeDefinition::Interface(old), TypeDefinition::Interface(new)) => {
+                self.members(
+                    (ChangeKind::ObjectTypeInterfaceAdded, Criticality::Dangerous),
+                    (
+                        ChangeKind::ObjectTypeInterfaceRemoved,
+                        Criticality::Breaking,
+                    ),
+                    name,
+                    "the interface",
+                    old.implements_interfaces(),
+                    new.implements_interfaces(),
+                );
+                self.fields(name, old.fields(), new.fields());
+            }
+            (TypeDefinition::Union(old), TypeDefinition::Union(new)) => {
+                self.members(
+                    (ChangeKind::UnionMemberAdded, Criticality::Dangerous),
+                    (ChangeKind::UnionMemberRemoved, Criticality::Breaking),
+                    name,
+                    "the member",
+                    old.members(),
+                    new.members(),
+                );
+            }
+            (TypeDefinition::Enum(old), TypeDefinition::Enum(new)) => {
+                for old_value in old.values() {
+                    let path = format!("{}.{}", name, old_value.value());
+                    let new_value = match new.value(old_value.value()) {
+                        Some(new_value) => new_value,
+                        None => {
+                            self.change(
+                                ChangeKind::EnumValueRemoved,
+                                Criticality::Breaking,
+                                &path,
+                                format!("the enum value `{}` was removed", path),
+                            );
+                            continue;
+                        }
+                    };
+                    self.description(
+                        ChangeKind::EnumValueDescriptionChanged,
+                        &path,
+                        &format!("the enum value `{}`", path),
+                        old_value.description(),
+                        new_value.description(),
+                    );
+                    self.deprecation(
+                        [
+                            ChangeKind::EnumValueDeprecationAdded,
+                            ChangeKind::EnumValueDeprecationRemoved,
+                            ChangeKind::EnumValueDeprecationReasonChanged,
+                        ],
+                        &path,
+                        &format!("the enum value `{}`", path),
+                        old_value.deprecation_reason(),
+                        new_value.deprecation_reason(),
+                    );
+                }
+                for new_value in new.values() {
+                    if old.value(new_value.value()).is_none() {
+                        let path = format!("{}.{}", name, new_value.value());
+                        self.change(
+                            ChangeKind::EnumValueAdded,
+                            Criticality::Dangerous,
+                            &path,
+                            format!("the enum value `{}` was added", path),
+                        );
+                    }
+                }
+            }
+            (TypeDefinition::InputObject(old), TypeDefinition::InputObject(new)) => {
+                self.input_values(&INPUT_FIELDS, name, old.fields(), new.fields());
+            }
+            _ => {}
+        }
+    }
+
+    /// Compare the interfaces a type implements, or the members of a union.
+    fn members(
+        &mut self,
+        (added, added_criticality): (ChangeKind, Criticality),
+        (removed, removed_criticality): (ChangeKind, Criticality),
+        name: &str,
+        noun: &str,
+        old: &[String],
+        new: &[String],
+    ) {
+        for member in old.iter().filter(|member| !new.contains(member)) {
+            self.change(
+                removed,
+                removed_criticality,
+                name,
+                format!("{} `{}` was removed from `{}`", noun, member, name),
+            );
+        }
+        for member in new.iter().filter(|member| !old.contains(member)) {
+            self.change(
+                added,
+                added_criticality,
+                name,
+                format!("{} `{}` was added to `{}`", noun, member, name),
+            );
+        }
+    }
+
+    fn fields(&mut self, parent: &str, old: &[FieldDefinition], new: &[FieldDefinition]) {
+        for old_field in old {
+            let path = format!("{}.{}", parent, old_field.name());
+            let new_field = match new.iter().find(|field| field.name() == old_field.name()) {
+                Some(new_field) => new_field,
+                None => {
+                    let deprecated = if old_field.is_deprecated() {
+                        "deprecated "
+                    } else {
+                        ""
+                    };
+                    self.change(
+                        ChangeKind::FieldRemoved,
+                        Criticality::Breaking,
+                        &path,
+                        format!("the {}field `{}` was removed", deprecated, path),
+                    );
+                    continue;
+                }
+            };
+            if old_field.ty() != new_field.ty() {
+                let criticality = if is_safe_output_change(old_field.ty(), new_field.ty()) {
+                    Criticality::NonBreaking
+                } else {
+                    Criticality::Breaking
+                };
+                self.change(
+                    ChangeKind::FieldTypeChanged,
+                    criticality,
+                    &path,
+                    format!(
+                        "the type of the field `{}` changed from `{}` to `{}`",
+                        path,
+                        old_field.ty(),
+                        new_field.ty()
+                    ),
+                );
+            }
+            self.description(
+                ChangeKind::FieldDescriptionChanged,
+                &path,
+                &format!("the field `{}`", path),
+                old_field.description(),
+                new_field.description(),
+            );
+            self.deprecation(
+                [
+                    ChangeKind::FieldDeprecationAdded,
+                    ChangeKind::FieldDeprecationRemoved,
+                    ChangeKind::FieldDeprecationReasonChanged,
+                ],
+                &path,
+                &format!("the field `{}`", path),
+                old_field.deprecation_reason(),
+                new_field.deprecation_reason(),
+            );
+            self.input_values(
+                &FIELD_ARGUMENTS,
+                &path,
+                old_field.arguments(),
+                new_field.arguments(),
+            );
+        }
+        for new_field in new {
+            if !old.iter().any(|field| field.name() == new_field.name()) {
+                let path = format!("{}.{}", parent, new_field.name());
+                self.change(
+                    ChangeKind::FieldAdded,
+                    Criticality::NonBreaking,
+                    &path,
+                    format!("the field `{}` was added", path),
+                );
+            }
+        }
+    }
+
+    fn input_values(
+        &mut self,
+        kinds: &InputValueKinds,
+        parent: &str,
+        old: &[InputValueDefinition],
+        new: &[InputValueDefinition],
+    ) {
+        for old_value in old {
+            let path = format!("{}.{}", parent, old_value.name());
+            let subject = format!("the {} `{}`", kinds.noun, path);
+            let new_value = match new.iter().find(|value| value.name() == old_value.name()) {
+                Some(new_value) => new_value,
+                None => {
+                    self.change(
+                        kinds.removed,
+                        Criticality::Breaking,
+                        &path,
+                        format!("{} was removed", subject),
+                    );
+                    continue;
+                }
+            };
+            if old_value.ty() != new_value.ty() {
+                let criticality = if is_safe_input_change(old_value.ty(), new_value.ty()) {
+                    Criticality::NonBreaking
+                } else {
+                    Criticality::Breaking
+                };
+                self.change(
+                    kinds.type_changed,
+                    criticality,
+                    &path,
+                    format!(
+                        "the type of {} changed from `{}` to `{}`",
+                        subject,
+                        old_value.ty(),
+                        new_value.ty()
+                    ),
+                );
+            }
+            if old_value.default_value() != new_value.default_value() {
+                self.change(
+                    kinds.default_changed,
+                    Criticality::Dangerous,
+                    &path,
+                    format!(
+                        "the default value of {} changed from {} to {}",
+                        subject,
+                        default_value(old_value.default_value()),
+                        default_value(new_value.default_value())
+                    ),
+                );
+            }
+            self.description(
+                kinds.description_changed,
+                &path,
+                &subject,
+                old_value.description(),
+                new_value.description(),
+            );
+        }
+        for new_value in new {
+            if !old.iter().any(|value| value.name() == new_value.name()) {
+                let path = format!("{}.{}", parent, new_value.name());
+                let (criticality, required) = if new_value.is_required() {
+                    (Criticality::Breaking, "required ")
+                } else {
+                    (kinds.optional_added, "")
+                };
+                self.change(
+                    kinds.added,
+                    criticality,
+                    &path,
+                    format!("the {}{} `{}` was added", required, kinds.noun, path),
+                );
+            }
+        }
+    }
+
+    fn directive_definition(&mut self, old: &DirectiveDefinition, new: &DirectiveDefinition) {
+        let path = format!("@{}", old.name());
+        let subject = format!("the directive `{}`", path);
+        self.description(
+            ChangeKind::DirectiveDescriptionChanged,
+            &path,
+            &subject,
+            old.description(),
+            new.description(),
+        );
+        match (old.is_repeatable(), new.is_repeatable()) {
+            (true, false) => self.change(
+                ChangeKind::DirectiveRepeatableRemoved,
+                Criticality::Breaking,
+                &path,
+                format!("{} is no longer repeatable", subject),
+            ),
+            (false, true) => self.change(
+                ChangeKind::DirectiveRepeatableAdded,
+                Criticality::NonBreaking,
+                &path,
+                format!("{} is now repeatable", subject),
+            ),
+            _ => {}
+        }
+        for location in old.locations() {
+            if !new.locations().contains(location) {
+                self.change(
+                    ChangeKind::DirectiveLocationRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!(
+                        "the location {} was removed from {}",
+                        location.name(),
+                        subject
+                    ),
+                );
+            }
+        }
+        for location in new.locations() {
+            if !old.locations().contains(location) {
+                self.change(
+                    ChangeKind::DirectiveLocationAdded,
+                    Criticality::NonBreaking,
+                    &path,
+                    format!("the location {} was added to {}", location.name(), subject),
+                );
+            }
+        }
+        self.input_values(
+            &DIRECTIVE_ARGUMENTS,
+            &path,
+            old.arguments(),
+            new.arguments(),
+        );
+    }
+
+    fn description(
+        &mut self,
+        kind: ChangeKind,
+        path: &str,
+        subject: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) {
+        let message = match (old, new) {
+            (None, Some(_)) => format!("a description was added to {}", subject),
+            (Some(_), None) => format!("the description of {} was removed", subject),
+            (Some(old), Some(new)) if old != new => {
+                format!("the description of {} changed", subject)
+            }
+            _ => return,
+        };
+        self.change(kind, Criticality::NonBreaking, path, message);
+    }
+
+    fn deprecation(
+        &mut self,
+        [added, removed, reason_changed]: [ChangeKind; 3],
+        path: &str,
+        subject: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) {
+        match (old, new) {
+            (None, Some(_)) => self.change(
+                added,
+                Criticality::NonBreaking,
+                path,
+                format!("{} was deprecated", subject),
+            ),
+            (Some(_), None) => self.change(
+                removed,
+                Criticality::Dangerous,
+                path,
+                format!("{} is no longer deprecated", subject),
+            ),
+            (Some(old), Some(new)) if old != new => self.change(
+                reason_changed,
+                Criticality::NonBreaking,
+                path,
+                format!(
+                    "the deprecation reason of {} changed from {:?} to {:?}",
+                    subject, old, new
+                ),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn default_value(value: Option<&Value>) -> String {
+    value.map_or("none".to_string(), |value| format!("`{}`", value))
+}
+
+/// Check whether changing the type of an output field from `old` to `new`
+/// keeps the responses valid for existing clients: only adding non-null
+/// modifiers is.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new)) => old == new,
+        (Type::List(old), Type::List(new)) | (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_output_change(old, new)
+        }
+        (old, Type::NonNull(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Check whether changing the type of an input value from `old` to `new`
+/// keeps the values existing clients send valid: only removing non-null
+/// modifiers is.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new)) => old == new,
+        (Type::List(old), Type::List(new)) | (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_input_change(old, new)
+        }
+        (Type::NonNull(old), new) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn changes(old: &str, new: &str) -> Vec<(Criticality, String)> {
+        let old = Parser::new(old).parse().document();
+        let new = Parser::new(new).parse().document();
+        diff(&Schema::new(&old), &Schema::new(&new))
+            .into_iter()
+            .map(|change| (change.criticality, change.message))
+            .collect()
+    }
+
+    #[test]
+    fn it_classifies_type_and_field_changes() {
+        let old = r#"
+type Query { user(id: ID!): User users(first: Int = 10): [User] }
+type User implements Node { id: ID! name: String email: String }
+interface Node { id: ID! }
+union Result = User | Post
+type Post { id: ID! }
+enum Role { ADMIN USER }
+scalar Date
+"#;
+        let new = r#"
+type Query { user(id: ID, name: String!): User! users(first: Int = 20, after: String): [User] }
+type User { id: ID! name: Int email: String @deprecated(reason: "use emails") }
+interface Node { id: ID! }
+union Result = User
+type Post { id: ID! }
+enum Role { ADMIN GUEST }
+input Date { day: Int }
+"#;
+        assert_eq!(
+            changes(old, new),
+            [
+                (
+                    Criticality::NonBreaking,
+                    "the type of the field `Query.user` changed from `User` to `User!`".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the type of the argument `Query.user.id` changed from `ID!` to `ID`".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the required argument `Query.user.name` was added".into()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "the default value of the argument `Query.users.first` changed from `10` to `20`"
+                        .into()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "the argument `Query.users.after` was added".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the interface `Node` was removed from `User`".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the type of the field `User.name` changed from `String` to `Int`".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the field `User.email` was deprecated".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the member `Post` was removed from `Result`".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the enum value `Role.USER` was removed".into()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "the enum value `Role.GUEST` was added".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the type `Date` changed from scalar to input object".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_classifies_input_and_directive_changes() {
+        let old = r#"
+type Query { search(filter: Filter): [String] }
+input Filter { text: String! limit: Int }
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+directive @old on FIELD
+"#;
+        let new = r#"
+type Query { search(filter: Filter): [String] }
+input Filter { text: String limit: Int! tags: [String] }
+directive @tag(name: String!, scope: String) on FIELD_DEFINITION
+directive @new on FIELD
+type Mutation { ping: Boolean }
+"#;
+        assert_eq!(
+            changes(old, new),
+            [
+                (
+                    Criticality::NonBreaking,
+                    "the mutation root operation type changed from none to `Mutation`".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the type of the input field `Filter.text` changed from `String!` to `String`"
+                        .into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the type of the input field `Filter.limit` changed from `Int` to `Int!`"
+                        .into()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "the input field `Filter.tags` was added".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the type `Mutation` was added".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the directive `@tag` is no longer repeatable".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the location OBJECT was removed from the directive `@tag`".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the argument `@tag.scope` was added".into()
+                ),
+                (
+                    Criticality::Breaking,
+                    "the directive `@old` was removed".into()
+                ),
+                (
+                    Criticality::NonBreaking,
+                    "the directive `@new` was added".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_serializes_changes() {
+        let old = Parser::new("type Query { a: Int b: Int }")
+            .parse()
+            .document();
+        let new = Parser::new("type Query { a: Int }").parse().document();
+        let changes = diff(&Schema::new(&old), &Schema::new(&new));
+        assert!(changes[0].is_breaking());
+        assert_eq!(
+            serde_json::to_value(&changes).unwrap(),
+            json!([{
+                "kind": "FIELD_REMOVED",
+                "criticality": "BREAKING",
+                "path": "Query.b",
+                "message": "the field `Query.b` was removed",
+            }])
+        );
+        assert_eq!(
+            changes[0].to_string(),
+            "[breaking] the field `Query.b` was removed"
+        );
+    }
+}
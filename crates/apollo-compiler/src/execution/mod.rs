@@ -0,0 +1,1561 @@
+//! Execution of operations against a [`Schema`], with the values of fields
+//! provided by a [`Resolver`].
+//!
+//! The [`Executor`] implements the spec's ExecuteQuery and ExecuteMutation
+//! algorithms, and Subscribe for subscriptions: fields are collected through
+//! fragments and the `@skip` and `@include` directives, arguments and
+//! variables are coerced, the values returned by the resolver are completed
+//! according to the fields' types, and errors make fields `null` up to the
+//! nearest nullable parent.
+//!
+//! Documents are expected to be valid; see [`validate`](crate::validate).
+//!
+//! See: https://spec.graphql.org/October2021/#sec-Execution
+mod response;
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use apollo_parser::ast::{self, AstNode};
+use futures::{
+    future::{self, FutureExt, LocalBoxFuture},
+    stream::{LocalBoxStream, StreamExt},
+};
+use indexmap::IndexMap;
+use serde_json::{Map, Value as Json};
+
+use crate::{
+    coercion,
+    diagnostics::{self, Diagnostic},
+    introspection,
+    schema::FieldDefinition,
+    Schema, Type, TypeDefinition, Value,
+};
+
+pub use response::{FieldError, GraphQLError, Location, PathSegment, Response, ResponseValue};
+
+/// Provides the values of fields.
+///
+/// The default implementation of every method reads values from the parent
+/// object's JSON, so a resolver only needs to override what cannot be read
+/// from there. In-memory resolvers can return a whole tree of JSON from a
+/// root field and let the default implementation resolve the fields below.
+///
+/// Execution walks the syntax tree of the operation, which is not `Send`, so
+/// it runs on a single thread and resolvers need not be `Send` or `Sync`
+/// either: they return local futures and streams, and can keep their state in
+/// a `Cell` or a `RefCell`.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::execution::{Executor, FieldError, ResolveInfo, Resolver};
+/// use apollo_compiler::Schema;
+/// use apollo_parser::Parser;
+/// use futures::future::{FutureExt, LocalBoxFuture};
+/// use serde_json::{json, Value};
+///
+/// struct Pets;
+///
+/// impl Resolver for Pets {
+///     fn resolve<'a>(
+///         &'a self,
+///         info: ResolveInfo<'a>,
+///     ) -> LocalBoxFuture<'a, Result<Value, FieldError>> {
+///         async move {
+///             match (info.parent_type(), info.field_name()) {
+///                 ("Query", "pets") => Ok(json!([
+///                     { "__typename": "Dog", "name": "Rex", "barks": true },
+///                     { "__typename": "Cat", "name": "Tom", "lives": 9 },
+///                 ])),
+///                 _ => Ok(info.parent()[info.field_name()].clone()),
+///             }
+///         }
+///         .boxed_local()
+///     }
+/// }
+///
+/// let schema = Parser::new("
+/// interface Pet { name: String! }
+/// type Dog implements Pet { name: String! barks: Boolean }
+/// type Cat implements Pet { name: String! lives: Int }
+/// type Query { pets: [Pet!]! }
+/// ").parse().document();
+/// let schema = Schema::new(&schema);
+/// let query = Parser::new("{ pets { name ... on Cat { lives } } }").parse().document();
+///
+/// let executor = Executor::new(&schema, &Pets);
+/// let response = futures::executor::block_on(executor.execute(&query, None, &Default::default()));
+///
+/// assert_eq!(response.errors(), []);
+/// assert_eq!(
+///     response.data().unwrap(),
+///     &json!({ "pets": [{ "name": "Rex" }, { "name": "Tom", "lives": 9 }] })
+/// );
+/// ```
+pub trait Resolver {
+    /// Resolve the value of a field. Lists are returned as JSON arrays and
+    /// objects as JSON objects, which become the parent of the fields
+    /// selected on them.
+    ///
+    /// By default, this reads the field's name from the parent object.
+    fn resolve<'a>(
+        &'a self,
+        info: ResolveInfo<'a>,
+    ) -> LocalBoxFuture<'a, Result<Json, FieldError>> {
+        let value = info
+            .parent()
+            .get(info.field_name())
+            .cloned()
+            .unwrap_or(Json::Null);
+        future::ready(Ok(value)).boxed_local()
+    }
+
+    /// Resolve a root field of the subscription type to a stream of source
+    /// events. Each event becomes the root value the subscription's selection
+    /// set is executed on, so its root field is then resolved with
+    /// [`Resolver::resolve`] on the event.
+    ///
+    /// By default, fields cannot be subscribed to.
+    ///
+    /// See: https://spec.graphql.org/October2021/#ResolveFieldEventStream()
+    fn subscribe<'a>(
+        &'a self,
+        info: ResolveInfo<'_>,
+    ) -> Result<LocalBoxStream<'a, Json>, FieldError> {
+        Err(FieldError::new(format!(
+            "the field `{}.{}` cannot be subscribed to",
+            info.parent_type(),
+            info.field_name()
+        )))
+    }
+
+    /// Get the name of the object type of `value`, the value of a field of
+    /// the interface or union type `abstract_type`.
+    ///
+    /// By default, this reads the value's `__typename`.
+    fn resolve_type(&self, abstract_type: &str, value: &Json) -> Option<String> {
+        let _ = abstract_type;
+        Some(value.get("__typename")?.as_str()?.to_string())
+    }
+}
+
+/// What a [`Resolver`] knows about the field it resolves.
+#[derive(Clone, Copy)]
+pub struct ResolveInfo<'a> {
+    schema: &'a Schema,
+    parent_type: &'a str,
+    parent: &'a Json,
+    field: &'a FieldDefinition,
+    arguments: &'a IndexMap<String, Value>,
+    path: &'a [PathSegment],
+}
+
+impl<'a> ResolveInfo<'a> {
+    /// Get the schema the operation is executed against.
+    pub fn schema(&self) -> &'a Schema {
+        self.schema
+    }
+
+    /// Get the name of the object type the field is selected on.
+    pub fn parent_type(&self) -> &'a str {
+        self.parent_type
+    }
+
+    /// Get the value of the object the field is selected on: the value its
+    /// own field resolved to, or the root value for root fields.
+    pub fn parent(&self) -> &'a Json {
+        self.parent
+    }
+
+    /// Get the name of the field, as defined in the schema rather than its
+    /// alias.
+    pub fn field_name(&self) -> &'a str {
+        self.field.name()
+    }
+
+    /// Get the definition of the field.
+    pub fn field_definition(&self) -> &'a FieldDefinition {
+        self.field
+    }
+
+    /// Get the coerced values of the field's arguments, including default
+    /// values.
+    pub fn arguments(&self) -> &'a IndexMap<String, Value> {
+        self.arguments
+    }
+
+    /// Get the path of the field in the response.
+    pub fn path(&self) -> &'a [PathSegment] {
+        self.path
+    }
+}
+
+/// Executes operations against a schema.
+///
+/// The executor is not tied to a runtime: [`Executor::execute`] returns a
+/// future that can be awaited on any executor, or blocked on with
+/// `futures::executor::block_on`. The future is not `Send`, as it holds the
+/// syntax tree of the operation, so on a multi-threaded runtime it has to be
+/// run on the current thread, e.g. with `tokio::task::spawn_local`.
+pub struct Executor<'a> {
+    schema: &'a Schema,
+    resolver: &'a dyn Resolver,
+    root_value: Json,
+}
+
+impl<'a> Executor<'a> {
+    /// Create a new instance of `Executor`.
+    pub fn new(schema: &'a Schema, resolver: &'a dyn Resolver) -> Self {
+        Self {
+            schema,
+            resolver,
+            root_value: Json::Null,
+        }
+    }
+
+    /// Set the value the root fields are resolved on. Defaults to `null`.
+    pub fn root_value(&mut self, root_value: Json) {
+        self.root_value = root_value;
+    }
+
+    /// Execute the operation named `operation_name`, or the only operation of
+    /// `document`, with the JSON values of its `variables`.
+    ///
+    /// The fields of a query are resolved concurrently, and the root fields
+    /// of a mutation one after the other, in order. Subscriptions are
+    /// executed with [`Executor::subscribe`] instead.
+    pub async fn execute(
+        &self,
+        document: &ast::Document,
+        operation_name: Option<&str>,
+        variables: &Map<String, Json>,
+    ) -> Response {
+        let source = document.syntax().to_string();
+        let (operation, variables) = match self.prepare(document, operation_name, variables) {
+            Ok(prepared) => prepared,
+            Err(diagnostics) => return Response::from_diagnostics(diagnostics, &source),
+        };
+        let (root_type, serial) = match operation.operation_type() {
+            Some(ty) if ty.mutation_token().is_some() => (self.schema.mutation_type(), true),
+            Some(ty) if ty.subscription_token().is_some() => {
+                return Response::from_diagnostics(
+                    vec![
+                        Diagnostic::new("subscriptions cannot be executed as a single request")
+                            .label(diagnostics::range(&operation), "subscription defined here")
+                            .help("use `Executor::subscribe` to get a stream of responses"),
+                    ],
+                    &source,
+                );
+            }
+            _ => (self.schema.query_type(), false),
+        };
+        let root_type = match root_type {
+            Some(root_type) => root_type,
+            None => {
+                return Response::from_diagnostics(
+                    vec![
+                        Diagnostic::new("the schema does not support this type of operation")
+                            .label(diagnostics::range(&operation), "operation defined here"),
+                    ],
+                    &source,
+                );
+            }
+        };
+
+        let execution = Execution::new(self, document, &source, &variables);
+        let selection_sets: Vec<_> = operation.selection_set().into_iter().collect();
+        execution
+            .response(root_type, &selection_sets, &self.root_value, serial)
+            .await
+    }
+
+    /// Subscribe to the subscription operation named `operation_name`, or the
+    /// only operation of `document`, with the JSON values of its `variables`.
+    ///
+    /// The subscription's single root field is resolved to a stream of source
+    /// events with [`Resolver::subscribe`], and each event is executed as the
+    /// root value of the subscription's selection set, so the returned stream
+    /// yields one response per event. If the stream cannot be created, a
+    /// response with the errors found is returned instead.
+    ///
+    /// See: https://spec.graphql.org/October2021/#Subscribe()
+    // The error is the response to the request, returned as is.
+    #[allow(clippy::result_large_err)]
+    pub fn subscribe<'b>(
+        &'b self,
+        document: &'b ast::Document,
+        operation_name: Option<&str>,
+        variables: &Map<String, Json>,
+    ) -> Result<LocalBoxStream<'b, Response>, Response> {
+        let source = document.syntax().to_string();
+        let (operation, variables) = self
+            .prepare(document, operation_name, variables)
+            .map_err(|diagnostics| Response::from_diagnostics(diagnostics, &source))?;
+        let error = |message: String, help: &str| {
+            let diagnostic = Diagnostic::new(message)
+                .label(diagnostics::range(&operation), "operation defined here")
+                .help(help);
+            Response::from_diagnostics(vec![diagnostic], &source)
+        };
+        let is_subscription = operation
+            .operation_type()
+            .map_or(false, |ty| ty.subscription_token().is_some());
+        if !is_subscription {
+            return Err(error(
+                "only subscription operations can be subscribed to".to_string(),
+                "use `Executor::execute` to execute queries and mutations",
+            ));
+        }
+        let subscription_type = self.schema.subscription_type().ok_or_else(|| {
+            error(
+                "the schema does not support subscriptions".to_string(),
+                "define a `Subscription` type, or set the subscription type with `schema { subscription: ... }`",
+            )
+        })?;
+
+        // CreateSourceEventStream()
+        let collector = FieldCollector::new(self.schema, document, &variables);
+        let mut grouped = IndexMap::new();
+        let mut diagnostics = Vec::new();
+        let selection_sets: Vec<_> = operation.selection_set().into_iter().collect();
+        for selection_set in &selection_sets {
+            collector.collect(
+                subscription_type,
+                selection_set,
+                &mut grouped,
+                &mut HashSet::new(),
+                &mut diagnostics,
+            );
+        }
+        if !diagnostics.is_empty() {
+            return Err(Response::from_diagnostics(diagnostics, &source));
+        }
+        if grouped.len() != 1 {
+            return Err(error(
+                format!(
+                    "a subscription must select a single root field, found {}",
+                    grouped.len()
+                ),
+                "split the subscription into one operation per root field",
+            ));
+        }
+        let (key, fields) = grouped.into_iter().next().unwrap();
+        let field = &fields[0];
+        let name = field.name().map(|name| name.text().to_string());
+        let definition = name
+            .as_deref()
+            .and_then(|name| self.schema.field_definition(subscription_type, name))
+            .filter(|definition| !definition.name().starts_with("__"))
+            .ok_or_else(|| {
+                error(
+                    format!(
+                        "the field `{}` cannot be subscribed to",
+                        name.unwrap_or_default()
+                    ),
+                    "select a field of the subscription type",
+                )
+            })?;
+        let path = vec![PathSegment::Field(key)];
+        let arguments = coercion::coerce_arguments(
+            self.schema,
+            definition.arguments(),
+            field.arguments().as_ref(),
+            &variables,
+        )
+        .map_err(|diagnostics| {
+            let errors = diagnostics.iter().map(|diagnostic| {
+                GraphQLError::from_diagnostic(diagnostic, &source).at_path(path.clone())
+            });
+            Response {
+                errors: errors.collect(),
+                ..Response::default()
+            }
+        })?;
+        let info = ResolveInfo {
+            schema: self.schema,
+            parent_type: subscription_type,
+            parent: &self.root_value,
+            field: definition,
+            arguments: &arguments,
+            path: &path,
+        };
+        let events = self.resolver.subscribe(info).map_err(|error| {
+            let offset = diagnostics::range(field).start;
+            Response::new(None).error(GraphQLError {
+                extensions: error.extensions,
+                ..GraphQLError::new(error.message)
+                    .location(Location::from_offset(&source, offset))
+                    .at_path(path.clone())
+            })
+        })?;
+
+        // MapSourceToResponseEvent()
+        // Every event is executed with the same document and variables, so
+        // they are shared by the futures of all events.
+        let state = Rc::new((source, variables, selection_sets));
+        let responses = events.then(move |event| {
+            let state = state.clone();
+            async move {
+                let (source, variables, selection_sets) = &*state;
+                let execution = Execution::new(self, document, source, variables);
+                execution
+                    .response(subscription_type, selection_sets, &event, false)
+                    .await
+            }
+        });
+        Ok(responses.boxed_local())
+    }
+
+    /// Find the operation to execute and coerce its variables.
+    fn prepare(
+        &self,
+        document: &ast::Document,
+        operation_name: Option<&str>,
+        variables: &Map<String, Json>,
+    ) -> Result<(ast::OperationDefinition, IndexMap<String, Value>), Vec<Diagnostic>> {
+        let operation = operation(document, operation_name)?;
+        let variables = coercion::coerce_variables(self.schema, &operation, variables)?;
+        Ok((operation, variables))
+    }
+}
+
+/// Shape raw JSON `data` into the response to an operation, as a gateway does
+/// to reproject the response of a subgraph onto the client's operation.
+///
+/// Each field is read from `data` under its response key, or else under its
+/// name, so `data` can be keyed either way. Aliases, `__typename`, fragments
+/// and `@skip`/`@include` are applied as in execution, and the objects of
+/// interfaces and unions are matched with fragments using their `__typename`.
+/// Values are completed as if returned by a [`Resolver`], so missing fields
+/// are `null` and non-null violations are reported in the response's
+/// errors.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{execution, Schema};
+/// use apollo_parser::Parser;
+/// use serde_json::json;
+///
+/// let schema = Parser::new("
+/// type Query { pets: [Pet] }
+/// union Pet = Dog | Cat
+/// type Dog { name: String barks: Boolean }
+/// type Cat { name: String }
+/// ").parse().document();
+/// let schema = Schema::new(&schema);
+/// let query = Parser::new("{ animals: pets { __typename ... on Dog { dog: name barks } } }")
+///     .parse()
+///     .document();
+/// let data = json!({
+///     "pets": [
+///         { "__typename": "Dog", "name": "Rex", "barks": true, "age": 3 },
+///         { "__typename": "Cat", "name": "Tom" },
+///     ]
+/// });
+///
+/// let response = execution::shape(&schema, &query, None, &Default::default(), &data);
+///
+/// // The fields are in the order of the query
+/// assert_eq!(
+///     response.data().unwrap().to_string(),
+///     r#"{"animals":[{"__typename":"Dog","dog":"Rex","barks":true},{"__typename":"Cat"}]}"#
+/// );
+/// ```
+pub fn shape(
+    schema: &Schema,
+    document: &ast::Document,
+    operation_name: Option<&str>,
+    variables: &Map<String, Json>,
+    data: &Json,
+) -> Response {
+    let mut executor = Executor::new(schema, &Shape);
+    executor.root_value(data.clone());
+    // `Shape` resolves every field immediately, so this never blocks.
+    futures::executor::block_on(executor.execute(document, operation_name, variables))
+}
+
+/// Reads fields from raw JSON, under their response key or their name.
+struct Shape;
+
+impl Resolver for Shape {
+    fn resolve<'a>(
+        &'a self,
+        info: ResolveInfo<'a>,
+    ) -> LocalBoxFuture<'a, Result<Json, FieldError>> {
+        let parent = info.parent();
+        let value = match info.path().last() {
+            Some(PathSegment::Field(key)) => parent.get(key),
+            _ => None,
+        };
+        let value = value
+            .or_else(|| parent.get(info.field_name()))
+            .cloned()
+            .unwrap_or(Json::Null);
+        future::ready(Ok(value)).boxed_local()
+    }
+}
+
+/// The state of one operation's execution. Fields may be executed
+/// concurrently, so errors are collected in a `RefCell`.
+struct Execution<'a> {
+    schema: &'a Schema,
+    resolver: &'a dyn Resolver,
+    collector: FieldCollector<'a>,
+    /// The text of the executed document, to locate errors.
+    source: &'a str,
+    errors: RefCell<Vec<GraphQLError>>,
+}
+
+impl<'a> Execution<'a> {
+    fn new(
+        executor: &Executor<'a>,
+        document: &ast::Document,
+        source: &'a str,
+        variables: &'a IndexMap<String, Value>,
+    ) -> Self {
+        Self {
+            schema: executor.schema,
+            resolver: executor.resolver,
+            collector: FieldCollector::new(executor.schema, document, variables),
+            source,
+            errors: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Execute the root selection set of an operation on `root_value`.
+    async fn response(
+        self,
+        root_type: &str,
+        selection_sets: &[ast::SelectionSet],
+        root_value: &Json,
+        serial: bool,
+    ) -> Response {
+        let data = self
+            .selection_set(root_type, selection_sets, root_value, &[], serial)
+            .await;
+        Response {
+            data: Some(data.unwrap_or(ResponseValue::Null)),
+            errors: self.errors.into_inner(),
+            extensions: Map::new(),
+        }
+    }
+
+    /// Execute the fields selected by `selection_sets` on an object of type
+    /// `type_name`. Returns `None` when a non-null field is `null`, so the
+    /// object itself must become `null`.
+    ///
+    /// See: https://spec.graphql.org/October2021/#ExecuteSelectionSet()
+    fn selection_set<'b>(
+        &'b self,
+        type_name: &'b str,
+        selection_sets: &'b [ast::SelectionSet],
+        value: &'b Json,
+        path: &'b [PathSegment],
+        serial: bool,
+    ) -> LocalBoxFuture<'b, Option<ResponseValue>> {
+        async move {
+            let mut diagnostics = Vec::new();
+            let mut grouped = IndexMap::new();
+            for selection_set in selection_sets {
+                self.collector.collect(
+                    type_name,
+                    selection_set,
+                    &mut grouped,
+                    &mut HashSet::new(),
+                    &mut diagnostics,
+                );
+            }
+            self.diagnostics(diagnostics, path);
+
+            let fields = grouped.into_iter().map(|(key, fields)| async move {
+                let mut path = path.to_vec();
+                path.push(PathSegment::Field(key.clone()));
+                let value = self.field(type_name, &fields, value, &path).await;
+                Some((key, value?))
+            });
+            let results = if serial {
+                let mut results = Vec::new();
+                for field in fields {
+                    results.push(field.await);
+                }
+                results
+            } else {
+                future::join_all(fields).await
+            };
+
+            results
+                .into_iter()
+                .collect::<Option<IndexMap<_, _>>>()
+                .map(ResponseValue::Object)
+        }
+        .boxed_local()
+    }
+
+    /// Execute a field, returning `None` when the error propagates to the
+    /// parent object.
+    ///
+    /// See: https://spec.graphql.org/October2021/#ExecuteField()
+    async fn field(
+        &self,
+        type_name: &str,
+        fields: &[ast::Field],
+        parent: &Json,
+        path: &[PathSegment],
+    ) -> Option<ResponseValue> {
+        let field = &fields[0];
+        let name = field.name()?.text().to_string();
+        if name == "__typename" {
+            return Some(type_name.into());
+        }
+        let definition = match self.schema.field_definition(type_name, &name) {
+            Some(definition) => definition,
+            None => {
+                self.error(
+                    format!("the type `{}` has no field `{}`", type_name, name),
+                    path,
+                    field,
+                );
+                return Some(ResponseValue::Null);
+            }
+        };
+        let nullable = |value: Option<ResponseValue>| match value {
+            None if !definition.ty().is_non_null() => Some(ResponseValue::Null),
+            value => value,
+        };
+
+        if name == "__schema" || name == "__type" {
+            return match introspection::meta_field(self.schema, &self.collector, fields) {
+                Ok(value) => Some(value),
+                Err(diagnostics) => {
+                    self.diagnostics(diagnostics, path);
+                    nullable(None)
+                }
+            };
+        }
+
+        let arguments = match coercion::coerce_arguments(
+            self.schema,
+            definition.arguments(),
+            field.arguments().as_ref(),
+            self.collector.variables,
+        ) {
+            Ok(arguments) => arguments,
+            Err(diagnostics) => {
+                self.diagnostics(diagnostics, path);
+                return nullable(None);
+            }
+        };
+        let info = ResolveInfo {
+            schema: self.schema,
+            parent_type: type_name,
+            parent,
+            field: definition,
+            arguments: &arguments,
+            path,
+        };
+        match self.resolver.resolve(info).await {
+            Ok(value) => {
+                let completed = self.complete(definition.ty(), fields, value, path).await;
+                nullable(completed)
+            }
+            Err(error) => {
+                self.errors.borrow_mut().push(GraphQLError {
+                    extensions: error.extensions,
+                    ..self.located(error.message, path, field)
+                });
+                nullable(None)
+            }
+        }
+    }
+
+    /// Complete a resolved value according to the field's type `ty`.
+    /// Returns `None` when the value is `null`, or has an error, and `ty` is
+    /// non-null.
+    ///
+    /// See: https://spec.graphql.org/October2021/#CompleteValue()
+    fn complete<'b>(
+        &'b self,
+        ty: &'b Type,
+        fields: &'b [ast::Field],
+        value: Json,
+        path: &'b [PathSegment],
+    ) -> LocalBoxFuture<'b, Option<ResponseValue>> {
+        async move {
+            let inner = match ty {
+                Type::NonNull(inner) => inner,
+                ty => {
+                    return Some(
+                        self.complete_nullable(ty, fields, value, path)
+                            .await
+                            .unwrap_or(ResponseValue::Null),
+                    )
+                }
+            };
+            match self.complete_nullable(inner, fields, value, path).await? {
+                ResponseValue::Null => {
+                    let field = &fields[0];
+                    let name = field.name().map(|name| name.text().to_string());
+                    self.error(
+                        format!(
+                            "cannot return null for the non-null field `{}`",
+                            name.unwrap_or_default()
+                        ),
+                        path,
+                        field,
+                    );
+                    None
+                }
+                value => Some(value),
+            }
+        }
+        .boxed_local()
+    }
+
+    /// Complete a value of a nullable type. Returns `None` when there was an
+    /// error, or an item of a list of non-null items was `null`.
+    async fn complete_nullable(
+        &self,
+        ty: &Type,
+        fields: &[ast::Field],
+        value: Json,
+        path: &[PathSegment],
+    ) -> Option<ResponseValue> {
+        if value.is_null() {
+            return Some(ResponseValue::Null);
+        }
+        let field = &fields[0];
+
+        let name = match ty {
+            Type::List(item_ty) => {
+                let items = match value {
+                    Json::Array(items) => items,
+                    value => {
+                        self.error(
+                            format!("expected a list for the type `{}`, found {}", ty, value),
+                            path,
+                            field,
+                        );
+                        return None;
+                    }
+                };
+                let items = items.into_iter().enumerate().map(|(i, item)| async move {
+                    let mut path = path.to_vec();
+                    path.push(PathSegment::Index(i));
+                    self.complete(item_ty, fields, item, &path).await
+                });
+                let items = future::join_all(items).await;
+                return items
+                    .into_iter()
+                    .collect::<Option<_>>()
+                    .map(ResponseValue::Array);
+            }
+            Type::Named(name) => name,
+            Type::NonNull(_) => unreachable!("non-null types are completed by `complete`"),
+        };
+
+        let object_type = match self.schema.type_definition(name) {
+            Some(TypeDefinition::Scalar(_)) | None => {
+                return self.leaf(name, value, path, field);
+            }
+            Some(TypeDefinition::Enum(enum_ty)) => {
+                return match value.as_str() {
+                    Some(text) if enum_ty.value(text).is_some() => Some(value.into()),
+                    _ => {
+                        self.error(
+                            format!("the enum `{}` has no value {}", name, value),
+                            path,
+                            field,
+                        );
+                        None
+                    }
+                };
+            }
+            Some(TypeDefinition::Object(object)) => object.name().to_string(),
+            Some(ty @ (TypeDefinition::Interface(_) | TypeDefinition::Union(_))) => {
+                match self.resolver.resolve_type(ty.name(), &value) {
+                    Some(object) if self.schema.is_possible_type(ty.name(), &object) => object,
+                    Some(object) => {
+                        self.error(
+                            format!(
+                                "the type `{}` is not a possible type of `{}`",
+                                object,
+                                ty.name()
+                            ),
+                            path,
+                            field,
+                        );
+                        return None;
+                    }
+                    None => {
+                        self.error(
+                            format!(
+                                "could not resolve the object type of the abstract type `{}`",
+                                ty.name()
+                            ),
+                            path,
+                            field,
+                        );
+                        return None;
+                    }
+                }
+            }
+            Some(TypeDefinition::InputObject(_)) => {
+                self.error(
+                    format!("the input object `{}` cannot be an output type", name),
+                    path,
+                    field,
+                );
+                return None;
+            }
+        };
+
+        let selection_sets: Vec<_> = fields
+            .iter()
+            .filter_map(|field| field.selection_set())
+            .collect();
+        self.selection_set(&object_type, &selection_sets, &value, path, false)
+            .await
+    }
+
+    /// Coerce the value of a scalar field.
+    ///
+    /// See: https://spec.graphql.org/October2021/#sec-Scalars.Result-Coercion-and-Serialization
+    fn leaf(
+        &self,
+        name: &str,
+        value: Json,
+        path: &[PathSegment],
+        field: &ast::Field,
+    ) -> Option<ResponseValue> {
+        let coerced = match (name, &value) {
+            ("Int", Json::Number(number)) => {
+                match number.as_i64().filter(|int| i32::try_from(*int).is_ok()) {
+                    Some(_) => Some(value.clone()),
+                    None => {
+                        self.error(
+                            format!(
+                                "Int cannot represent non 32-bit signed integer value: {}",
+                                number
+                            ),
+                            path,
+                            field,
+                        );
+                        return None;
+                    }
+                }
+            }
+            ("Float", Json::Number(_))
+            | ("String", Json::String(_))
+            | ("Boolean", Json::Bool(_)) => Some(value.clone()),
+            ("String", Json::Number(_) | Json::Bool(_)) => Some(Json::String(value.to_string())),
+            ("ID", Json::String(_)) => Some(value.clone()),
+            ("ID", Json::Number(number)) if number.is_i64() || number.is_u64() => {
+                Some(Json::String(number.to_string()))
+            }
+            ("Int" | "Float" | "String" | "Boolean" | "ID", _) => None,
+            // Custom scalars are serialized as they are.
+            _ => Some(value.clone()),
+        };
+        let coerced = coerced.map(ResponseValue::from);
+        if coerced.is_none() {
+            self.error(
+                format!("expected a value of type `{}`, found {}", name, value),
+                path,
+                field,
+            );
+        }
+        coerced
+    }
+
+    fn error(&self, message: String, path: &[PathSegment], field: &ast::Field) {
+        let error = self.located(message, path, field);
+        self.errors.borrow_mut().push(error);
+    }
+
+    /// An error at `path`, located at the start of `field`.
+    fn located(&self, message: String, path: &[PathSegment], field: &ast::Field) -> GraphQLError {
+        let offset = diagnostics::range(field).start;
+        GraphQLError::new(message)
+            .location(Location::from_offset(self.source, offset))
+            .at_path(path.to_vec())
+    }
+
+    fn diagnostics(&self, diagnostics: Vec<Diagnostic>, path: &[PathSegment]) {
+        self.errors
+            .borrow_mut()
+            .extend(diagnostics.iter().map(|diagnostic| {
+                GraphQLError::from_diagnostic(diagnostic, self.source).at_path(path.to_vec())
+            }));
+    }
+}
+
+/// Find the operation to execute: the one named `operation_name`, or the only
+/// operation of the document.
+///
+/// See: https://spec.graphql.org/October2021/#GetOperation()
+pub(crate) fn operation(
+    document: &ast::Document,
+    operation_name: Option<&str>,
+) -> Result<ast::OperationDefinition, Vec<Diagnostic>> {
+    let mut operations = document.definitions().filter_map(|def| match def {
+        ast::Definition::OperationDefinition(operation) => Some(operation),
+        _ => None,
+    });
+
+    match operation_name {
+        Some(name) => operations
+            .find(|operation| operation.name().map_or(false, |n| n.text() == name))
+            .ok_or_else(|| {
+                vec![Diagnostic::new(format!(
+                    "the operation `{}` is not defined in this document",
+                    name
+                ))]
+            }),
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => Ok(operation),
+            (None, _) => Err(vec![Diagnostic::new(
+                "the document does not contain any operation",
+            )]),
+            (Some(_), Some(_)) => Err(vec![Diagnostic::new(
+                "the document contains several operations, an operation name is required",
+            )]),
+        },
+    }
+}
+
+/// Groups the fields of selection sets by response key, following fragments
+/// and applying `@skip` and `@include`.
+pub(crate) struct FieldCollector<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<String, ast::FragmentDefinition>,
+    pub(crate) variables: &'a IndexMap<String, Value>,
+}
+
+impl<'a> FieldCollector<'a> {
+    pub(crate) fn new(
+        schema: &'a Schema,
+        document: &ast::Document,
+        variables: &'a IndexMap<String, Value>,
+    ) -> Self {
+        let fragments = document
+            .definitions()
+            .filter_map(|def| match def {
+                ast::Definition::FragmentDefinition(fragment) => {
+                    let name = fragment.fragment_name()?.name()?.text().to_string();
+                    Some((name, fragment))
+                }
+                _ => None,
+            })
+            .collect();
+        Self {
+            schema,
+            fragments,
+            variables,
+        }
+    }
+
+    /// Add the fields of `selection_set`, selected on an object of type
+    /// `type_name`, to `grouped`.
+    ///
+    /// See: https://spec.graphql.org/October2021/#CollectFields()
+    pub(crate) fn collect(
+        &self,
+        type_name: &str,
+        selection_set: &ast::SelectionSet,
+        grouped: &mut IndexMap<String, Vec<ast::Field>>,
+        visited: &mut HashSet<String>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    if !self.is_included(field.directives(), diagnostics) {
+                        continue;
+                    }
+                    let key = field
+                        .alias()
+                        .and_then(|alias| alias.name())
+                        .or_else(|| field.name());
+                    if let Some(key) = key {
+                        grouped
+                            .entry(key.text().to_string())
+                            .or_default()
+                            .push(field);
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    if !self.is_included(spread.directives(), diagnostics) {
+                        continue;
+                    }
+                    let name = match spread.fragment_name().and_then(|n| n.name()) {
+                        Some(name) => name.text().to_string(),
+                        None => continue,
+                    };
+                    if !visited.insert(name.clone()) {
+                        continue;
+                    }
+                    let fragment = match self.fragments.get(&name) {
+                        Some(fragment) => fragment,
+                        None => continue,
+                    };
+                    if self.applies(type_name, fragment.type_condition()) {
+                        if let Some(selection_set) = fragment.selection_set() {
+                            self.collect(type_name, &selection_set, grouped, visited, diagnostics);
+                        }
+                    }
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    if !self.is_included(inline.directives(), diagnostics) {
+                        continue;
+                    }
+                    let applies = inline.type_condition().is_none()
+                        || self.applies(type_name, inline.type_condition());
+                    if applies {
+                        if let Some(selection_set) = inline.selection_set() {
+                            self.collect(type_name, &selection_set, grouped, visited, diagnostics);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check whether a fragment with `type_condition` applies to an object of
+    /// type `type_name`.
+    ///
+    /// See: https://spec.graphql.org/October2021/#DoesFragmentTypeApply()
+    fn applies(&self, type_name: &str, type_condition: Option<ast::TypeCondition>) -> bool {
+        let condition = match type_condition
+            .and_then(|tc| tc.named_type())
+            .and_then(|ty| ty.name())
+        {
+            Some(name) => name.text().to_string(),
+            None => return false,
+        };
+        condition == type_name || self.schema.is_possible_type(&condition, type_name)
+    }
+
+    /// Apply the `@skip` and `@include` directives.
+    fn is_included(
+        &self,
+        directives: Option<ast::Directives>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        for directive in directives.iter().flat_map(|d| d.directives()) {
+            let name = match directive.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            // `@skip` excludes the selection when `if` is true, `@include`
+            // when it is false.
+            let excluded_if = match name.as_str() {
+                "skip" => true,
+                "include" => false,
+                _ => continue,
+            };
+            let definition = match self.schema.directive_definition(&name) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            match coercion::coerce_arguments(
+                self.schema,
+                definition.arguments(),
+                directive.arguments().as_ref(),
+                self.variables,
+            ) {
+                Ok(arguments) => {
+                    if arguments.get("if") == Some(&Value::Boolean(excluded_if)) {
+                        return false;
+                    }
+                }
+                Err(mut errors) => diagnostics.append(&mut errors),
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use apollo_parser::Parser;
+    use futures::channel::mpsc;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!): User
+  users(first: Int = 2): [User!]!
+  node(id: ID!): Node
+  big: Int
+}
+type Subscription {
+  messages(room: String!): Message!
+}
+type Message { room: String! text: String! }
+type Mutation {
+  increment(by: Int!): Int!
+  fail: Int!
+}
+interface Node { id: ID! }
+type User implements Node {
+  id: ID!
+  name: String!
+  role: Role
+  friends: [User]
+}
+type Post implements Node { id: ID! }
+enum Role { ADMIN USER }
+"#;
+
+    #[derive(Default)]
+    struct Store {
+        counter: Cell<i64>,
+    }
+
+    impl Resolver for Store {
+        fn resolve<'a>(
+            &'a self,
+            info: ResolveInfo<'a>,
+        ) -> LocalBoxFuture<'a, Result<Json, FieldError>> {
+            async move {
+                let users = json!([
+                    { "__typename": "User", "id": 1, "name": "Ada", "role": "ADMIN",
+                      "friends": [{ "id": 2, "name": "Bob" }, { "id": 3, "name": null }] },
+                    { "__typename": "User", "id": 2, "name": "Bob", "role": "GUEST" },
+                    { "__typename": "User", "id": 3, "name": null },
+                ]);
+                match (info.parent_type(), info.field_name()) {
+                    ("Query", "user") | ("Query", "node") => {
+                        let id = match info.arguments().get("id") {
+                            Some(Value::String(id)) => id.clone(),
+                            _ => unreachable!(),
+                        };
+                        if id == "post" {
+                            return Ok(json!({ "__typename": "Post", "id": "post" }));
+                        }
+                        let user = users
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .find(|user| user["id"].as_i64() == id.parse().ok());
+                        user.cloned().ok_or_else(|| {
+                            FieldError::new("not found").extension("code", json!("NOT_FOUND"))
+                        })
+                    }
+                    ("Query", "users") => {
+                        let first = match info.arguments().get("first") {
+                            Some(Value::Int(first)) => *first as usize,
+                            _ => unreachable!(),
+                        };
+                        Ok(Json::Array(users.as_array().unwrap()[..first].to_vec()))
+                    }
+                    ("Query", "big") => Ok(json!(1_i64 << 40)),
+                    ("Mutation", "increment") => {
+                        let by = match info.arguments().get("by") {
+                            Some(Value::Int(by)) => *by,
+                            _ => unreachable!(),
+                        };
+                        let counter = self.counter.get() * 10 + by;
+                        self.counter.set(counter);
+                        Ok(json!(counter))
+                    }
+                    ("Mutation", "fail") => Err(FieldError::new("failed")),
+                    _ => Ok(info.parent()[info.field_name()].clone()),
+                }
+            }
+            .boxed_local()
+        }
+    }
+
+    fn execute(query: &str, variables: Json) -> Response {
+        let schema = Parser::new(SCHEMA).parse().document();
+        let schema = Schema::new(&schema);
+        let query = Parser::new(query).parse().document();
+        let store = Store::default();
+        let executor = Executor::new(&schema, &store);
+        futures::executor::block_on(executor.execute(&query, None, variables.as_object().unwrap()))
+    }
+
+    fn errors(response: &Response) -> Vec<(String, Vec<String>)> {
+        response
+            .errors()
+            .iter()
+            .map(|error| {
+                let path = error.path().iter().map(ToString::to_string).collect();
+                (error.message().to_string(), path)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_executes_queries() {
+        let response = execute(
+            r#"
+query($id: ID!, $skip: Boolean!) {
+  me: user(id: $id) { __typename id name role ...Friends @skip(if: $skip) }
+  users { id }
+}
+fragment Friends on User { friends { name } }
+"#,
+            json!({ "id": "1", "skip": false }),
+        );
+
+        assert_eq!(
+            errors(&response),
+            [(
+                "cannot return null for the non-null field `name`".to_string(),
+                vec!["me", "friends", "1", "name"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )]
+        );
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({
+                "me": {
+                    "__typename": "User",
+                    "id": "1",
+                    "name": "Ada",
+                    "role": "ADMIN",
+                    "friends": [{ "name": "Bob" }, null],
+                },
+                "users": [{ "id": "1" }, { "id": "2" }],
+            })
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_order_of_the_selected_fields() {
+        let response = execute(
+            r#"{ users { id __typename } b: user(id: "2") { id } a: __typename }"#,
+            json!({}),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"data":{"users":[{"id":"1","__typename":"User"},{"id":"2","__typename":"User"}],"b":{"id":"2"},"a":"Query"}}"#
+        );
+    }
+
+    #[test]
+    fn it_propagates_errors_to_nullable_parents() {
+        // `users` is non-null, so a `null` name makes `data` itself `null`.
+        let response = execute("{ users(first: 3) { name } big }", json!({}));
+        assert_eq!(response.data().unwrap(), &Json::Null);
+        assert_eq!(
+            errors(&response),
+            [
+                (
+                    "cannot return null for the non-null field `name`".to_string(),
+                    vec!["users".to_string(), "2".to_string(), "name".to_string()]
+                ),
+                (
+                    "Int cannot represent non 32-bit signed integer value: 1099511627776"
+                        .to_string(),
+                    vec!["big".to_string()]
+                ),
+            ]
+        );
+
+        let response = execute(
+            r#"{ user(id: "2") { name role } missing: user(id: "9") { id } }"#,
+            json!({}),
+        );
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({ "user": { "name": "Bob", "role": null }, "missing": null })
+        );
+        assert_eq!(
+            response.errors()[0].message(),
+            "the enum `Role` has no value \"GUEST\""
+        );
+        assert_eq!(response.errors()[1].message(), "not found");
+        assert_eq!(
+            response.errors()[1].extensions()["code"],
+            json!("NOT_FOUND")
+        );
+        assert_eq!(response.errors()[1].locations(), [Location::new(1, 31)]);
+    }
+
+    #[test]
+    fn it_resolves_abstract_types() {
+        let response = execute(
+            r#"{
+  user: node(id: "1") { __typename id ... on User { name } }
+  post: node(id: "post") { __typename id ... on User { name } }
+}"#,
+            json!({}),
+        );
+        assert_eq!(errors(&response), []);
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({
+                "user": { "__typename": "User", "id": "1", "name": "Ada" },
+                "post": { "__typename": "Post", "id": "post" },
+            })
+        );
+    }
+
+    #[test]
+    fn it_executes_mutations_serially() {
+        let response = execute(
+            "mutation { a: increment(by: 1) b: increment(by: 2) c: increment(by: 3) }",
+            json!({}),
+        );
+        assert_eq!(errors(&response), []);
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({ "a": 1, "b": 12, "c": 123 })
+        );
+
+        let response = execute("mutation { a: increment(by: 1) fail }", json!({}));
+        assert_eq!(response.data().unwrap(), &Json::Null);
+        assert_eq!(
+            errors(&response),
+            [("failed".to_string(), vec!["fail".to_string()])]
+        );
+    }
+
+    #[test]
+    fn it_reports_request_errors() {
+        let response = execute("query($id: ID!) { user(id: $id) { id } }", json!({}));
+        assert_eq!(response.data(), None);
+        assert_eq!(response.errors().len(), 1);
+
+        let response = execute("{ a: __typename } { b: __typename }", json!({}));
+        assert_eq!(response.data(), None);
+        assert_eq!(
+            response.errors()[0].message(),
+            "the document contains several operations, an operation name is required"
+        );
+
+        let response = execute(r#"{ __type(name: "Role") { kind } __typename }"#, json!({}));
+        assert_eq!(errors(&response), []);
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({ "__type": { "kind": "ENUM" }, "__typename": "Query" })
+        );
+    }
+
+    /// Sends the messages received on a channel to the subscribers of their
+    /// room.
+    struct Chat {
+        messages: RefCell<Option<mpsc::UnboundedReceiver<Json>>>,
+    }
+
+    impl Resolver for Chat {
+        fn subscribe<'a>(
+            &'a self,
+            info: ResolveInfo<'_>,
+        ) -> Result<LocalBoxStream<'a, Json>, FieldError> {
+            let room = match &info.arguments()["room"] {
+                Value::String(room) => room.clone(),
+                _ => unreachable!(),
+            };
+            let messages = self.messages.borrow_mut().take();
+            let messages = messages.ok_or_else(|| FieldError::new("already subscribed"))?;
+            let messages = messages
+                .filter(move |message| {
+                    let in_room = message["room"] == room.as_str();
+                    future::ready(in_room)
+                })
+                .map(|message| json!({ "messages": message }));
+            Ok(messages.boxed_local())
+        }
+    }
+
+    #[test]
+    fn it_executes_subscriptions() {
+        let schema = Parser::new(SCHEMA).parse().document();
+        let schema = Schema::new(&schema);
+        let query = r#"
+subscription($room: String!) { ...Messages }
+fragment Messages on Subscription { message: messages(room: $room) { text } }
+"#;
+        let query = Parser::new(query).parse().document();
+        let (sender, receiver) = mpsc::unbounded();
+        let chat = Chat {
+            messages: RefCell::new(Some(receiver)),
+        };
+        let executor = Executor::new(&schema, &chat);
+        let variables = json!({ "room": "general" });
+
+        let responses = executor
+            .subscribe(&query, None, variables.as_object().unwrap())
+            .unwrap();
+        for message in [
+            json!({ "room": "general", "text": "hello" }),
+            json!({ "room": "random", "text": "ignored" }),
+            json!({ "room": "general", "text": null }),
+        ] {
+            sender.unbounded_send(message).unwrap();
+        }
+        drop(sender);
+        let responses: Vec<_> = futures::executor::block_on(responses.collect());
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(
+            responses[0],
+            Response::new(Some(json!({ "message": { "text": "hello" } }).into()))
+        );
+        assert_eq!(responses[1].data().unwrap(), &Json::Null);
+        assert_eq!(
+            errors(&responses[1]),
+            [(
+                "cannot return null for the non-null field `text`".to_string(),
+                vec!["message".to_string(), "text".to_string()]
+            )]
+        );
+
+        let response = executor
+            .subscribe(&query, None, variables.as_object().unwrap())
+            .err()
+            .unwrap();
+        assert_eq!(response.data(), None);
+        assert_eq!(
+            errors(&response),
+            [(
+                "already subscribed".to_string(),
+                vec!["message".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn it_reports_subscription_errors() {
+        let schema = Parser::new(SCHEMA).parse().document();
+        let schema = Schema::new(&schema);
+        let store = Store::default();
+        let executor = Executor::new(&schema, &store);
+        let subscribe = |query: &str| {
+            let query = Parser::new(query).parse().document();
+            let response = executor.subscribe(&query, None, &Map::new()).err().unwrap();
+            response.errors()[0].message().to_string()
+        };
+
+        assert_eq!(
+            subscribe(
+                r#"subscription { a: messages(room: "a") { text } ... on Subscription { b: messages(room: "b") { text } } }"#
+            ),
+            "a subscription must select a single root field, found 2"
+        );
+        assert_eq!(
+            subscribe(r#"subscription { messages(room: "a") { text } }"#),
+            "the field `Subscription.messages` cannot be subscribed to"
+        );
+        assert_eq!(
+            subscribe("subscription { __typename }"),
+            "the field `__typename` cannot be subscribed to"
+        );
+        assert_eq!(
+            subscribe("{ __typename }"),
+            "only subscription operations can be subscribed to"
+        );
+        assert_eq!(
+            errors(&execute(
+                r#"subscription { messages(room: "a") { text } }"#,
+                json!({})
+            )),
+            [(
+                "subscriptions cannot be executed as a single request".to_string(),
+                vec![]
+            )]
+        );
+    }
+
+    #[test]
+    fn it_serializes_responses() {
+        let response = execute("{ users(first: 3) {\r\n  name } }", json!({}));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "errors": [{
+                    "message": "cannot return null for the non-null field `name`",
+                    "locations": [{ "line": 2, "column": 3 }],
+                    "path": ["users", 2, "name"],
+                }],
+                "data": null,
+            })
+        );
+
+        let response = execute("query($id: ID!) { user(id: $id) { id } }", json!({}));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json.get("data"), None);
+        assert_eq!(
+            json["errors"][0]["locations"],
+            json!([{ "line": 1, "column": 7 }])
+        );
+
+        let json = json!({
+            "data": { "user": null },
+            "errors": [{ "message": "not found", "path": ["user"], "extensions": { "code": 404 } }],
+        });
+        let response: Response = serde_json::from_value(json).unwrap();
+        assert_eq!(response.data().unwrap(), &json!({ "user": null }));
+        assert_eq!(
+            response.errors(),
+            [GraphQLError::new("not found")
+                .at_path(vec![PathSegment::Field("user".to_string())])
+                .extension("code", json!(404))]
+        );
+    }
+
+    #[test]
+    fn it_locates_offsets() {
+        let source = "a\nb\r\ncé\rd";
+        let locations: Vec<_> = source
+            .char_indices()
+            .map(|(offset, _)| Location::from_offset(source, offset).to_string())
+            .collect();
+        assert_eq!(
+            locations,
+            ["1:1", "1:2", "2:1", "2:2", "2:2", "3:1", "3:2", "3:3", "4:1"]
+        );
+    }
+
+    #[test]
+    fn it_shapes_raw_data() {
+        let schema = Parser::new(SCHEMA).parse().document();
+        let schema = Schema::new(&schema);
+        let query = r#"
+query($friends: Boolean!) {
+  first: node(id: "1") { __typename ...User }
+  second: node(id: "2") { __typename ...User }
+  users { id }
+}
+fragment User on User { id displayName: name friends @include(if: $friends) { name } }
+"#;
+        let query = Parser::new(query).parse().document();
+        let data = json!({
+            "first": { "__typename": "User", "id": 1, "name": "Ada", "friends": [] },
+            "second": { "__typename": "Post", "id": "p" },
+            "users": [{ "id": "1", "name": "Ada" }],
+        });
+
+        let variables = json!({ "friends": false });
+        let response = shape(&schema, &query, None, variables.as_object().unwrap(), &data);
+        assert_eq!(errors(&response), []);
+        assert_eq!(
+            response.data().unwrap(),
+            &json!({
+                "first": { "__typename": "User", "id": "1", "displayName": "Ada" },
+                "second": { "__typename": "Post" },
+                "users": [{ "id": "1" }],
+            })
+        );
+
+        let data = json!({ "users": [{ "id": null }] });
+        let response = shape(&schema, &query, None, variables.as_object().unwrap(), &data);
+        assert_eq!(response.data().unwrap(), &Json::Null);
+        assert_eq!(response.errors().len(), 1);
+    }
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value as Json};
+
+use crate::Diagnostic;
+
+/// The result of executing an operation.
+///
+/// `data` is `None` when the request failed before execution started, for
+/// example because of invalid variables, and `null` when an error propagated
+/// up to the root of the response.
+///
+/// A `Response` serializes to the JSON the spec describes: `errors` is only
+/// present when there are errors and comes first, `data` is only present when
+/// execution started, and `extensions` only when it is not empty.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::execution::{GraphQLError, Location, PathSegment, Response};
+/// use serde_json::json;
+///
+/// let error = GraphQLError::new("not found")
+///     .location(Location::new(1, 3))
+///     .at_path(vec![PathSegment::Field("user".into()), PathSegment::Index(0)]);
+/// let response = Response::new(Some(json!({ "user": [null] }).into())).error(error);
+///
+/// assert_eq!(
+///     serde_json::to_value(&response).unwrap(),
+///     json!({
+///         "errors": [{
+///             "message": "not found",
+///             "locations": [{ "line": 1, "column": 3 }],
+///             "path": ["user", 0],
+///         }],
+///         "data": { "user": [null] },
+///     })
+/// );
+/// ```
+///
+/// See: https://spec.graphql.org/October2021/#sec-Response-Format
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) errors: Vec<GraphQLError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) data: Option<ResponseValue>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub(crate) extensions: Map<String, Json>,
+}
+
+impl Response {
+    /// Create a new instance of `Response`.
+    pub fn new(data: Option<ResponseValue>) -> Self {
+        Self {
+            data,
+            ..Self::default()
+        }
+    }
+
+    /// Add an error to the response.
+    pub fn error(mut self, error: GraphQLError) -> Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Add an entry to the response's extensions.
+    pub fn extension<S: Into<String>>(mut self, key: S, value: Json) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Get the response's data, if execution started.
+    pub fn data(&self) -> Option<&ResponseValue> {
+        self.data.as_ref()
+    }
+
+    /// Get the errors raised during the request.
+    pub fn errors(&self) -> &[GraphQLError] {
+        self.errors.as_ref()
+    }
+
+    /// Get the response's extensions.
+    pub fn extensions(&self) -> &Map<String, Json> {
+        &self.extensions
+    }
+
+    /// A response for a request that failed before execution started.
+    pub(crate) fn from_diagnostics(diagnostics: Vec<Diagnostic>, source: &str) -> Self {
+        Self {
+            errors: diagnostics
+                .iter()
+                .map(|diagnostic| GraphQLError::from_diagnostic(diagnostic, source))
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// A JSON value in a response, whose objects keep their fields in the order
+/// they were selected in, as the spec requires of serialized responses.
+///
+/// Values convert to and from `serde_json::Value`, which does not keep that
+/// order, and compare equal to a `serde_json::Value` with the same fields in
+/// any order.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::execution::ResponseValue;
+/// use serde_json::json;
+///
+/// let value = ResponseValue::Object(
+///     [("b", json!(1)), ("a", json!([true]))]
+///         .into_iter()
+///         .map(|(key, value)| (key.to_string(), value.into()))
+///         .collect(),
+/// );
+///
+/// assert_eq!(value, json!({ "a": [true], "b": 1 }));
+/// assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"b":1,"a":[true]}"#);
+/// ```
+///
+/// See: https://spec.graphql.org/October2021/#sec-Serialized-Map-Ordering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ResponseValue {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<ResponseValue>),
+    Object(IndexMap<String, ResponseValue>),
+}
+
+impl ResponseValue {
+    /// Whether the value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, ResponseValue::Null)
+    }
+
+    /// Get the value as a string, if it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ResponseValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Get the items of the value, if it is an array.
+    pub fn as_array(&self) -> Option<&Vec<ResponseValue>> {
+        match self {
+            ResponseValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Get the fields of the value, in order, if it is an object.
+    pub fn as_object(&self) -> Option<&IndexMap<String, ResponseValue>> {
+        match self {
+            ResponseValue::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+impl From<Json> for ResponseValue {
+    fn from(value: Json) -> Self {
+        match value {
+            Json::Null => ResponseValue::Null,
+            Json::Bool(boolean) => ResponseValue::Bool(boolean),
+            Json::Number(number) => ResponseValue::Number(number),
+            Json::String(string) => ResponseValue::String(string),
+            Json::Array(items) => ResponseValue::Array(items.into_iter().map(Into::into).collect()),
+            Json::Object(fields) => ResponseValue::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<ResponseValue> for Json {
+    fn from(value: ResponseValue) -> Self {
+        match value {
+            ResponseValue::Null => Json::Null,
+            ResponseValue::Bool(boolean) => Json::Bool(boolean),
+            ResponseValue::Number(number) => Json::Number(number),
+            ResponseValue::String(string) => Json::String(string),
+            ResponseValue::Array(items) => Json::Array(items.into_iter().map(Into::into).collect()),
+            ResponseValue::Object(fields) => Json::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<bool> for ResponseValue {
+    fn from(boolean: bool) -> Self {
+        ResponseValue::Bool(boolean)
+    }
+}
+
+impl From<&str> for ResponseValue {
+    fn from(string: &str) -> Self {
+        ResponseValue::String(string.to_string())
+    }
+}
+
+impl From<String> for ResponseValue {
+    fn from(string: String) -> Self {
+        ResponseValue::String(string)
+    }
+}
+
+impl PartialEq<Json> for ResponseValue {
+    fn eq(&self, other: &Json) -> bool {
+        match (self, other) {
+            (ResponseValue::Null, Json::Null) => true,
+            (ResponseValue::Bool(boolean), Json::Bool(other)) => boolean == other,
+            (ResponseValue::Number(number), Json::Number(other)) => number == other,
+            (ResponseValue::String(string), Json::String(other)) => string == other,
+            (ResponseValue::Array(items), Json::Array(other)) => {
+                items.len() == other.len()
+                    && items.iter().zip(other).all(|(item, other)| item == other)
+            }
+            (ResponseValue::Object(fields), Json::Object(other)) => {
+                fields.len() == other.len()
+                    && fields
+                        .iter()
+                        .all(|(key, value)| other.get(key).map_or(false, |other| value == other))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ResponseValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// An error in a [`Response`].
+///
+/// See: https://spec.graphql.org/October2021/#sec-Errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQLError {
+    pub(crate) message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) locations: Vec<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) path: Vec<PathSegment>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub(crate) extensions: Map<String, Json>,
+}
+
+impl GraphQLError {
+    /// Create a new instance of `GraphQLError`.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            locations: Vec::new(),
+            path: Vec::new(),
+            extensions: Map::new(),
+        }
+    }
+
+    /// Create a `GraphQLError` from a diagnostic about `source`, located at
+    /// the start of each of the diagnostic's labels.
+    pub fn from_diagnostic(diagnostic: &Diagnostic, source: &str) -> Self {
+        diagnostic
+            .labels()
+            .iter()
+            .fold(Self::new(diagnostic.message()), |error, label| {
+                error.location(Location::from_offset(source, label.range().start))
+            })
+    }
+
+    /// Add a location in the executed document the error is about.
+    pub fn location(mut self, location: Location) -> Self {
+        self.locations.push(location);
+        self
+    }
+
+    /// Set the path of the response field the error was raised at.
+    pub fn at_path(mut self, path: Vec<PathSegment>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Add an entry to the error's extensions, e.g. an error `code`.
+    pub fn extension<S: Into<String>>(mut self, key: S, value: Json) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Get the error's message.
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+
+    /// Get the locations in the executed document the error is about.
+    pub fn locations(&self) -> &[Location] {
+        self.locations.as_ref()
+    }
+
+    /// Get the path of the response field the error was raised at, if it was
+    /// raised while executing a field.
+    pub fn path(&self) -> &[PathSegment] {
+        self.path.as_ref()
+    }
+
+    /// Get the error's extensions.
+    pub fn extensions(&self) -> &Map<String, Json> {
+        &self.extensions
+    }
+}
+
+impl fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = self.locations.first() {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// A line and column in a GraphQL document, both starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Location {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Location {
+    /// Create a new instance of `Location`.
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// Get the location of a byte `offset` in `source`, such as the start of
+    /// a range reported by `apollo-parser`.
+    ///
+    /// Lines end with `\n`, `\r\n` or `\r`, as in the spec's `LineTerminator`,
+    /// and columns count characters.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut location = Self::new(1, 1);
+        let mut chars = source.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if index >= offset {
+                break;
+            }
+            match c {
+                '\r' if chars.peek().map_or(false, |(_, next)| *next == '\n') => {}
+                '\n' | '\r' => {
+                    location.line += 1;
+                    location.column = 1;
+                }
+                _ => location.column += 1,
+            }
+        }
+        location
+    }
+
+    /// Get the location's line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the location's column.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An error returned by a [`Resolver`](super::Resolver). It is added to the
+/// response with the path of the field being resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub(crate) message: String,
+    pub(crate) extensions: Map<String, Json>,
+}
+
+impl FieldError {
+    /// Create a new instance of `FieldError`.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            extensions: Map::new(),
+        }
+    }
+
+    /// Add an extension to the error, e.g. an error `code`.
+    pub fn extension<S: Into<String>>(mut self, key: S, value: Json) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Get the error's message.
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+}
+
+/// A segment of the path to a response field: a field's response key, or an
+/// index in a list. Serializes to a JSON string or integer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, "{}", name),
+            PathSegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! Conversion of introspection results back into SDL, with
+//! [`to_document`].
+use apollo_encoder as encoder;
+use serde_json::{Map, Value as Json};
+
+use crate::{
+    schema::encode::{deprecated, schema_definition, specified_by},
+    Diagnostic, SchemaBuilder,
+};
+
+/// Convert the JSON result of an introspection query into an
+/// `apollo_encoder::Document`, to print it as SDL.
+///
+/// `introspection` can be a whole response (`{ "data": { "__schema": ... } }`),
+/// its data (`{ "__schema": ... }`) or the `__schema` object itself, with the
+/// fields selected by the [`INTROSPECTION_QUERY`](super::INTROSPECTION_QUERY).
+/// The `specifiedByURL` and `isRepeatable` fields are used when they are
+/// present. Built-in scalars, directives and introspection types are left
+/// out of the document.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::introspection;
+/// use indoc::indoc;
+/// use serde_json::json;
+///
+/// let introspection = json!({
+///     "__schema": {
+///         "queryType": { "name": "Query" },
+///         "types": [{
+///             "kind": "OBJECT",
+///             "name": "Query",
+///             "description": "The root",
+///             "interfaces": [],
+///             "fields": [{
+///                 "name": "me",
+///                 "args": [],
+///                 "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "String" } },
+///                 "isDeprecated": true,
+///                 "deprecationReason": "Use `you`"
+///             }]
+///         }],
+///         "directives": []
+///     }
+/// });
+/// let document = introspection::to_document(&introspection).unwrap();
+///
+/// assert_eq!(
+///     document.to_string(),
+///     indoc! { r#"
+///         "The root"
+///         type Query {
+///           me: String! @deprecated(reason: "Use `you`")
+///         }
+///     "#}
+/// );
+/// ```
+pub fn to_document(introspection: &Json) -> Result<encoder::Document, Vec<Diagnostic>> {
+    let schema = if let Some(data) = introspection.get("data") {
+        data.get("__schema")
+    } else if let Some(schema) = introspection.get("__schema") {
+        Some(schema)
+    } else {
+        Some(introspection)
+    };
+    let mut reader = Reader::default();
+    let document = schema
+        .and_then(|schema| reader.object(schema))
+        .map(|schema| reader.schema(schema));
+
+    match document {
+        Some(document) if reader.errors.is_empty() => Ok(document),
+        _ if reader.errors.is_empty() => Err(vec![Diagnostic::new(
+            "the introspection result has no `__schema` field",
+        )]),
+        _ => Err(reader.errors),
+    }
+}
+
+/// Reads introspection JSON, reporting where it does not have the expected
+/// shape.
+#[derive(Default)]
+struct Reader {
+    /// Where in the JSON the reader is, e.g. `types[3].fields[0]`.
+    path: Vec<String>,
+    errors: Vec<Diagnostic>,
+}
+
+impl Reader {
+    fn schema(&mut self, schema: &Map<String, Json>) -> encoder::Document {
+        let mut document = encoder::Document::new();
+        let prelude = SchemaBuilder::new().build();
+
+        let query = self.root_type(schema, "queryType");
+        let mutation = self.root_type(schema, "mutationType");
+        let subscription = self.root_type(schema, "subscriptionType");
+        if let Some(schema) = schema_definition(
+            query.as_deref(),
+            mutation.as_deref(),
+            subscription.as_deref(),
+        ) {
+            document.schema(schema);
+        }
+
+        self.each(schema, "types", |reader, ty| {
+            if let Some(name) = reader.string(ty, "name") {
+                if prelude.type_definition(&name).is_none() {
+                    reader.type_definition(&mut document, ty, name);
+                }
+            }
+        });
+
+        self.each(schema, "directives", |reader, directive| {
+            if let Some(name) = reader.string(directive, "name") {
+                if prelude.directive_definition(&name).is_none() {
+                    document.directive(reader.directive_definition(directive, name));
+                }
+            }
+        });
+
+        document
+    }
+
+    fn root_type(&mut self, schema: &Map<String, Json>, key: &str) -> Option<String> {
+        self.path.push(key.to_string());
+        let name = self
+            .nullable_object(schema, key)
+            .and_then(|ty| self.string(ty, "name"));
+        self.path.pop();
+        name
+    }
+
+    fn type_definition(
+        &mut self,
+        document: &mut encoder::Document,
+        ty: &Map<String, Json>,
+        name: String,
+    ) {
+        let description = self.optional_string(ty, "description");
+        match self.string(ty, "kind").as_deref() {
+            Some("SCALAR") => {
+                let mut def = encoder::ScalarDefinition::new(name);
+                def.description(description);
+                if let Some(url) = self.optional_string(ty, "specifiedByURL") {
+                    def.directive(specified_by(&url));
+                }
+                document.scalar(def);
+            }
+            Some("OBJECT") => {
+                let mut def = encoder::ObjectDefinition::new(name);
+                def.description(description);
+                for interface in self.type_names(ty, "interfaces") {
+                    def.interface(interface);
+                }
+                for field in self.fields(ty) {
+                    def.field(field);
+                }
+                document.object(def);
+            }
+            Some("INTERFACE") => {
+                let mut def = encoder::InterfaceDefinition::new(name);
+                def.description(description);
+                for interface in self.type_names(ty, "interfaces") {
+                    def.interface(interface);
+                }
+                for field in self.fields(ty) {
+                    def.field(field);
+                }
+                document.interface(def);
+            }
+            Some("UNION") => {
+                let mut def = encoder::UnionDefinition::new(name);
+                def.description(description);
+                for member in self.type_names(ty, "possibleTypes") {
+                    def.member(member);
+                }
+                document.union(def);
+            }
+            Some("ENUM") => {
+                let mut def = encoder::EnumDefinition::new(name);
+                def.description(description);
+                self.each(ty, "enumValues", |reader, value| {
+                    if let Some(name) = reader.string(value, "name") {
+                        let mut enum_value = encoder::EnumValue::new(name);
+                        enum_value.description(reader.optional_string(value, "description"));
+                        if let Some(reason) = reader.deprecation_reason(value) {
+                            enum_value.directive(deprecated(&reason));
+                        }
+                        def.value(enum_value);
+                    }
+                });
+                document.enum_(def);
+            }
+            Some("INPUT_OBJECT") => {
+                let mut def = encoder::InputObjectDefinition::new(name);
+                def.description(description);
+                self.each(ty, "inputFields", |reader, field| {
+                    if let Some((name, ty)) = reader.name_and_type(field) {
+                        let mut input_field = encoder::InputField::new(name, ty);
+                        input_field.description(reader.optional_string(field, "description"));
+                        input_field.default(reader.optional_string(field, "defaultValue"));
+                        if let Some(reason) = reader.deprecation_reason(field) {
+                            input_field.directive(deprecated(&reason));
+                        }
+                        def.field(input_field);
+                    }
+                });
+                document.input_object(def);
+            }
+            Some(kind) => self.error(format!("unknown type kind `{}`", kind)),
+            None => (),
+        }
+    }
+
+    fn fields(&mut self, ty: &Map<String, Json>) -> Vec<encoder::FieldDefinition> {
+        let mut fields = Vec::new();
+        self.each(ty, "fields", |reader, field| {
+            if let Some((name, ty)) = reader.name_and_type(field) {
+                let mut def = encoder::FieldDefinition::new(name, ty);
+                def.description(reader.optional_string(field, "description"));
+                for arg in reader.input_values(field) {
+                    def.arg(arg);
+                }
+                if let Some(reason) = reader.deprecation_reason(field) {
+                    def.directive(deprecated(&reason));
+                }
+                fields.push(def);
+            }
+        });
+        fields
+    }
+
+    fn directive_definition(
+        &mut self,
+        directive: &Map<String, Json>,
+        name: String,
+    ) -> encoder::DirectiveDefinition {
+        let mut def = encoder::DirectiveDefinition::new(name);
+        def.description(self.optional_string(directive, "description"));
+        for arg in self.input_values(directive) {
+            def.arg(arg);
+        }
+        if directive.get("isRepeatable") == Some(&Json::Bool(true)) {
+            def.repeatable();
+        }
+        self.path.push("locations".to_string());
+        match directive.get("locations") {
+            Some(Json::Array(locations)) => {
+                for location in locations {
+                    match location {
+                        Json::String(location) => def.location(location.clone()),
+                        _ => self.error("expected a directive location"),
+                    }
+                }
+            }
+            _ => self.error("expected a list of directive locations"),
+        }
+        self.path.pop();
+        def
+    }
+
+    /// Get the `args` of a field or a directive.
+    fn input_values(&mut self, parent: &Map<String, Json>) -> Vec<encoder::InputValueDefinition> {
+        let mut args = Vec::new();
+        self.each(parent, "args", |reader, arg| {
+            if let Some((name, ty)) = reader.name_and_type(arg) {
+                let mut def = encoder::InputValueDefinition::new(name, ty);
+                def.description(reader.optional_string(arg, "description"));
+                def.default(reader.optional_string(arg, "defaultValue"));
+                if let Some(reason) = reader.deprecation_reason(arg) {
+                    def.directive(deprecated(&reason));
+                }
+                args.push(def);
+            }
+        });
+        args
+    }
+
+    fn name_and_type(&mut self, value: &Map<String, Json>) -> Option<(String, encoder::Type_)> {
+        let name = self.string(value, "name");
+        self.path.push("type".to_string());
+        let ty = match value.get("type") {
+            Some(ty) => self.type_ref(ty),
+            None => {
+                self.error("expected a type");
+                None
+            }
+        };
+        self.path.pop();
+        Some((name?, ty?))
+    }
+
+    /// Read a `__Type` reference, e.g. `{ "kind": "LIST", "ofType": { ... } }`.
+    fn type_ref(&mut self, ty: &Json) -> Option<encoder::Type_> {
+        let ty = self.object(ty)?;
+        match self.string(ty, "kind")?.as_str() {
+            kind @ ("LIST" | "NON_NULL") => {
+                self.path.push("ofType".to_string());
+                let of_type = match ty.get("ofType") {
+                    Some(of_type) => self.type_ref(of_type),
+                    None => {
+                        self.error("expected the wrapped type");
+                        None
+                    }
+                };
+                self.path.pop();
+                let ty = Box::new(of_type?);
+                Some(match kind {
+                    "LIST" => encoder::Type_::List { ty },
+                    _ => encoder::Type_::NonNull { ty },
+                })
+            }
+            _ => Some(encoder::Type_::NamedType {
+                name: self.string(ty, "name")?,
+            }),
+        }
+    }
+
+    /// Get the names of the types listed under `key`, e.g. `interfaces`.
+    fn type_names(&mut self, ty: &Map<String, Json>, key: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        self.each(ty, key, |reader, ty| {
+            names.extend(reader.string(ty, "name"))
+        });
+        names
+    }
+
+    fn deprecation_reason(&mut self, value: &Map<String, Json>) -> Option<String> {
+        if value.get("isDeprecated") != Some(&Json::Bool(true)) {
+            return None;
+        }
+        Some(
+            self.optional_string(value, "deprecationReason")
+                .unwrap_or_else(|| crate::schema::DEFAULT_DEPRECATION_REASON.to_string()),
+        )
+    }
+
+    /// Call `f` with each object of the list under `key`, with the item's
+    /// index in the path. A missing or `null` list is empty.
+    fn each<'j>(
+        &mut self,
+        parent: &'j Map<String, Json>,
+        key: &str,
+        mut f: impl FnMut(&mut Self, &'j Map<String, Json>),
+    ) {
+        match parent.get(key) {
+            None | Some(Json::Null) => (),
+            Some(Json::Array(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.path.push(format!("{}[{}]", key, i));
+                    if let Some(object) = self.object(item) {
+                        f(self, object);
+                    }
+                    self.path.pop();
+                }
+            }
+            Some(_) => {
+                self.path.push(key.to_string());
+                self.error("expected a list");
+                self.path.pop();
+            }
+        }
+    }
+
+    fn object<'j>(&mut self, value: &'j Json) -> Option<&'j Map<String, Json>> {
+        match value {
+            Json::Object(object) => Some(object),
+            _ => {
+                self.error("expected an object");
+                None
+            }
+        }
+    }
+
+    fn nullable_object<'j>(
+        &mut self,
+        parent: &'j Map<String, Json>,
+        key: &str,
+    ) -> Option<&'j Map<String, Json>> {
+        match parent.get(key) {
+            None | Some(Json::Null) => None,
+            Some(value) => self.object(value),
+        }
+    }
+
+    /// Get the string under `key`, which must be present.
+    fn string(&mut self, parent: &Map<String, Json>, key: &str) -> Option<String> {
+        match parent.get(key) {
+            Some(Json::String(string)) => Some(string.clone()),
+            _ => {
+                self.path.push(key.to_string());
+                self.error("expected a string");
+                self.path.pop();
+                None
+            }
+        }
+    }
+
+    /// Get the string under `key`, if it is present and not `null`.
+    fn optional_string(&mut self, parent: &Map<String, Json>, key: &str) -> Option<String> {
+        match parent.get(key) {
+            None | Some(Json::Null) => None,
+            Some(_) => self.string(parent, key),
+        }
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        let path = if self.path.is_empty() {
+            "__schema".to_string()
+        } else {
+            format!("__schema.{}", self.path.join("."))
+        };
+        self.errors.push(Diagnostic::new(format!(
+            "invalid introspection result at `{}`: {}",
+            path,
+            message.into()
+        )));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{introspection, Schema};
+
+    #[test]
+    fn it_prints_the_schema_it_was_introspected_from() {
+        let sdl = r#"
+schema { query: Root mutation: Mutation }
+"The root"
+type Root implements Node {
+  id: ID!
+  "Search everything"
+  search(filter: Filter, first: Int = 10): [Result!]!
+  old: String @deprecated(reason: "Use `search`")
+}
+type Mutation { reset(all: Boolean = true): Boolean }
+interface Node { id: ID! }
+union Result = Root | Mutation
+enum Size { SMALL LARGE @deprecated }
+input Filter { size: Size = SMALL tags: [String!] = ["new"] }
+scalar Date
+directive @tag(name: String!) on FIELD_DEFINITION | OBJECT
+"#;
+        let schema = Schema::new(&Parser::new(sdl).parse().document());
+        assert_eq!(schema.errors(), []);
+
+        let query = Parser::new(introspection::INTROSPECTION_QUERY)
+            .parse()
+            .document();
+        let response =
+            introspection::execute(&schema, &query, None, &serde_json::Map::new()).unwrap();
+
+        let printed = introspection::to_document(&serde_json::Value::from(response))
+            .unwrap()
+            .to_string();
+        assert_eq!(printed, schema.to_document().to_string());
+        assert_eq!(Parser::new(&printed).parse().errors().len(), 0);
+    }
+
+    #[test]
+    fn it_reads_optional_fields() {
+        let introspection = json!({
+            "queryType": { "name": "Query" },
+            "types": [
+                {
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [{ "name": "now", "args": [], "type": { "kind": "SCALAR", "name": "Date" } }]
+                },
+                { "kind": "SCALAR", "name": "Date", "specifiedByURL": "https://example.com" },
+                { "kind": "SCALAR", "name": "String" }
+            ],
+            "directives": [
+                { "name": "tag", "isRepeatable": true, "locations": ["FIELD"], "args": [] },
+                { "name": "skip", "locations": ["FIELD"], "args": [] }
+            ]
+        });
+        let printed = introspection::to_document(&introspection)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            printed,
+            indoc::indoc! { r#"
+                scalar Date @specifiedBy(url: "https://example.com")
+                type Query {
+                  now: Date
+                }
+                directive @tag repeatable on FIELD
+            "#}
+        );
+        assert_eq!(Parser::new(&printed).parse().errors().len(), 0);
+    }
+
+    #[test]
+    fn it_reports_malformed_results() {
+        let introspection = json!({
+            "data": {
+                "__schema": {
+                    "queryType": { "name": "Query" },
+                    "types": [
+                        { "kind": "OBJECT", "name": "Query", "fields": [{ "name": "a" }] },
+                        { "kind": "ENUM", "name": "Size", "enumValues": [{ "name": 1 }] },
+                        { "kind": "CLASS", "name": "Mystery" },
+                        "Date"
+                    ],
+                    "directives": [{ "name": "tag" }]
+                }
+            }
+        });
+        let messages: Vec<_> = introspection::to_document(&introspection)
+            .unwrap_err()
+            .iter()
+            .map(|error| error.message().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "invalid introspection result at `__schema.types[0].fields[0].type`: expected a type",
+                "invalid introspection result at `__schema.types[1].enumValues[0].name`: expected a string",
+                "invalid introspection result at `__schema.types[2]`: unknown type kind `CLASS`",
+                "invalid introspection result at `__schema.types[3]`: expected an object",
+                "invalid introspection result at `__schema.directives[0].locations`: expected a list of directive locations",
+            ]
+        );
+
+        assert_eq!(
+            introspection::to_document(&json!({ "data": null })).unwrap_err()[0].message(),
+            "the introspection result has no `__schema` field"
+        );
+    }
+}
@@ -0,0 +1,832 @@
+//! Execution of introspection queries against a [`Schema`].
+//!
+//! Everything the `__schema`, `__type` and `__typename` meta-fields return is
+//! part of the type system, so a schema built from SDL can answer
+//! introspection queries without any resolvers. The response has the same
+//! shape and values as graphql-js returns for the same schema, so the
+//! [`INTROSPECTION_QUERY`] sent by GraphQL clients and tools can be served
+//! from SDL alone.
+//!
+//! The JSON result of an introspection query can also be turned back into
+//! SDL with [`to_document`].
+//!
+//! See: https://spec.graphql.org/October2021/#sec-Introspection
+mod encode;
+
+use std::collections::HashSet;
+
+use apollo_parser::ast;
+use indexmap::{IndexMap, IndexSet};
+use serde_json::{Map, Value as Json};
+
+use crate::{
+    coercion,
+    diagnostics::{self, Diagnostic},
+    execution::{operation, FieldCollector, ResponseValue},
+    schema::{EnumValueDefinition, FieldDefinition, InputValueDefinition},
+    DirectiveDefinition, Schema, Type, TypeDefinition, TypeKind, Value,
+};
+
+pub use encode::to_document;
+
+/// The introspection query GraphQL tools send to learn about a schema, as
+/// written by graphql-js' `getIntrospectionQuery()`.
+pub const INTROSPECTION_QUERY: &str = include_str!("query.graphql");
+
+/// Execute an introspection query against `schema`, returning the response
+/// as JSON: an object with the query's result under `"data"`, with fields in
+/// the order they were selected in.
+///
+/// `operation_name` selects the operation to execute when the document has
+/// several. Only the `__schema`, `__type` and `__typename` fields can be
+/// selected on the query root operation type; selecting any other field, an
+/// invalid argument or variable, or an operation that is not a query returns
+/// the diagnostics found instead.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{introspection, Schema};
+/// use apollo_parser::Parser;
+/// use serde_json::json;
+///
+/// let schema = Parser::new("type Query { me: String @deprecated }").parse().document();
+/// let schema = Schema::new(&schema);
+///
+/// let query = r#"
+/// query Fields($deprecated: Boolean!) {
+///   __type(name: "Query") {
+///     name
+///     fields(includeDeprecated: $deprecated) { name deprecationReason }
+///   }
+/// }
+/// "#;
+/// let query = Parser::new(query).parse().document();
+/// let variables = json!({ "deprecated": true });
+/// let response =
+///     introspection::execute(&schema, &query, None, variables.as_object().unwrap()).unwrap();
+///
+/// assert_eq!(
+///     response,
+///     json!({
+///         "data": {
+///             "__type": {
+///                 "name": "Query",
+///                 "fields": [{ "name": "me", "deprecationReason": "No longer supported" }]
+///             }
+///         }
+///     })
+/// );
+/// ```
+pub fn execute(
+    schema: &Schema,
+    document: &ast::Document,
+    operation_name: Option<&str>,
+    variables: &Map<String, Json>,
+) -> Result<ResponseValue, Vec<Diagnostic>> {
+    let operation = operation(document, operation_name)?;
+    let is_query = operation
+        .operation_type()
+        .map_or(true, |ty| ty.query_token().is_some());
+    if !is_query {
+        return Err(vec![Diagnostic::new(
+            "only query operations can be introspected",
+        )
+        .label(diagnostics::range(&operation), "not a query")]);
+    }
+    let query_type = schema.query_type().ok_or_else(|| {
+        vec![
+            Diagnostic::new("the schema has no query root operation type")
+                .help("define a `Query` type, or set the query type with `schema { query: ... }`"),
+        ]
+    })?;
+    let variables = coercion::coerce_variables(schema, &operation, variables)?;
+    let collector = FieldCollector::new(schema, document, &variables);
+
+    let mut execution = Execution::new(schema, &collector);
+    let mut grouped = IndexMap::new();
+    if let Some(selection_set) = operation.selection_set() {
+        collector.collect(
+            query_type,
+            &selection_set,
+            &mut grouped,
+            &mut HashSet::new(),
+            &mut execution.errors,
+        );
+    }
+    let data = execution.fields(&Object::Query(query_type), grouped);
+
+    if execution.errors.is_empty() {
+        let mut response = IndexMap::new();
+        response.insert("data".to_string(), data);
+        Ok(ResponseValue::Object(response))
+    } else {
+        Err(execution.errors)
+    }
+}
+
+/// Execute the `__schema` or `__type` meta-field selected by `fields` on the
+/// query root operation type, for the [`Executor`](crate::execution::Executor).
+pub(crate) fn meta_field(
+    schema: &Schema,
+    collector: &FieldCollector,
+    fields: &[ast::Field],
+) -> Result<ResponseValue, Vec<Diagnostic>> {
+    let (query_type, name) = match (schema.query_type(), fields[0].name()) {
+        (Some(query_type), Some(name)) => (query_type, name.text().to_string()),
+        _ => return Ok(ResponseValue::Null),
+    };
+    let mut execution = Execution::new(schema, collector);
+    let value = execution.field(&Object::Query(query_type), fields, &name);
+    if execution.errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(execution.errors)
+    }
+}
+
+/// The introspection objects fields are selected on.
+enum Object<'a> {
+    /// The query root operation type, with its name.
+    Query(&'a str),
+    Schema,
+    Type(IntrospectedType<'a>),
+    Field(&'a FieldDefinition),
+    InputValue(&'a InputValueDefinition),
+    EnumValue(&'a EnumValueDefinition),
+    Directive(&'a DirectiveDefinition),
+}
+
+impl<'a> Object<'a> {
+    fn type_name(&self) -> &'a str {
+        match self {
+            Object::Query(name) => name,
+            Object::Schema => "__Schema",
+            Object::Type(_) => "__Type",
+            Object::Field(_) => "__Field",
+            Object::InputValue(_) => "__InputValue",
+            Object::EnumValue(_) => "__EnumValue",
+            Object::Directive(_) => "__Directive",
+        }
+    }
+}
+
+/// A `__Type`: a named type, or a list or non-null type wrapping `of_type`.
+enum IntrospectedType<'a> {
+    Named(&'a TypeDefinition),
+    List(&'a Type),
+    NonNull(&'a Type),
+}
+
+/// The result of resolving a field, before its selection set is applied.
+enum Resolved<'a> {
+    Leaf(ResponseValue),
+    Object(Option<Object<'a>>),
+    List(Option<Vec<Object<'a>>>),
+}
+
+impl<'a> Resolved<'a> {
+    fn string(string: Option<&str>) -> Self {
+        Resolved::Leaf(string.map_or(ResponseValue::Null, Into::into))
+    }
+}
+
+struct Execution<'a> {
+    schema: &'a Schema,
+    collector: &'a FieldCollector<'a>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> Execution<'a> {
+    fn new(schema: &'a Schema, collector: &'a FieldCollector<'a>) -> Self {
+        Self {
+            schema,
+            collector,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Execute the fields selected on `object`, grouped by response key.
+    fn fields(
+        &mut self,
+        object: &Object<'a>,
+        grouped: IndexMap<String, Vec<ast::Field>>,
+    ) -> ResponseValue {
+        let mut map = IndexMap::new();
+        for (key, fields) in grouped {
+            let name = match fields[0].name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let value = if name == "__typename" {
+                object.type_name().into()
+            } else {
+                self.field(object, &fields, &name)
+            };
+            map.insert(key, value);
+        }
+        ResponseValue::Object(map)
+    }
+
+    /// Execute the field `name`, selected by `fields`, on `object`.
+    fn field(&mut self, object: &Object<'a>, fields: &[ast::Field], name: &str) -> ResponseValue {
+        match self.resolve(object, &fields[0], name) {
+            Resolved::Leaf(value) => value,
+            Resolved::Object(Some(inner)) => self.object(&inner, fields),
+            Resolved::List(Some(items)) => {
+                ResponseValue::Array(items.iter().map(|item| self.object(item, fields)).collect())
+            }
+            Resolved::Object(None) | Resolved::List(None) => ResponseValue::Null,
+        }
+    }
+
+    /// Execute the merged selection sets of `fields` on `object`.
+    fn object(&mut self, object: &Object<'a>, fields: &[ast::Field]) -> ResponseValue {
+        let mut grouped = IndexMap::new();
+        for selection_set in fields.iter().filter_map(|field| field.selection_set()) {
+            self.collector.collect(
+                object.type_name(),
+                &selection_set,
+                &mut grouped,
+                &mut HashSet::new(),
+                &mut self.errors,
+            );
+        }
+        self.fields(object, grouped)
+    }
+
+    fn resolve(&mut self, object: &Object<'a>, field: &ast::Field, name: &str) -> Resolved<'a> {
+        let schema = self.schema;
+        let type_name = object.type_name();
+        let definition = match schema.field_definition(type_name, name) {
+            Some(definition) => definition,
+            None => {
+                self.errors.push(
+                    Diagnostic::new(format!("the type `{}` has no field `{}`", type_name, name))
+                        .label(diagnostics::range(field), "field not defined"),
+                );
+                return Resolved::Leaf(ResponseValue::Null);
+            }
+        };
+        let arguments = match coercion::coerce_arguments(
+            schema,
+            definition.arguments(),
+            field.arguments().as_ref(),
+            self.collector.variables,
+        ) {
+            Ok(arguments) => arguments,
+            Err(mut errors) => {
+                self.errors.append(&mut errors);
+                return Resolved::Leaf(ResponseValue::Null);
+            }
+        };
+        let include_deprecated = arguments.get("includeDeprecated") == Some(&Value::Boolean(true));
+        let deprecated = |is_deprecated: bool| include_deprecated || !is_deprecated;
+
+        match object {
+            Object::Query(_) => match name {
+                "__schema" => Resolved::Object(Some(Object::Schema)),
+                "__type" => match arguments.get("name") {
+                    Some(Value::String(name)) => Resolved::Object(self.named(name)),
+                    _ => Resolved::Object(None),
+                },
+                _ => {
+                    self.errors.push(
+                        Diagnostic::new(format!(
+                            "the field `{}.{}` cannot be resolved by introspection",
+                            type_name, name
+                        ))
+                        .label(diagnostics::range(field), "not an introspection field")
+                        .help("only `__schema`, `__type` and `__typename` can be selected"),
+                    );
+                    Resolved::Leaf(ResponseValue::Null)
+                }
+            },
+            Object::Schema => match name {
+                "types" => Resolved::List(Some(
+                    schema_types(schema)
+                        .into_iter()
+                        .filter_map(|name| self.named(name))
+                        .collect(),
+                )),
+                "queryType" => Resolved::Object(schema.query_type().and_then(|ty| self.named(ty))),
+                "mutationType" => {
+                    Resolved::Object(schema.mutation_type().and_then(|ty| self.named(ty)))
+                }
+                "subscriptionType" => {
+                    Resolved::Object(schema.subscription_type().and_then(|ty| self.named(ty)))
+                }
+                "directives" => Resolved::List(Some(
+                    schema_directives(schema).map(Object::Directive).collect(),
+                )),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::Type(IntrospectedType::Named(ty)) => match name {
+                "kind" => Resolved::Leaf(kind(ty.kind()).into()),
+                "name" => Resolved::string(Some(ty.name())),
+                "description" => Resolved::string(ty.description()),
+                "specifiedByURL" => match ty {
+                    TypeDefinition::Scalar(scalar) => Resolved::string(scalar.specified_by_url()),
+                    _ => Resolved::Leaf(ResponseValue::Null),
+                },
+                "fields" => match ty {
+                    TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                        Resolved::List(Some(
+                            ty.fields()
+                                .iter()
+                                .filter(|field| deprecated(field.is_deprecated()))
+                                .map(Object::Field)
+                                .collect(),
+                        ))
+                    }
+                    _ => Resolved::List(None),
+                },
+                "interfaces" => {
+                    let interfaces = match ty {
+                        TypeDefinition::Object(object) => object.implements_interfaces(),
+                        TypeDefinition::Interface(interface) => interface.implements_interfaces(),
+                        _ => return Resolved::List(None),
+                    };
+                    Resolved::List(Some(
+                        interfaces
+                            .iter()
+                            .filter_map(|name| self.named(name))
+                            .collect(),
+                    ))
+                }
+                "possibleTypes" if ty.is_abstract() => Resolved::List(Some(
+                    schema
+                        .possible_types(ty.name())
+                        .into_iter()
+                        .filter_map(|name| self.named(name))
+                        .collect(),
+                )),
+                "enumValues" => match ty {
+                    TypeDefinition::Enum(enum_ty) => Resolved::List(Some(
+                        enum_ty
+                            .values()
+                            .iter()
+                            .filter(|value| deprecated(value.is_deprecated()))
+                            .map(Object::EnumValue)
+                            .collect(),
+                    )),
+                    _ => Resolved::List(None),
+                },
+                "inputFields" => match ty {
+                    TypeDefinition::InputObject(input) => Resolved::List(Some(
+                        input
+                            .fields()
+                            .iter()
+                            .filter(|field| deprecated(field.is_deprecated()))
+                            .map(Object::InputValue)
+                            .collect(),
+                    )),
+                    _ => Resolved::List(None),
+                },
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::Type(IntrospectedType::List(of_type)) => match name {
+                "kind" => Resolved::Leaf("LIST".into()),
+                "ofType" => Resolved::Object(self.ty(of_type)),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::Type(IntrospectedType::NonNull(of_type)) => match name {
+                "kind" => Resolved::Leaf("NON_NULL".into()),
+                "ofType" => Resolved::Object(self.ty(of_type)),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::Field(field) => match name {
+                "name" => Resolved::string(Some(field.name())),
+                "description" => Resolved::string(field.description()),
+                "args" => Resolved::List(Some(
+                    field
+                        .arguments()
+                        .iter()
+                        .filter(|arg| deprecated(arg.is_deprecated()))
+                        .map(Object::InputValue)
+                        .collect(),
+                )),
+                "type" => Resolved::Object(self.ty(field.ty())),
+                "isDeprecated" => Resolved::Leaf(field.is_deprecated().into()),
+                "deprecationReason" => Resolved::string(field.deprecation_reason()),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::InputValue(input) => match name {
+                "name" => Resolved::string(Some(input.name())),
+                "description" => Resolved::string(input.description()),
+                "type" => Resolved::Object(self.ty(input.ty())),
+                "defaultValue" => {
+                    Resolved::string(input.default_value().map(print_value).as_deref())
+                }
+                "isDeprecated" => Resolved::Leaf(input.is_deprecated().into()),
+                "deprecationReason" => Resolved::string(input.deprecation_reason()),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::EnumValue(value) => match name {
+                "name" => Resolved::string(Some(value.value())),
+                "description" => Resolved::string(value.description()),
+                "isDeprecated" => Resolved::Leaf(value.is_deprecated().into()),
+                "deprecationReason" => Resolved::string(value.deprecation_reason()),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+            Object::Directive(directive) => match name {
+                "name" => Resolved::string(Some(directive.name())),
+                "description" => Resolved::string(directive.description()),
+                "isRepeatable" => Resolved::Leaf(directive.is_repeatable().into()),
+                "locations" => Resolved::Leaf(ResponseValue::Array(
+                    directive
+                        .locations()
+                        .iter()
+                        .map(|location| location.name().into())
+                        .collect(),
+                )),
+                "args" => Resolved::List(Some(
+                    directive
+                        .arguments()
+                        .iter()
+                        .filter(|arg| deprecated(arg.is_deprecated()))
+                        .map(Object::InputValue)
+                        .collect(),
+                )),
+                _ => Resolved::Leaf(ResponseValue::Null),
+            },
+        }
+    }
+
+    /// Get the `__Type` of a named type, if the schema defines it.
+    fn named(&self, name: &str) -> Option<Object<'a>> {
+        let ty = self.schema.type_definition(name)?;
+        Some(Object::Type(IntrospectedType::Named(ty)))
+    }
+
+    /// Get the `__Type` of a type reference.
+    fn ty(&self, ty: &'a Type) -> Option<Object<'a>> {
+        match ty {
+            Type::Named(name) => self.named(name),
+            Type::List(of_type) => Some(Object::Type(IntrospectedType::List(of_type))),
+            Type::NonNull(of_type) => Some(Object::Type(IntrospectedType::NonNull(of_type))),
+        }
+    }
+}
+
+/// Get the directives of `__Schema.directives`: the defined directives, then
+/// the built-in ones.
+fn schema_directives(schema: &Schema) -> impl Iterator<Item = &DirectiveDefinition> {
+    let (built_in, defined): (Vec<_>, Vec<_>) = schema
+        .directives()
+        .partition(|directive| directive.is_built_in());
+    defined.into_iter().chain(built_in)
+}
+
+/// Get the names of the types of `__Schema.types`, in the order graphql-js
+/// lists them: each defined type, in order, followed by the built-in types it
+/// uses that are not listed yet, then the built-in types used by directives
+/// and by the introspection types. Built-in scalars that are never used are
+/// left out.
+///
+/// See: https://github.com/graphql/graphql-js/blob/v16.6.0/src/type/schema.ts#L174-L215
+fn schema_types(schema: &Schema) -> IndexSet<&str> {
+    let defined: Vec<_> = schema.types().filter(|ty| !ty.is_built_in()).collect();
+    let mut types: IndexSet<&str> = defined.iter().map(|ty| ty.name()).collect();
+    for ty in defined {
+        // A defined type moves after the ones before it, with the types it uses.
+        types.shift_remove(ty.name());
+        collect_types(schema, ty.name(), &mut types);
+    }
+    for directive in schema_directives(schema) {
+        for arg in directive.arguments() {
+            collect_types(schema, arg.ty().name(), &mut types);
+        }
+    }
+    collect_types(schema, "__Schema", &mut types);
+    types
+}
+
+/// Add the type `name` and the types it uses, depth first, to `types`.
+fn collect_types<'a>(schema: &'a Schema, name: &'a str, types: &mut IndexSet<&'a str>) {
+    if !types.insert(name) {
+        return;
+    }
+    let ty = match schema.type_definition(name) {
+        Some(ty) => ty,
+        None => return,
+    };
+    match ty {
+        TypeDefinition::Union(union) => {
+            for member in union.members() {
+                collect_types(schema, member, types);
+            }
+        }
+        TypeDefinition::InputObject(input) => {
+            for field in input.fields() {
+                collect_types(schema, field.ty().name(), types);
+            }
+        }
+        _ => {
+            let interfaces = match ty {
+                TypeDefinition::Object(object) => object.implements_interfaces(),
+                TypeDefinition::Interface(interface) => interface.implements_interfaces(),
+                _ => &[],
+            };
+            for interface in interfaces {
+                collect_types(schema, interface, types);
+            }
+            for field in ty.fields() {
+                collect_types(schema, field.ty().name(), types);
+                for arg in field.arguments() {
+                    collect_types(schema, arg.ty().name(), types);
+                }
+            }
+        }
+    }
+}
+
+/// Get the `__TypeKind` value of a named type.
+fn kind(kind: TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "SCALAR",
+        TypeKind::Object => "OBJECT",
+        TypeKind::Interface => "INTERFACE",
+        TypeKind::Union => "UNION",
+        TypeKind::Enum => "ENUM",
+        TypeKind::InputObject => "INPUT_OBJECT",
+    }
+}
+
+/// Print a default value the way graphql-js does for `defaultValue`, e.g.
+/// `{size: SMALL, tags: ["a"]}`.
+fn print_value(value: &Value) -> String {
+    match value {
+        Value::List(values) => {
+            let values: Vec<_> = values.iter().map(print_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, print_value(value)))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+"The root"
+type Query implements Node {
+  id: ID!
+  search(filter: Filter = { size: SMALL, tags: ["new"] }, first: Int = 10): [Result!]!
+  old: String @deprecated(reason: "Use `search`")
+}
+interface Node { id: ID! }
+union Result = Query
+enum Size { SMALL LARGE @deprecated }
+input Filter { size: Size tags: [String!] }
+scalar Date @specifiedBy(url: "https://example.com/date")
+directive @tag(name: String!) repeatable on FIELD_DEFINITION
+"#;
+
+    fn execute(query: &str, variables: Json) -> Result<Json, Vec<String>> {
+        let schema = Schema::new(&Parser::new(SCHEMA).parse().document());
+        assert_eq!(schema.errors(), []);
+        let document = Parser::new(query).parse().document();
+        super::execute(&schema, &document, None, variables.as_object().unwrap())
+            .map(Json::from)
+            .map_err(|errors| {
+                errors
+                    .iter()
+                    .map(|error| error.message().to_string())
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn it_executes_the_introspection_query() {
+        let response = execute(INTROSPECTION_QUERY, json!({})).unwrap();
+        let schema = &response["data"]["__schema"];
+
+        assert_eq!(schema["queryType"], json!({ "name": "Query" }));
+        assert_eq!(schema["mutationType"], Json::Null);
+
+        // Types are in the order of graphql-js: each defined type comes with
+        // the built-in types it uses first, and built-in scalars are only
+        // listed when they are used.
+        let types: Vec<_> = schema["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "Query",
+                "ID",
+                "Int",
+                "String",
+                "Node",
+                "Result",
+                "Size",
+                "Filter",
+                "Date",
+                "Boolean",
+                "__Schema",
+                "__Type",
+                "__TypeKind",
+                "__Field",
+                "__InputValue",
+                "__EnumValue",
+                "__Directive",
+                "__DirectiveLocation"
+            ]
+        );
+
+        let query = &schema["types"][0];
+        assert_eq!(query["kind"], "OBJECT");
+        assert_eq!(query["description"], "The root");
+        assert_eq!(
+            query["interfaces"],
+            json!([{ "kind": "INTERFACE", "name": "Node", "ofType": null }])
+        );
+        assert_eq!(query["possibleTypes"], Json::Null);
+        assert_eq!(
+            query["fields"][1]["args"],
+            json!([
+                {
+                    "name": "filter",
+                    "description": null,
+                    "type": { "kind": "INPUT_OBJECT", "name": "Filter", "ofType": null },
+                    "defaultValue": "{size: SMALL, tags: [\"new\"]}"
+                },
+                {
+                    "name": "first",
+                    "description": null,
+                    "type": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                    "defaultValue": "10"
+                }
+            ])
+        );
+        assert_eq!(
+            query["fields"][1]["type"],
+            json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": {
+                    "kind": "LIST",
+                    "name": null,
+                    "ofType": {
+                        "kind": "NON_NULL",
+                        "name": null,
+                        "ofType": {
+                            "kind": "UNION",
+                            "name": "Result",
+                            "ofType": null,
+                        }
+                    }
+                }
+            })
+        );
+        assert_eq!(query["fields"][2]["isDeprecated"], true);
+        assert_eq!(query["fields"][2]["deprecationReason"], "Use `search`");
+
+        let result = &schema["types"][5];
+        assert_eq!(result["kind"], "UNION");
+        assert_eq!(result["fields"], Json::Null);
+        assert_eq!(result["possibleTypes"][0]["name"], "Query");
+
+        let directives: Vec<_> = schema["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|directive| directive["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            directives,
+            ["tag", "include", "skip", "deprecated", "specifiedBy"]
+        );
+        assert_eq!(
+            schema["directives"][0]["locations"],
+            json!(["FIELD_DEFINITION"])
+        );
+        assert_eq!(
+            schema["directives"][3]["args"][0]["defaultValue"],
+            "\"No longer supported\""
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_order_of_the_selected_fields() {
+        let schema = Schema::new(&Parser::new(SCHEMA).parse().document());
+        let query = r#"{ __type(name: "Size") { name kind } __typename }"#;
+        let document = Parser::new(query).parse().document();
+        let response = super::execute(&schema, &document, None, &Map::new()).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"data":{"__type":{"name":"Size","kind":"ENUM"},"__typename":"Query"}}"#
+        );
+    }
+
+    #[test]
+    fn it_honors_include_deprecated() {
+        let query = r#"
+query($all: Boolean = false) {
+  size: __type(name: "Size") {
+    all: enumValues(includeDeprecated: true) { name }
+    current: enumValues(includeDeprecated: $all) { name }
+  }
+  query: __type(name: "Query") {
+    fields { name }
+  }
+  date: __type(name: "Date") { specifiedByURL }
+  missing: __type(name: "Missing") { name }
+}
+"#;
+        assert_eq!(
+            execute(query, json!({})).unwrap(),
+            json!({
+                "data": {
+                    "size": {
+                        "all": [{ "name": "SMALL" }, { "name": "LARGE" }],
+                        "current": [{ "name": "SMALL" }]
+                    },
+                    "query": { "fields": [{ "name": "id" }, { "name": "search" }] },
+                    "date": { "specifiedByURL": "https://example.com/date" },
+                    "missing": null
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_applies_fragments_and_directives() {
+        let query = r#"
+query($skip: Boolean!) {
+  __typename
+  __schema {
+    queryType { ...Named kind @skip(if: $skip) }
+    ... on __Schema { mutationType { name } }
+    ... @include(if: $skip) { directives { name } }
+  }
+}
+fragment Named on __Type { __typename name }
+"#;
+        assert_eq!(
+            execute(query, json!({ "skip": true })).unwrap(),
+            json!({
+                "data": {
+                    "__typename": "Query",
+                    "__schema": {
+                        "queryType": { "__typename": "__Type", "name": "Query" },
+                        "mutationType": null,
+                        "directives": [
+                            { "name": "tag" },
+                            { "name": "include" },
+                            { "name": "skip" },
+                            { "name": "deprecated" },
+                            { "name": "specifiedBy" }
+                        ]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_reports_queries_it_cannot_execute() {
+        assert_eq!(
+            execute("{ id __schema { types { name } } }", json!({})),
+            Err(vec![
+                "the field `Query.id` cannot be resolved by introspection".to_string()
+            ])
+        );
+        assert_eq!(
+            execute("mutation { __typename }", json!({})),
+            Err(vec!["only query operations can be introspected".to_string()])
+        );
+        assert_eq!(
+            execute("query A { __typename } query B { __typename }", json!({})),
+            Err(vec![
+                "the document contains several operations, an operation name is required"
+                    .to_string()
+            ])
+        );
+        assert_eq!(
+            execute(
+                "query($name: String!) { __type(name: $name) { name } }",
+                json!({})
+            ),
+            Err(vec![
+                "variable `$name` of required type `String!` was not provided".to_string()
+            ])
+        );
+    }
+}
@@ -0,0 +1,20 @@
+#![doc = include_str!("../README.md")]
+
+pub mod check;
+pub mod coercion;
+pub mod complexity;
+mod diagnostics;
+pub mod diff;
+pub mod execution;
+pub mod introspection;
+pub mod normalize;
+pub mod schema;
+pub mod validation;
+mod values;
+
+pub use crate::diagnostics::{Diagnostic, Label};
+pub use crate::schema::{
+    DirectiveDefinition, DirectiveLocation, Schema, SchemaBuilder, Type, TypeDefinition, TypeKind,
+};
+pub use crate::validation::validate;
+pub use crate::values::Value;
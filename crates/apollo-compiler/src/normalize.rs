@@ -0,0 +1,676 @@
+//! Normalization of operations, for query planners and caches that compare
+//! or split operations.
+//!
+//! A [`Normalizer`] selects an operation from a document and rewrites it so
+//! that equivalent operations look the same:
+//! - named fragments are inlined as inline fragments, and inline fragments
+//!   that always apply are replaced with their selections;
+//! - `@skip` and `@include` with a literal condition are evaluated, and
+//!   inline fragments left without selections are removed;
+//! - fields with the same response key, and inline fragments with the same
+//!   type condition, are merged;
+//! - optionally, variables are replaced with their values.
+//!
+//! The result is an `apollo_encoder::OperationDefinition`.
+use std::collections::HashMap;
+
+use apollo_encoder as encoder;
+use apollo_parser::ast;
+use indexmap::IndexMap;
+use serde_json::{Map, Value as Json};
+
+use crate::{
+    coercion, execution,
+    schema::{self, encode},
+    values, Diagnostic, Schema, Value,
+};
+
+/// Normalizes operations of documents validated against a schema.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{normalize::Normalizer, Schema};
+/// use apollo_parser::Parser;
+/// use indoc::indoc;
+///
+/// let schema = Parser::new("type Query { me: User } type User { id: ID name: String }")
+///     .parse()
+///     .document();
+/// let schema = Schema::new(&schema);
+/// let query = "
+/// query Me { me { id } ...Name me { id @include(if: true) } }
+/// fragment Name on Query { me { name @skip(if: false) } }
+/// ";
+/// let query = Parser::new(query).parse().document();
+///
+/// let operation = Normalizer::new(&schema).normalize(&query, Some("Me")).unwrap();
+/// assert_eq!(
+///     operation.to_string(),
+///     indoc! { r#"
+///         query Me {
+///           me {
+///             id
+///             name
+///           }
+///         }
+///     "# }
+/// );
+/// ```
+pub struct Normalizer<'a> {
+    schema: &'a Schema,
+    variables: Option<Map<String, Json>>,
+}
+
+impl<'a> Normalizer<'a> {
+    /// Create a new instance of `Normalizer`.
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            variables: None,
+        }
+    }
+
+    /// Replace the operation's variables with the values coerced from their
+    /// JSON `variables`, or their default values, and remove the variable
+    /// definitions. `@skip` and `@include` using variables are then
+    /// evaluated too.
+    pub fn substitute_variables(&mut self, variables: Map<String, Json>) {
+        self.variables = Some(variables);
+    }
+
+    /// Normalize the operation named `operation_name`, or the only operation
+    /// of `document`.
+    pub fn normalize(
+        &self,
+        document: &ast::Document,
+        operation_name: Option<&str>,
+    ) -> Result<encoder::OperationDefinition, Vec<Diagnostic>> {
+        let operation = execution::operation(document, operation_name)?;
+        let variables = match &self.variables {
+            Some(variables) => Some(coercion::coerce_variables(
+                self.schema,
+                &operation,
+                variables,
+            )?),
+            None => None,
+        };
+        let fragments = document
+            .definitions()
+            .filter_map(|def| match def {
+                ast::Definition::FragmentDefinition(fragment) => {
+                    let name = fragment.fragment_name()?.name()?.text().to_string();
+                    Some((name, fragment))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut normalization = Normalization {
+            schema: self.schema,
+            fragments,
+            variables,
+            spreads: Vec::new(),
+        };
+
+        let root_type = crate::validation::root_type(self.schema, &operation);
+        let mut selections = Vec::new();
+        if let Some(selection_set) = operation.selection_set() {
+            normalization.selection_set(&selection_set, root_type, &mut selections);
+        }
+        let selection_set = selection_set(non_empty(merge(selections)));
+        let operation_type = match operation.operation_type() {
+            Some(ty) if ty.mutation_token().is_some() => encoder::OperationType::Mutation,
+            Some(ty) if ty.subscription_token().is_some() => encoder::OperationType::Subscription,
+            _ => encoder::OperationType::Query,
+        };
+        let mut def = encoder::OperationDefinition::new(operation_type, selection_set);
+        def.name(operation.name().map(|name| name.text().to_string()));
+        if normalization.variables.is_none() {
+            for variable in operation
+                .variable_definitions()
+                .iter()
+                .flat_map(|defs| defs.variable_definitions())
+            {
+                let name = variable.variable().and_then(|variable| variable.name());
+                let ty = variable.ty().and_then(schema::ty);
+                let (name, ty) = match (name, ty) {
+                    (Some(name), Some(ty)) => (name.text().to_string(), ty),
+                    _ => continue,
+                };
+                let mut variable_def =
+                    encoder::VariableDefinition::new(name, encode::encoder_type(&ty));
+                let default_value = variable
+                    .default_value()
+                    .and_then(|default| default.value())
+                    .and_then(values::value);
+                variable_def.default_value(default_value.as_ref().map(encode::encoder_value));
+                for directive in normalization.directives(variable.directives()) {
+                    variable_def.directive(directive.encode());
+                }
+                def.variable_definition(variable_def);
+            }
+        }
+        for directive in normalization.directives(operation.directives()) {
+            def.directive(directive.encode());
+        }
+        Ok(def)
+    }
+}
+
+struct Normalization<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<String, ast::FragmentDefinition>,
+    /// The coerced values of variables, when they are substituted.
+    variables: Option<IndexMap<String, Value>>,
+    /// The fragments being inlined, to stop at cycles.
+    spreads: Vec<String>,
+}
+
+impl<'a> Normalization<'a> {
+    /// Normalize the selections of `selection_set`, selected on `parent`,
+    /// into `selections`.
+    fn selection_set(
+        &mut self,
+        selection_set: &ast::SelectionSet,
+        parent: Option<&str>,
+        selections: &mut Vec<Selection>,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let directives = match self.included(field.directives()) {
+                        Some(directives) => directives,
+                        None => continue,
+                    };
+                    let name = match field.name() {
+                        Some(name) => name.text().to_string(),
+                        None => continue,
+                    };
+                    let ty = parent
+                        .and_then(|parent| self.schema.field_definition(parent, &name))
+                        .map(|definition| definition.ty().name().to_string());
+                    let mut children = Vec::new();
+                    if let Some(selection_set) = field.selection_set() {
+                        self.selection_set(&selection_set, ty.as_deref(), &mut children);
+                    }
+                    selections.push(Selection::Field(Field {
+                        alias: field
+                            .alias()
+                            .and_then(|alias| alias.name())
+                            .map(|name| name.text().to_string()),
+                        name,
+                        arguments: self.arguments(field.arguments()),
+                        directives,
+                        selections: field.selection_set().map(|_| children),
+                    }));
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    let directives = match self.included(spread.directives()) {
+                        Some(directives) => directives,
+                        None => continue,
+                    };
+                    let name = match spread.fragment_name().and_then(|n| n.name()) {
+                        Some(name) => name.text().to_string(),
+                        None => continue,
+                    };
+                    let fragment = match self.fragments.get(&name) {
+                        Some(fragment) => fragment.clone(),
+                        None => continue,
+                    };
+                    if self.spreads.contains(&name) {
+                        continue;
+                    }
+                    self.spreads.push(name);
+                    self.inline_fragment(
+                        type_condition(fragment.type_condition()),
+                        directives,
+                        fragment.selection_set(),
+                        parent,
+                        selections,
+                    );
+                    self.spreads.pop();
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    let directives = match self.included(inline.directives()) {
+                        Some(directives) => directives,
+                        None => continue,
+                    };
+                    self.inline_fragment(
+                        type_condition(inline.type_condition()),
+                        directives,
+                        inline.selection_set(),
+                        parent,
+                        selections,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Normalize an inline fragment, or a fragment spread, into
+    /// `selections`. The fragment is replaced with its selections when it
+    /// always applies.
+    fn inline_fragment(
+        &mut self,
+        type_condition: Option<String>,
+        directives: Vec<Directive>,
+        selection_set: Option<ast::SelectionSet>,
+        parent: Option<&str>,
+        selections: &mut Vec<Selection>,
+    ) {
+        let selection_set = match selection_set {
+            Some(selection_set) => selection_set,
+            None => return,
+        };
+        let always_applies = directives.is_empty()
+            && type_condition
+                .as_deref()
+                .map_or(true, |condition| Some(condition) == parent);
+        if always_applies {
+            self.selection_set(&selection_set, parent, selections);
+            return;
+        }
+        let mut children = Vec::new();
+        let fragment_type = type_condition.as_deref().or(parent);
+        self.selection_set(&selection_set, fragment_type, &mut children);
+        selections.push(Selection::InlineFragment(InlineFragment {
+            type_condition,
+            directives,
+            selections: children,
+        }));
+    }
+
+    /// Evaluate `@skip` and `@include`, returning `None` if the selection is
+    /// excluded, or its remaining directives.
+    fn included(&self, directives: Option<ast::Directives>) -> Option<Vec<Directive>> {
+        let mut remaining = Vec::new();
+        for directive in self.directives(directives) {
+            // `@skip` excludes the selection when `if` is true, `@include`
+            // when it is false.
+            let excluded_if = match directive.name.as_str() {
+                "skip" => true,
+                "include" => false,
+                _ => {
+                    remaining.push(directive);
+                    continue;
+                }
+            };
+            match directive.arguments.get("if") {
+                Some(Value::Boolean(condition)) if *condition == excluded_if => return None,
+                Some(Value::Boolean(_)) => {}
+                _ => remaining.push(directive),
+            }
+        }
+        Some(remaining)
+    }
+
+    fn directives(&self, directives: Option<ast::Directives>) -> Vec<Directive> {
+        directives
+            .iter()
+            .flat_map(|directives| directives.directives())
+            .filter_map(|directive| {
+                Some(Directive {
+                    name: directive.name()?.text().to_string(),
+                    arguments: self.arguments(directive.arguments()),
+                })
+            })
+            .collect()
+    }
+
+    fn arguments(&self, arguments: Option<ast::Arguments>) -> IndexMap<String, Value> {
+        arguments
+            .iter()
+            .flat_map(|arguments| arguments.arguments())
+            .filter_map(|argument| {
+                let name = argument.name()?.text().to_string();
+                let value = values::value(argument.value()?)?;
+                // An argument whose variable is not provided is not provided
+                // either.
+                Some((name, self.substitute(value)?))
+            })
+            .collect()
+    }
+
+    /// Replace the variables in `value` with their values, if variables are
+    /// substituted. Returns `None` for a variable that is not provided.
+    fn substitute(&self, value: Value) -> Option<Value> {
+        let variables = match &self.variables {
+            Some(variables) => variables,
+            None => return Some(value),
+        };
+        let value = match value {
+            Value::Variable(name) => variables.get(&name)?.clone(),
+            Value::List(values) => Value::List(
+                values
+                    .into_iter()
+                    .map(|value| self.substitute(value).unwrap_or(Value::Null))
+                    .collect(),
+            ),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name, self.substitute(value)?)))
+                    .collect(),
+            ),
+            value => value,
+        };
+        Some(value)
+    }
+}
+
+enum Selection {
+    Field(Field),
+    InlineFragment(InlineFragment),
+}
+
+struct Field {
+    alias: Option<String>,
+    name: String,
+    arguments: IndexMap<String, Value>,
+    directives: Vec<Directive>,
+    /// `None` for leaf fields.
+    selections: Option<Vec<Selection>>,
+}
+
+impl Field {
+    fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+struct InlineFragment {
+    type_condition: Option<String>,
+    directives: Vec<Directive>,
+    selections: Vec<Selection>,
+}
+
+#[derive(PartialEq)]
+struct Directive {
+    name: String,
+    arguments: IndexMap<String, Value>,
+}
+
+impl Directive {
+    fn encode(&self) -> encoder::Directive {
+        let mut directive = encoder::Directive::new(self.name.clone());
+        for (name, value) in &self.arguments {
+            directive.arg(encoder::Argument::new(
+                name.clone(),
+                encode::encoder_value(value),
+            ));
+        }
+        directive
+    }
+}
+
+/// Merge the fields with the same response key and directives, and the
+/// inline fragments with the same type condition and directives, keeping the
+/// position of the first one.
+fn merge(selections: Vec<Selection>) -> Vec<Selection> {
+    let mut merged: Vec<Selection> = Vec::new();
+    for selection in selections {
+        let position = merged
+            .iter()
+            .position(|existing| is_mergeable(existing, &selection));
+        match (position.map(|i| &mut merged[i]), selection) {
+            (Some(Selection::Field(existing)), Selection::Field(field)) => {
+                if let (Some(existing), Some(selections)) =
+                    (&mut existing.selections, field.selections)
+                {
+                    existing.extend(selections);
+                }
+            }
+            (Some(Selection::InlineFragment(existing)), Selection::InlineFragment(inline)) => {
+                existing.selections.extend(inline.selections);
+            }
+            (_, selection) => merged.push(selection),
+        }
+    }
+    for selection in &mut merged {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(selections) = field.selections.take() {
+                    field.selections = Some(non_empty(merge(selections)));
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                inline.selections = merge(std::mem::take(&mut inline.selections));
+            }
+        }
+    }
+    merged.retain(|selection| match selection {
+        Selection::Field(_) => true,
+        Selection::InlineFragment(inline) => !inline.selections.is_empty(),
+    });
+    merged
+}
+
+/// Selection sets cannot be empty: when all the selections of a field or an
+/// operation are excluded, `__typename` is selected instead.
+fn non_empty(mut selections: Vec<Selection>) -> Vec<Selection> {
+    if selections.is_empty() {
+        selections.push(Selection::Field(Field {
+            alias: None,
+            name: "__typename".to_string(),
+            arguments: IndexMap::new(),
+            directives: Vec::new(),
+            selections: None,
+        }));
+    }
+    selections
+}
+
+fn is_mergeable(existing: &Selection, selection: &Selection) -> bool {
+    match (existing, selection) {
+        (Selection::Field(existing), Selection::Field(field)) => {
+            existing.response_key() == field.response_key()
+                && existing.directives == field.directives
+        }
+        (Selection::InlineFragment(existing), Selection::InlineFragment(inline)) => {
+            existing.type_condition == inline.type_condition
+                && existing.directives == inline.directives
+        }
+        _ => false,
+    }
+}
+
+fn selection_set(selections: Vec<Selection>) -> encoder::SelectionSet {
+    let mut encoded = encoder::SelectionSet::new();
+    for selection in selections {
+        encoded.selection(match selection {
+            Selection::Field(field) => {
+                let mut def = encoder::Field::new(field.name);
+                def.alias(field.alias);
+                for (name, value) in &field.arguments {
+                    def.argument(encoder::Argument::new(
+                        name.clone(),
+                        encode::encoder_value(value),
+                    ));
+                }
+                for directive in &field.directives {
+                    def.directive(directive.encode());
+                }
+                def.selection_set(field.selections.map(selection_set));
+                encoder::Selection::Field(def)
+            }
+            Selection::InlineFragment(inline) => {
+                let mut def = encoder::InlineFragment::new(selection_set(inline.selections));
+                def.type_condition(inline.type_condition.map(encoder::TypeCondition::new));
+                for directive in &inline.directives {
+                    def.directive(directive.encode());
+                }
+                encoder::Selection::InlineFragment(def)
+            }
+        });
+    }
+    encoded
+}
+
+fn type_condition(type_condition: Option<ast::TypeCondition>) -> Option<String> {
+    Some(type_condition?.named_type()?.name()?.text().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+directive @tag(name: String) on FIELD | INLINE_FRAGMENT | QUERY
+type Query {
+  node(id: ID!): Node
+  search(term: String, size: Size = SMALL, first: Int): [Result]
+}
+interface Node { id: ID! }
+type User implements Node { id: ID! name: String friends: [User] }
+type Post implements Node { id: ID! title: String }
+union Result = User | Post
+enum Size { SMALL LARGE }
+"#;
+
+    fn normalize(query: &str, variables: Option<Json>) -> String {
+        let schema = Parser::new(SCHEMA).parse().document();
+        let schema = Schema::new(&schema);
+        let query = Parser::new(query).parse().document();
+        let mut normalizer = Normalizer::new(&schema);
+        if let Some(Json::Object(variables)) = variables {
+            normalizer.substitute_variables(variables);
+        }
+        normalizer.normalize(&query, None).unwrap().to_string()
+    }
+
+    #[test]
+    fn it_inlines_fragments_and_merges_fields() {
+        let query = r#"
+query Search($term: String) @tag {
+  search(term: $term) {
+    ...UserFields
+    ... on User { friends { id } }
+    ... on Post { title }
+    ... on Post { id @skip(if: true) }
+  }
+  node(id: "1") { ... on Node { id } id }
+}
+fragment UserFields on User { name friends { name } }
+"#;
+        assert_eq!(
+            normalize(query, None),
+            indoc! { r#"
+                query Search($term: String) @tag {
+                  search(term: $term) {
+                    ... on User {
+                      name
+                      friends {
+                        name
+                        id
+                      }
+                    }
+                    ... on Post {
+                      title
+                    }
+                  }
+                  node(id: "1") {
+                    id
+                  }
+                }
+            "# }
+        );
+    }
+
+    #[test]
+    fn it_keeps_conditional_selections() {
+        let query = r#"
+query($skip: Boolean!) {
+  a: node(id: "1") { id @skip(if: $skip) id }
+  a: node(id: "1") @tag(name: "x") { id }
+  node(id: "2") { ... @include(if: false) { id } ... @tag { id } }
+}
+"#;
+        assert_eq!(
+            normalize(query, None),
+            indoc! { r#"
+                query($skip: Boolean!) {
+                  a: node(id: "1") {
+                    id @skip(if: $skip)
+                    id
+                  }
+                  a: node(id: "1") @tag(name: "x") {
+                    id
+                  }
+                  node(id: "2") {
+                    ... @tag {
+                      id
+                    }
+                  }
+                }
+            "# }
+        );
+    }
+
+    #[test]
+    fn it_removes_selection_sets_left_empty() {
+        let query = r#"
+{
+  a: node(id: "1") { id @skip(if: true) }
+  b: node(id: "2") { ... on Post { id @skip(if: true) } ... on User { ... @include(if: false) { id } } }
+  c: node(id: "3") { ... on Post { title @include(if: false) } id }
+}
+"#;
+        let normalized = normalize(query, None);
+        assert_eq!(
+            normalized,
+            indoc! { r#"
+                query {
+                  a: node(id: "1") {
+                    __typename
+                  }
+                  b: node(id: "2") {
+                    __typename
+                  }
+                  c: node(id: "3") {
+                    id
+                  }
+                }
+            "# }
+        );
+        assert_eq!(Parser::new(&normalized).parse().errors().len(), 0);
+
+        assert_eq!(
+            normalize("{ node(id: \"1\") @skip(if: true) { id } }", None),
+            indoc! { r#"
+                query {
+                  __typename
+                }
+            "# }
+        );
+    }
+
+    #[test]
+    fn it_substitutes_variables() {
+        let query = r#"
+query($term: String, $size: Size, $first: Int = 10, $skip: Boolean!, $missing: Int) {
+  search(term: $term, size: $size, first: $first) { ... on User { id @skip(if: $skip) name } }
+  other: search(first: $missing) { __typename }
+}
+"#;
+        let variables = json!({ "term": "a\"b", "size": "LARGE", "skip": true });
+        assert_eq!(
+            normalize(query, Some(variables)),
+            indoc! { r#"
+                query {
+                  search(term: """a"b""", size: LARGE, first: 10) {
+                    ... on User {
+                      name
+                    }
+                  }
+                  other: search {
+                    __typename
+                  }
+                }
+            "# }
+        );
+    }
+}
@@ -0,0 +1,973 @@
+use std::{collections::HashMap, ops::Range};
+
+use apollo_parser::{
+    ast::{self, AstNode},
+    Parser,
+};
+
+use crate::{
+    diagnostics::{self, Diagnostic},
+    schema::{name, ty, DEFAULT_DEPRECATION_REASON, PRELUDE},
+    values, DirectiveDefinition, DirectiveLocation, Schema, TypeDefinition, TypeKind, Value,
+};
+
+use super::definitions::*;
+
+/// Build a [`Schema`] from the type system definitions of one or more
+/// documents.
+///
+/// Type definitions can be spread across documents, and type extensions
+/// (`extend type`, `extend schema`, ...) are merged into the definition they
+/// extend, wherever it is defined. Diagnostics found while merging label the
+/// document they point into with [`Label::document`], which is the index of
+/// the document in the order documents were added.
+///
+/// [`Label::document`]: crate::Label::document
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::SchemaBuilder;
+/// use apollo_parser::Parser;
+///
+/// let base = Parser::new("type Query { me: User } type User { id: ID! }")
+///     .parse()
+///     .document();
+/// let extension = Parser::new("extend type User { name: String }")
+///     .parse()
+///     .document();
+///
+/// let mut builder = SchemaBuilder::new();
+/// builder.document(&base);
+/// builder.document(&extension);
+/// let schema = builder.build();
+///
+/// assert_eq!(schema.errors().len(), 0);
+/// let user = schema.type_definition("User").unwrap();
+/// assert_eq!(user.fields().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBuilder {
+    documents: Vec<ast::Document>,
+}
+
+impl SchemaBuilder {
+    /// Create a new instance of `SchemaBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a document's type system definitions and extensions to the
+    /// schema. Executable definitions are ignored.
+    pub fn document(&mut self, document: &ast::Document) {
+        self.documents.push(document.clone());
+    }
+
+    /// Build the schema.
+    ///
+    /// The definitions of the [`PRELUDE`] are added before the documents'.
+    /// Definitions are added first, then extensions are merged into them,
+    /// so an extension may come before the definition it extends.
+    ///
+    /// [`PRELUDE`]: crate::schema::PRELUDE
+    pub fn build(self) -> Schema {
+        let mut build = Build::default();
+        build.prelude();
+
+        for (document, ast) in self.documents.iter().enumerate() {
+            for definition in ast.definitions() {
+                build.definition(document, definition);
+            }
+        }
+
+        for (document, ast) in self.documents.iter().enumerate() {
+            for definition in ast.definitions() {
+                build.extension(document, definition);
+            }
+        }
+
+        // Without a schema definition, root operation types that are not set
+        // by a schema extension are found by their conventional names.
+        if !build.has_schema_definition {
+            let Schema {
+                types,
+                query_type,
+                mutation_type,
+                subscription_type,
+                ..
+            } = &mut build.schema;
+            let by_name = |name: &str| {
+                matches!(types.get(name), Some(TypeDefinition::Object(_))).then(|| name.to_string())
+            };
+            for (root, name) in [
+                (query_type, "Query"),
+                (mutation_type, "Mutation"),
+                (subscription_type, "Subscription"),
+            ] {
+                if root.is_none() {
+                    *root = by_name(name);
+                }
+            }
+        }
+
+        build.schema
+    }
+}
+
+/// The meta-fields, written as the fields of a type so they can be parsed.
+/// `__typename` is available on every composite type, `__schema` and `__type`
+/// on the query root operation type.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Type-Name-Introspection
+const META_FIELDS: &str = "
+type __MetaFields {
+  __typename: String!
+  __schema: __Schema!
+  __type(name: String!): __Type
+}
+";
+
+/// The state of a schema being built: the schema itself, and which document
+/// each definition came from, so diagnostics can point at the right one.
+#[derive(Default)]
+struct Build {
+    schema: Schema,
+    has_schema_definition: bool,
+    type_documents: HashMap<String, usize>,
+    member_documents: HashMap<(String, String), usize>,
+    directive_documents: HashMap<String, usize>,
+    /// Where each root operation type was set, for duplicates.
+    root_documents: HashMap<&'static str, (usize, Range<usize>)>,
+}
+
+/// The members a type definition or a type extension adds to a type.
+#[derive(Default)]
+struct Members {
+    directives: Vec<Directive>,
+    interfaces: Vec<(String, Range<usize>)>,
+    fields: Vec<FieldDefinition>,
+    union_members: Vec<(String, Range<usize>)>,
+    enum_values: Vec<EnumValueDefinition>,
+    input_fields: Vec<InputValueDefinition>,
+}
+
+impl Build {
+    /// Add the built-in types and directives, and the meta-fields.
+    fn prelude(&mut self) {
+        let prelude = Parser::new(PRELUDE).parse().document();
+        for definition in prelude.definitions() {
+            match definition {
+                ast::Definition::DirectiveDefinition(def) => {
+                    if let Some(mut directive) = directive_definition(def) {
+                        directive.built_in = true;
+                        self.add_directive(0, directive);
+                    }
+                }
+                definition => {
+                    if let Some((mut ty, members)) = type_definition(definition) {
+                        match &mut ty {
+                            TypeDefinition::Scalar(ty) => ty.built_in = true,
+                            TypeDefinition::Object(ty) => ty.built_in = true,
+                            TypeDefinition::Interface(ty) => ty.built_in = true,
+                            TypeDefinition::Union(ty) => ty.built_in = true,
+                            TypeDefinition::Enum(ty) => ty.built_in = true,
+                            TypeDefinition::InputObject(ty) => ty.built_in = true,
+                        }
+                        self.add_type(0, ty, members);
+                    }
+                }
+            }
+        }
+
+        let meta_fields = Parser::new(META_FIELDS).parse().document();
+        for definition in meta_fields.definitions() {
+            if let ast::Definition::ObjectTypeDefinition(def) = definition {
+                self.schema.meta_fields = fields(def.fields_definition());
+            }
+        }
+    }
+
+    fn definition(&mut self, document: usize, definition: ast::Definition) {
+        match definition {
+            ast::Definition::SchemaDefinition(def) => {
+                if self.has_schema_definition {
+                    self.schema.errors.push(
+                        Diagnostic::new("a schema can only be defined once").label_in(
+                            document,
+                            diagnostics::range(&def),
+                            "schema redefined here",
+                        ),
+                    );
+                    return;
+                }
+                self.has_schema_definition = true;
+                self.schema.description = values::description(def.description());
+                self.schema
+                    .schema_directives
+                    .extend(directives(def.directives()));
+                for root in def.root_operation_type_definitions() {
+                    self.root_operation(document, root);
+                }
+            }
+            ast::Definition::DirectiveDefinition(def) => {
+                if let Some(directive) = directive_definition(def) {
+                    self.add_directive(document, directive);
+                }
+            }
+            definition => {
+                if let Some((ty, members)) = type_definition(definition) {
+                    self.add_type(document, ty, members);
+                }
+            }
+        }
+    }
+
+    fn extension(&mut self, document: usize, definition: ast::Definition) {
+        if let ast::Definition::SchemaExtension(def) = &definition {
+            self.schema
+                .schema_directives
+                .extend(directives(def.directives()));
+            for root in def.root_operation_type_definitions() {
+                self.root_operation(document, root);
+            }
+            return;
+        }
+
+        let (kind, name, range, members) = match type_extension(definition) {
+            Some(extension) => extension,
+            None => return,
+        };
+
+        match self.schema.types.get(&name) {
+            None => {
+                self.schema.errors.push(
+                    Diagnostic::new(format!("cannot extend undefined type `{}`", name))
+                        .label_in(document, range, "extension here")
+                        .help(format!("add a definition for `{}`", name)),
+                );
+            }
+            Some(ty) if ty.is_built_in() => {
+                self.schema.errors.push(
+                    Diagnostic::new(format!("cannot extend the built-in type `{}`", name))
+                        .label_in(document, range, "extension here"),
+                );
+            }
+            Some(ty) if ty.kind() != kind => {
+                let original = self.type_documents.get(&name).copied().unwrap_or_default();
+                self.schema.errors.push(
+                    Diagnostic::new(format!(
+                        "cannot extend the {} type `{}` with `extend {}`",
+                        ty.kind(),
+                        name,
+                        keyword(kind)
+                    ))
+                    .label_in(document, range, "extension here")
+                    .label_in(
+                        original,
+                        ty.range(),
+                        format!("`{}` is defined as {} here", name, article(ty.kind())),
+                    ),
+                );
+            }
+            Some(_) => self.merge(document, &name, members),
+        }
+    }
+
+    fn add_type(&mut self, document: usize, ty: TypeDefinition, members: Members) {
+        let name = ty.name().to_string();
+        if let Some(original) = self.schema.types.get(&name) {
+            if original.is_built_in() {
+                self.schema.errors.push(
+                    Diagnostic::new(format!("the built-in type `{}` cannot be redefined", name))
+                        .label_in(document, ty.range(), "redefined here")
+                        .help(format!(
+                            "remove this definition, every schema includes `{}`",
+                            name
+                        )),
+                );
+                return;
+            }
+            let original_document = self.type_documents.get(&name).copied().unwrap_or_default();
+            self.schema.errors.push(
+                Diagnostic::new(format!("the type `{}` is defined multiple times", name))
+                    .label_in(
+                        original_document,
+                        original.range(),
+                        "previous definition here",
+                    )
+                    .label_in(document, ty.range(), "redefined here"),
+            );
+            return;
+        }
+        self.schema.types.insert(name.clone(), ty);
+        self.type_documents.insert(name.clone(), document);
+        self.merge(document, &name, members);
+    }
+
+    fn add_directive(&mut self, document: usize, directive: DirectiveDefinition) {
+        if let Some(original) = self.schema.directives.get(&directive.name) {
+            if original.is_built_in() {
+                self.schema.errors.push(
+                    Diagnostic::new(format!(
+                        "the built-in directive `@{}` cannot be redefined",
+                        directive.name
+                    ))
+                    .label_in(document, directive.range(), "redefined here")
+                    .help(format!(
+                        "remove this definition, every schema includes `@{}`",
+                        directive.name
+                    )),
+                );
+                return;
+            }
+            let original_document = self
+                .directive_documents
+                .get(&directive.name)
+                .copied()
+                .unwrap_or_default();
+            self.schema.errors.push(
+                Diagnostic::new(format!(
+                    "the directive `@{}` is defined multiple times",
+                    directive.name
+                ))
+                .label_in(
+                    original_document,
+                    original.range(),
+                    "previous definition here",
+                )
+                .label_in(document, directive.range(), "redefined here"),
+            );
+            return;
+        }
+        self.directive_documents
+            .insert(directive.name.clone(), document);
+        self.schema
+            .directives
+            .insert(directive.name.clone(), directive);
+    }
+
+    fn root_operation(&mut self, document: usize, root: ast::RootOperationTypeDefinition) {
+        let ty = match root.named_type().and_then(|ty| name(ty.name())) {
+            Some(ty) => ty,
+            None => return,
+        };
+        let (operation, slot) = match root.operation_type() {
+            Some(op) if op.query_token().is_some() => ("query", &mut self.schema.query_type),
+            Some(op) if op.mutation_token().is_some() => {
+                ("mutation", &mut self.schema.mutation_type)
+            }
+            Some(op) if op.subscription_token().is_some() => {
+                ("subscription", &mut self.schema.subscription_type)
+            }
+            _ => return,
+        };
+        let range = diagnostics::range(&root);
+
+        if slot.is_some() {
+            let mut diagnostic = Diagnostic::new(format!(
+                "the `{}` root operation type is defined multiple times",
+                operation
+            ));
+            if let Some((original_document, original)) = self.root_documents.get(operation) {
+                diagnostic = diagnostic.label_in(
+                    *original_document,
+                    original.clone(),
+                    "previously defined here",
+                );
+            }
+            self.schema
+                .errors
+                .push(diagnostic.label_in(document, range, "redefined here"));
+            return;
+        }
+        *slot = Some(ty);
+        self.root_documents.insert(operation, (document, range));
+    }
+
+    /// Add the members of a definition or an extension to the type `name`.
+    fn merge(&mut self, document: usize, name: &str, members: Members) {
+        let Build {
+            schema,
+            member_documents,
+            ..
+        } = self;
+        let ty = match schema.types.get_mut(name) {
+            Some(ty) => ty,
+            None => return,
+        };
+        let mut merge = Merge {
+            document,
+            type_name: name,
+            member_documents,
+            errors: &mut schema.errors,
+        };
+
+        match ty {
+            TypeDefinition::Scalar(scalar) => scalar.directives.extend(members.directives),
+            TypeDefinition::Object(ObjectType {
+                implements_interfaces,
+                fields,
+                directives,
+                ..
+            })
+            | TypeDefinition::Interface(InterfaceType {
+                implements_interfaces,
+                fields,
+                directives,
+                ..
+            }) => {
+                directives.extend(members.directives);
+                merge.names(implements_interfaces, members.interfaces, |ty, name| {
+                    format!("the type `{}` already implements `{}`", ty, name)
+                });
+                merge.members(fields, members.fields, "field");
+            }
+            TypeDefinition::Union(union) => {
+                union.directives.extend(members.directives);
+                merge.names(&mut union.members, members.union_members, |ty, name| {
+                    format!("the union `{}` already includes `{}`", ty, name)
+                });
+            }
+            TypeDefinition::Enum(enum_ty) => {
+                enum_ty.directives.extend(members.directives);
+                merge.members(&mut enum_ty.values, members.enum_values, "enum value");
+            }
+            TypeDefinition::InputObject(input) => {
+                input.directives.extend(members.directives);
+                merge.members(&mut input.fields, members.input_fields, "field");
+            }
+        }
+    }
+}
+
+/// Adds members to one type, reporting duplicates.
+struct Merge<'a> {
+    document: usize,
+    type_name: &'a str,
+    member_documents: &'a mut HashMap<(String, String), usize>,
+    errors: &'a mut Vec<Diagnostic>,
+}
+
+impl Merge<'_> {
+    fn members<T: Member>(&mut self, existing: &mut Vec<T>, added: Vec<T>, what: &str) {
+        for member in added {
+            let key = (self.type_name.to_string(), member.name().to_string());
+            match existing.iter().find(|m| m.name() == member.name()) {
+                Some(original) => {
+                    let original_document =
+                        self.member_documents.get(&key).copied().unwrap_or_default();
+                    self.errors.push(
+                        Diagnostic::new(format!(
+                            "the {} `{}.{}` is defined multiple times",
+                            what,
+                            self.type_name,
+                            member.name()
+                        ))
+                        .label_in(
+                            original_document,
+                            original.range(),
+                            "previous definition here",
+                        )
+                        .label_in(
+                            self.document,
+                            member.range(),
+                            "redefined here",
+                        ),
+                    );
+                }
+                None => {
+                    self.member_documents.insert(key, self.document);
+                    existing.push(member);
+                }
+            }
+        }
+    }
+
+    fn names(
+        &mut self,
+        existing: &mut Vec<String>,
+        added: Vec<(String, Range<usize>)>,
+        message: impl Fn(&str, &str) -> String,
+    ) {
+        for (name, range) in added {
+            if existing.contains(&name) {
+                self.errors
+                    .push(Diagnostic::new(message(self.type_name, &name)).label_in(
+                        self.document,
+                        range,
+                        "added again here",
+                    ));
+            } else {
+                existing.push(name);
+            }
+        }
+    }
+}
+
+/// A named member of a type, with a range.
+trait Member {
+    fn name(&self) -> &str;
+    fn range(&self) -> Range<usize>;
+}
+
+impl Member for FieldDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Member for InputValueDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Member for EnumValueDefinition {
+    fn name(&self) -> &str {
+        &self.value
+    }
+    fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+fn keyword(kind: TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object => "type",
+        TypeKind::Interface => "interface",
+        TypeKind::Union => "union",
+        TypeKind::Enum => "enum",
+        TypeKind::InputObject => "input",
+    }
+}
+
+fn article(kind: TypeKind) -> String {
+    match kind {
+        TypeKind::Object | TypeKind::Interface | TypeKind::Enum | TypeKind::InputObject => {
+            format!("an {}", kind)
+        }
+        TypeKind::Scalar | TypeKind::Union => format!("a {}", kind),
+    }
+}
+
+/// Lower a type definition into a type without members, and the members it
+/// defines.
+fn type_definition(definition: ast::Definition) -> Option<(TypeDefinition, Members)> {
+    let def = match definition {
+        ast::Definition::ScalarTypeDefinition(def) => (
+            TypeDefinition::Scalar(ScalarType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                specified_by_url: find_directive(def.directives(), "specifiedBy")
+                    .and_then(|directive| string_argument(&directive, "url")),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members::default(),
+        ),
+        ast::Definition::ObjectTypeDefinition(def) => (
+            TypeDefinition::Object(ObjectType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                implements_interfaces: Vec::new(),
+                fields: Vec::new(),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members {
+                interfaces: interfaces(def.implements_interfaces()),
+                fields: fields(def.fields_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::InterfaceTypeDefinition(def) => (
+            TypeDefinition::Interface(InterfaceType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                implements_interfaces: Vec::new(),
+                fields: Vec::new(),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members {
+                interfaces: interfaces(def.implements_interfaces()),
+                fields: fields(def.fields_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::UnionTypeDefinition(def) => (
+            TypeDefinition::Union(UnionType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                members: Vec::new(),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members {
+                union_members: union_members(def.union_member_types()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::EnumTypeDefinition(def) => (
+            TypeDefinition::Enum(EnumType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                values: Vec::new(),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members {
+                enum_values: enum_values(def.enum_values_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::InputObjectTypeDefinition(def) => (
+            TypeDefinition::InputObject(InputObjectType {
+                name: name(def.name())?,
+                description: values::description(def.description()),
+                fields: Vec::new(),
+                directives: directives(def.directives()),
+                built_in: false,
+                range: diagnostics::range(&def),
+            }),
+            Members {
+                input_fields: input_fields(def.input_fields_definition()),
+                ..Members::default()
+            },
+        ),
+        _ => return None,
+    };
+    Some(def)
+}
+
+/// Lower a type extension into the kind and name of the type it extends, its
+/// range and the members it adds.
+fn type_extension(
+    definition: ast::Definition,
+) -> Option<(TypeKind, String, Range<usize>, Members)> {
+    let range = diagnostics::syntax_range(definition.syntax());
+    let (kind, name, members) = match definition {
+        ast::Definition::ScalarTypeExtension(def) => (
+            TypeKind::Scalar,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::ObjectTypeExtension(def) => (
+            TypeKind::Object,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                interfaces: interfaces(def.implements_interfaces()),
+                fields: fields(def.fields_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::InterfaceTypeExtension(def) => (
+            TypeKind::Interface,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                interfaces: interfaces(def.implements_interfaces()),
+                fields: fields(def.fields_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::UnionTypeExtension(def) => (
+            TypeKind::Union,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                union_members: union_members(def.union_member_types()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::EnumTypeExtension(def) => (
+            TypeKind::Enum,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                enum_values: enum_values(def.enum_values_definition()),
+                ..Members::default()
+            },
+        ),
+        ast::Definition::InputObjectTypeExtension(def) => (
+            TypeKind::InputObject,
+            name(def.name())?,
+            Members {
+                directives: directives(def.directives()),
+                input_fields: input_fields(def.input_fields_definition()),
+                ..Members::default()
+            },
+        ),
+        _ => return None,
+    };
+    Some((kind, name, range, members))
+}
+
+fn named_types(nodes: impl Iterator<Item = ast::NamedType>) -> Vec<(String, Range<usize>)> {
+    nodes
+        .filter_map(|ty| Some((name(ty.name())?, diagnostics::range(&ty))))
+        .collect()
+}
+
+fn interfaces(node: Option<ast::ImplementsInterfaces>) -> Vec<(String, Range<usize>)> {
+    node.map(|i| named_types(i.named_types()))
+        .unwrap_or_default()
+}
+
+fn union_members(node: Option<ast::UnionMemberTypes>) -> Vec<(String, Range<usize>)> {
+    node.map(|members| named_types(members.named_types()))
+        .unwrap_or_default()
+}
+
+fn fields(node: Option<ast::FieldsDefinition>) -> Vec<FieldDefinition> {
+    node.iter()
+        .flat_map(|fields| fields.field_definitions())
+        .filter_map(|field| {
+            Some(FieldDefinition {
+                name: name(field.name())?,
+                description: values::description(field.description()),
+                arguments: input_values(
+                    field
+                        .arguments_definition()
+                        .into_iter()
+                        .flat_map(|args| args.input_value_definitions()),
+                ),
+                ty: ty(field.ty()?)?,
+                deprecation_reason: deprecation_reason(field.directives()),
+                directives: directives(field.directives()),
+                range: diagnostics::range(&field),
+            })
+        })
+        .collect()
+}
+
+fn input_fields(node: Option<ast::InputFieldsDefinition>) -> Vec<InputValueDefinition> {
+    input_values(
+        node.into_iter()
+            .flat_map(|fields| fields.input_value_definitions()),
+    )
+}
+
+fn input_values(
+    nodes: impl Iterator<Item = ast::InputValueDefinition>,
+) -> Vec<InputValueDefinition> {
+    nodes
+        .filter_map(|input| {
+            Some(InputValueDefinition {
+                name: name(input.name())?,
+                description: values::description(input.description()),
+                ty: ty(input.ty()?)?,
+                default_value: input
+                    .default_value()
+                    .and_then(|default| values::value(default.value()?)),
+                deprecation_reason: deprecation_reason(input.directives()),
+                directives: directives(input.directives()),
+                range: diagnostics::range(&input),
+            })
+        })
+        .collect()
+}
+
+fn enum_values(node: Option<ast::EnumValuesDefinition>) -> Vec<EnumValueDefinition> {
+    node.iter()
+        .flat_map(|values| values.enum_value_definitions())
+        .filter_map(|value| {
+            Some(EnumValueDefinition {
+                value: name(value.enum_value()?.name())?,
+                description: values::description(value.description()),
+                deprecation_reason: deprecation_reason(value.directives()),
+                directives: directives(value.directives()),
+                range: diagnostics::range(&value),
+            })
+        })
+        .collect()
+}
+
+/// Lower the directives applied to a definition. Arguments whose value
+/// cannot be lowered, e.g. a variable, are left out.
+fn directives(node: Option<ast::Directives>) -> Vec<Directive> {
+    node.iter()
+        .flat_map(|directives| directives.directives())
+        .filter_map(|directive| {
+            Some(Directive {
+                name: name(directive.name())?,
+                arguments: directive
+                    .arguments()
+                    .iter()
+                    .flat_map(|arguments| arguments.arguments())
+                    .filter_map(|argument| {
+                        Some((name(argument.name())?, values::value(argument.value()?)?))
+                    })
+                    .collect(),
+                range: diagnostics::range(&directive),
+            })
+        })
+        .collect()
+}
+
+/// Get the reason of a `@deprecated` directive among `directives`, or the
+/// default reason if it has no `reason` argument.
+fn deprecation_reason(directives: Option<ast::Directives>) -> Option<String> {
+    let directive = find_directive(directives, "deprecated")?;
+    Some(
+        string_argument(&directive, "reason")
+            .unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string()),
+    )
+}
+
+fn find_directive(directives: Option<ast::Directives>, name: &str) -> Option<ast::Directive> {
+    directives?
+        .directives()
+        .find(|directive| directive.name().map_or(false, |n| n.text() == name))
+}
+
+/// Get the value of a directive's string argument.
+fn string_argument(directive: &ast::Directive, name: &str) -> Option<String> {
+    let argument = directive
+        .arguments()?
+        .arguments()
+        .find(|argument| argument.name().map_or(false, |n| n.text() == name))?;
+    match values::value(argument.value()?)? {
+        Value::String(string) => Some(string),
+        _ => None,
+    }
+}
+
+fn directive_definition(def: ast::DirectiveDefinition) -> Option<DirectiveDefinition> {
+    Some(DirectiveDefinition {
+        name: name(def.name())?,
+        description: values::description(def.description()),
+        arguments: input_values(
+            def.arguments_definition()
+                .into_iter()
+                .flat_map(|args| args.input_value_definitions()),
+        ),
+        repeatable: def.repeatable_token().is_some(),
+        locations: def
+            .directive_locations()
+            .iter()
+            .flat_map(|locations| locations.directive_locations())
+            .filter_map(|location| {
+                let token = location.syntax().first_token()?;
+                DirectiveLocation::from_name(token.text())
+            })
+            .collect(),
+        built_in: false,
+        range: diagnostics::range(&def),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn build(inputs: &[&str]) -> Schema {
+        let mut builder = SchemaBuilder::new();
+        for input in inputs {
+            builder.document(&Parser::new(input).parse().document());
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn it_merges_extensions_across_documents() {
+        let schema = build(&[
+            "
+extend type Query { b: Int }
+extend schema { mutation: Mutation }
+extend enum Size { LARGE }
+",
+            "
+type Query implements Node { id: ID! a: Int }
+type Mutation { c: Int }
+interface Node { id: ID! }
+interface Named { name: String }
+extend type Query implements Named { name: String }
+union Search = Query
+extend union Search = Mutation
+enum Size { SMALL }
+input Filter { size: Size }
+extend input Filter { limit: Int }
+scalar Date
+extend scalar Date
+",
+        ]);
+        assert_eq!(schema.errors(), []);
+
+        let query = schema.type_definition("Query").unwrap();
+        let fields: Vec<_> = query.fields().iter().map(|f| f.name()).collect();
+        assert_eq!(fields, ["id", "a", "b", "name"]);
+        match query {
+            TypeDefinition::Object(object) => {
+                assert_eq!(object.implements_interfaces(), ["Node", "Named"])
+            }
+            _ => panic!("expected an object type"),
+        }
+        assert_eq!(schema.possible_types("Search"), ["Query", "Mutation"]);
+        assert_eq!(schema.query_type(), Some("Query"));
+        assert_eq!(schema.mutation_type(), Some("Mutation"));
+        match schema.type_definition("Size").unwrap() {
+            TypeDefinition::Enum(size) => assert_eq!(size.values().len(), 2),
+            _ => panic!("expected an enum type"),
+        }
+        match schema.type_definition("Filter").unwrap() {
+            TypeDefinition::InputObject(filter) => assert!(filter.field("limit").is_some()),
+            _ => panic!("expected an input object type"),
+        }
+    }
+
+    #[test]
+    fn it_reports_invalid_extensions() {
+        let base = "type Query { a: Int }\nenum Size { SMALL }\nschema { query: Query }";
+        let extensions = "
+extend type Missing { a: Int }
+extend union Query = Size
+extend type Query { a: String }
+extend enum Size { SMALL }
+extend schema { query: Query }
+";
+        let schema = build(&[base, extensions]);
+
+        let messages: Vec<_> = schema.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "cannot extend undefined type `Missing`",
+                "cannot extend the object type `Query` with `extend union`",
+                "the field `Query.a` is defined multiple times",
+                "the enum value `Size.SMALL` is defined multiple times",
+                "the `query` root operation type is defined multiple times",
+            ]
+        );
+
+        // Labels point into the document they were found in.
+        let duplicate = &schema.errors()[2];
+        let labels: Vec<_> = duplicate
+            .labels()
+            .iter()
+            .map(|label| {
+                let input = [base, extensions][label.document()];
+                &input[label.range()]
+            })
+            .collect();
+        assert_eq!(labels, ["a: Int", "a: String"]);
+    }
+}
@@ -0,0 +1,720 @@
+use std::{fmt, ops::Range};
+
+use crate::Value;
+
+/// A named type defined in a [`Schema`](crate::Schema).
+///
+/// See: https://spec.graphql.org/October2021/#sec-Types
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDefinition {
+    Scalar(ScalarType),
+    Object(ObjectType),
+    Interface(InterfaceType),
+    Union(UnionType),
+    Enum(EnumType),
+    InputObject(InputObjectType),
+}
+
+impl TypeDefinition {
+    /// Get the type's name.
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDefinition::Scalar(ty) => &ty.name,
+            TypeDefinition::Object(ty) => &ty.name,
+            TypeDefinition::Interface(ty) => &ty.name,
+            TypeDefinition::Union(ty) => &ty.name,
+            TypeDefinition::Enum(ty) => &ty.name,
+            TypeDefinition::InputObject(ty) => &ty.name,
+        }
+    }
+
+    /// Get the type's description, if any.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            TypeDefinition::Scalar(ty) => ty.description.as_deref(),
+            TypeDefinition::Object(ty) => ty.description.as_deref(),
+            TypeDefinition::Interface(ty) => ty.description.as_deref(),
+            TypeDefinition::Union(ty) => ty.description.as_deref(),
+            TypeDefinition::Enum(ty) => ty.description.as_deref(),
+            TypeDefinition::InputObject(ty) => ty.description.as_deref(),
+        }
+    }
+
+    /// Get the directives applied to the type's definition and extensions.
+    pub fn directives(&self) -> &[Directive] {
+        match self {
+            TypeDefinition::Scalar(ty) => &ty.directives,
+            TypeDefinition::Object(ty) => &ty.directives,
+            TypeDefinition::Interface(ty) => &ty.directives,
+            TypeDefinition::Union(ty) => &ty.directives,
+            TypeDefinition::Enum(ty) => &ty.directives,
+            TypeDefinition::InputObject(ty) => &ty.directives,
+        }
+    }
+
+    /// Get the byte range of the type's definition.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            TypeDefinition::Scalar(ty) => ty.range.clone(),
+            TypeDefinition::Object(ty) => ty.range.clone(),
+            TypeDefinition::Interface(ty) => ty.range.clone(),
+            TypeDefinition::Union(ty) => ty.range.clone(),
+            TypeDefinition::Enum(ty) => ty.range.clone(),
+            TypeDefinition::InputObject(ty) => ty.range.clone(),
+        }
+    }
+
+    /// Check whether this is one of the types built into every schema: the
+    /// built-in scalars and the introspection types.
+    pub fn is_built_in(&self) -> bool {
+        match self {
+            TypeDefinition::Scalar(ty) => ty.built_in,
+            TypeDefinition::Object(ty) => ty.built_in,
+            TypeDefinition::Interface(ty) => ty.built_in,
+            TypeDefinition::Union(ty) => ty.built_in,
+            TypeDefinition::Enum(ty) => ty.built_in,
+            TypeDefinition::InputObject(ty) => ty.built_in,
+        }
+    }
+
+    /// Get the type's kind.
+    pub fn kind(&self) -> TypeKind {
+        match self {
+            TypeDefinition::Scalar(_) => TypeKind::Scalar,
+            TypeDefinition::Object(_) => TypeKind::Object,
+            TypeDefinition::Interface(_) => TypeKind::Interface,
+            TypeDefinition::Union(_) => TypeKind::Union,
+            TypeDefinition::Enum(_) => TypeKind::Enum,
+            TypeDefinition::InputObject(_) => TypeKind::InputObject,
+        }
+    }
+
+    /// Get the fields of an object or an interface type. Other types have no
+    /// fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        match self {
+            TypeDefinition::Object(ty) => &ty.fields,
+            TypeDefinition::Interface(ty) => &ty.fields,
+            _ => &[],
+        }
+    }
+
+    /// Find a field of an object or an interface type by name.
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields().iter().find(|field| field.name == name)
+    }
+
+    /// Check whether this is an object, interface or union type: the types
+    /// that can be selected on.
+    pub fn is_composite(&self) -> bool {
+        self.kind().is_composite()
+    }
+
+    /// Check whether this is an interface or a union type.
+    pub fn is_abstract(&self) -> bool {
+        matches!(self.kind(), TypeKind::Interface | TypeKind::Union)
+    }
+
+    /// Check whether this type can be used as an input type.
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self.kind(),
+            TypeKind::Scalar | TypeKind::Enum | TypeKind::InputObject
+        )
+    }
+
+    /// Check whether this type can be used as an output type.
+    pub fn is_output(&self) -> bool {
+        !matches!(self.kind(), TypeKind::InputObject)
+    }
+}
+
+/// The kinds of named types, as listed in the `__TypeKind` introspection enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+impl TypeKind {
+    /// Check whether this kind can be selected on.
+    pub fn is_composite(self) -> bool {
+        matches!(
+            self,
+            TypeKind::Object | TypeKind::Interface | TypeKind::Union
+        )
+    }
+}
+
+impl fmt::Display for TypeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            TypeKind::Scalar => "scalar",
+            TypeKind::Object => "object",
+            TypeKind::Interface => "interface",
+            TypeKind::Union => "union",
+            TypeKind::Enum => "enum",
+            TypeKind::InputObject => "input object",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#ScalarTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) specified_by_url: Option<String>,
+    pub(crate) range: Range<usize>,
+}
+
+impl ScalarType {
+    /// Get the scalar's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the URL given by the scalar's `@specifiedBy` directive, if any.
+    pub fn specified_by_url(&self) -> Option<&str> {
+        self.specified_by_url.as_deref()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#ObjectTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) implements_interfaces: Vec<String>,
+    pub(crate) fields: Vec<FieldDefinition>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl ObjectType {
+    /// Get the object type's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the names of the interfaces this object type implements.
+    pub fn implements_interfaces(&self) -> &[String] {
+        self.implements_interfaces.as_ref()
+    }
+
+    /// Get the object type's fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        self.fields.as_ref()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#InterfaceTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) implements_interfaces: Vec<String>,
+    pub(crate) fields: Vec<FieldDefinition>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl InterfaceType {
+    /// Get the interface's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the names of the interfaces this interface implements.
+    pub fn implements_interfaces(&self) -> &[String] {
+        self.implements_interfaces.as_ref()
+    }
+
+    /// Get the interface's fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        self.fields.as_ref()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#UnionTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) members: Vec<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl UnionType {
+    /// Get the union's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the names of the union's member types.
+    pub fn members(&self) -> &[String] {
+        self.members.as_ref()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#EnumTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) values: Vec<EnumValueDefinition>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl EnumType {
+    /// Get the enum's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the enum's values.
+    pub fn values(&self) -> &[EnumValueDefinition] {
+        self.values.as_ref()
+    }
+
+    /// Find an enum value by name.
+    pub fn value(&self, name: &str) -> Option<&EnumValueDefinition> {
+        self.values.iter().find(|value| value.value == name)
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#EnumValueDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValueDefinition {
+    pub(crate) value: String,
+    pub(crate) description: Option<String>,
+    pub(crate) deprecation_reason: Option<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) range: Range<usize>,
+}
+
+impl EnumValueDefinition {
+    /// Get the enum value's name.
+    pub fn value(&self) -> &str {
+        self.value.as_ref()
+    }
+
+    /// Get the enum value's description, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Check whether the enum value is marked as `@deprecated`.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
+    /// Get the reason given by the `@deprecated` directive, if the enum value is
+    /// deprecated.
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_reason.as_deref()
+    }
+
+    /// Get the directives applied to the enum value, including `@deprecated`.
+    pub fn directives(&self) -> &[Directive] {
+        self.directives.as_ref()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#InputObjectTypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputObjectType {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: Vec<InputValueDefinition>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl InputObjectType {
+    /// Get the input object's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the input object's fields.
+    pub fn fields(&self) -> &[InputValueDefinition] {
+        self.fields.as_ref()
+    }
+
+    /// Find an input field by name.
+    pub fn field(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#FieldDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDefinition {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) arguments: Vec<InputValueDefinition>,
+    pub(crate) ty: Type,
+    pub(crate) deprecation_reason: Option<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) range: Range<usize>,
+}
+
+impl FieldDefinition {
+    /// Get the field's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the field's description, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Check whether the field is marked as `@deprecated`.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
+    /// Get the reason given by the `@deprecated` directive, if the field is
+    /// deprecated.
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_reason.as_deref()
+    }
+
+    /// Get the directives applied to the field, including `@deprecated`.
+    pub fn directives(&self) -> &[Directive] {
+        self.directives.as_ref()
+    }
+
+    /// Get the field's arguments.
+    pub fn arguments(&self) -> &[InputValueDefinition] {
+        self.arguments.as_ref()
+    }
+
+    /// Find an argument by name.
+    pub fn argument(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+
+    /// Get the field's type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Get the byte range of the field's definition.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// An argument or an input object field definition.
+///
+/// See: https://spec.graphql.org/October2021/#InputValueDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputValueDefinition {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) ty: Type,
+    pub(crate) default_value: Option<Value>,
+    pub(crate) deprecation_reason: Option<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) range: Range<usize>,
+}
+
+impl InputValueDefinition {
+    /// Get the input value's name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the input value's description, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Check whether the input value is marked as `@deprecated`.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
+    /// Get the reason given by the `@deprecated` directive, if the input value is
+    /// deprecated.
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_reason.as_deref()
+    }
+
+    /// Get the directives applied to the input value, including `@deprecated`.
+    pub fn directives(&self) -> &[Directive] {
+        self.directives.as_ref()
+    }
+
+    /// Get the input value's type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Get the input value's default value, if any.
+    pub fn default_value(&self) -> Option<&Value> {
+        self.default_value.as_ref()
+    }
+
+    /// Check whether a value must be provided for this input value: it is
+    /// non-null and has no default value.
+    pub fn is_required(&self) -> bool {
+        self.ty.is_non_null() && self.default_value.is_none()
+    }
+
+    /// Get the byte range of the input value's definition.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// See: https://spec.graphql.org/October2021/#DirectiveDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectiveDefinition {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) arguments: Vec<InputValueDefinition>,
+    pub(crate) repeatable: bool,
+    pub(crate) locations: Vec<DirectiveLocation>,
+    pub(crate) built_in: bool,
+    pub(crate) range: Range<usize>,
+}
+
+impl DirectiveDefinition {
+    /// Get the directive's name, without the `@`.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the directive's description, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the directive's arguments.
+    pub fn arguments(&self) -> &[InputValueDefinition] {
+        self.arguments.as_ref()
+    }
+
+    /// Find an argument by name.
+    pub fn argument(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+
+    /// Check whether the directive can be used more than once at a single
+    /// location.
+    pub fn is_repeatable(&self) -> bool {
+        self.repeatable
+    }
+
+    /// Get the locations the directive can be used at.
+    pub fn locations(&self) -> &[DirectiveLocation] {
+        self.locations.as_ref()
+    }
+
+    /// Check whether this is one of the directives built into every schema,
+    /// rather than one defined in a document.
+    pub fn is_built_in(&self) -> bool {
+        self.built_in
+    }
+
+    /// Get the byte range of the directive's definition.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// A directive applied to the schema, a type, a field, an argument or an enum
+/// value, e.g. `@key(fields: "id")`.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Language.Directives
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<(String, Value)>,
+    pub(crate) range: Range<usize>,
+}
+
+impl Directive {
+    /// Get the directive's name, without the `@`.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Get the directive's arguments, in the order they are written.
+    pub fn arguments(&self) -> &[(String, Value)] {
+        self.arguments.as_ref()
+    }
+
+    /// Find the value of an argument by name.
+    pub fn argument(&self, name: &str) -> Option<&Value> {
+        self.arguments
+            .iter()
+            .find(|(arg, _)| arg == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Get the byte range of the directive.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// The places in a document a directive can be used at.
+///
+/// See: https://spec.graphql.org/October2021/#DirectiveLocations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirectiveLocation {
+    Query,
+    Mutation,
+    Subscription,
+    Field,
+    FragmentDefinition,
+    FragmentSpread,
+    InlineFragment,
+    VariableDefinition,
+    Schema,
+    Scalar,
+    Object,
+    FieldDefinition,
+    ArgumentDefinition,
+    Interface,
+    Union,
+    Enum,
+    EnumValue,
+    InputObject,
+    InputFieldDefinition,
+}
+
+impl DirectiveLocation {
+    /// Get the location's name as written in a directive definition, e.g.
+    /// `FIELD_DEFINITION`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DirectiveLocation::Query => "QUERY",
+            DirectiveLocation::Mutation => "MUTATION",
+            DirectiveLocation::Subscription => "SUBSCRIPTION",
+            DirectiveLocation::Field => "FIELD",
+            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+            DirectiveLocation::Schema => "SCHEMA",
+            DirectiveLocation::Scalar => "SCALAR",
+            DirectiveLocation::Object => "OBJECT",
+            DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            DirectiveLocation::Interface => "INTERFACE",
+            DirectiveLocation::Union => "UNION",
+            DirectiveLocation::Enum => "ENUM",
+            DirectiveLocation::EnumValue => "ENUM_VALUE",
+            DirectiveLocation::InputObject => "INPUT_OBJECT",
+            DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let location = match name {
+            "QUERY" => DirectiveLocation::Query,
+            "MUTATION" => DirectiveLocation::Mutation,
+            "SUBSCRIPTION" => DirectiveLocation::Subscription,
+            "FIELD" => DirectiveLocation::Field,
+            "FRAGMENT_DEFINITION" => DirectiveLocation::FragmentDefinition,
+            "FRAGMENT_SPREAD" => DirectiveLocation::FragmentSpread,
+            "INLINE_FRAGMENT" => DirectiveLocation::InlineFragment,
+            "VARIABLE_DEFINITION" => DirectiveLocation::VariableDefinition,
+            "SCHEMA" => DirectiveLocation::Schema,
+            "SCALAR" => DirectiveLocation::Scalar,
+            "OBJECT" => DirectiveLocation::Object,
+            "FIELD_DEFINITION" => DirectiveLocation::FieldDefinition,
+            "ARGUMENT_DEFINITION" => DirectiveLocation::ArgumentDefinition,
+            "INTERFACE" => DirectiveLocation::Interface,
+            "UNION" => DirectiveLocation::Union,
+            "ENUM" => DirectiveLocation::Enum,
+            "ENUM_VALUE" => DirectiveLocation::EnumValue,
+            "INPUT_OBJECT" => DirectiveLocation::InputObject,
+            "INPUT_FIELD_DEFINITION" => DirectiveLocation::InputFieldDefinition,
+            _ => return None,
+        };
+        Some(location)
+    }
+}
+
+impl fmt::Display for DirectiveLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A reference to a type, possibly wrapped in lists and non-null modifiers.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Type-References
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// A named type, e.g. `String`.
+    Named(String),
+    /// A list type, e.g. `[String]`.
+    List(Box<Type>),
+    /// A non-null type, e.g. `String!`.
+    NonNull(Box<Type>),
+}
+
+impl Type {
+    /// Get the name of the named type at the core of this type, e.g.
+    /// `String` for `[String!]!`.
+    pub fn name(&self) -> &str {
+        match self {
+            Type::Named(name) => name,
+            Type::List(ty) | Type::NonNull(ty) => ty.name(),
+        }
+    }
+
+    /// Check whether this type is non-null.
+    pub fn is_non_null(&self) -> bool {
+        matches!(self, Type::NonNull(_))
+    }
+
+    /// Check whether this type is a list, ignoring a non-null modifier.
+    pub fn is_list(&self) -> bool {
+        match self {
+            Type::List(_) => true,
+            Type::NonNull(ty) => ty.is_list(),
+            Type::Named(_) => false,
+        }
+    }
+
+    /// Get this type without its outermost non-null modifier.
+    pub fn nullable(&self) -> &Type {
+        match self {
+            Type::NonNull(ty) => ty,
+            ty => ty,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named(name) => write!(f, "{}", name),
+            Type::List(ty) => write!(f, "[{}]", ty),
+            Type::NonNull(ty) => write!(f, "{}!", ty),
+        }
+    }
+}
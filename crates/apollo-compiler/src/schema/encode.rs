@@ -0,0 +1,415 @@
+use apollo_encoder as encoder;
+
+use crate::{Schema, Type, TypeDefinition, Value};
+
+use super::{definitions::*, DEFAULT_DEPRECATION_REASON};
+
+impl Schema {
+    /// Convert the schema into an `apollo_encoder::Document`, to print it as
+    /// SDL.
+    ///
+    /// Extensions are printed merged into the types they extend, with the
+    /// directives applied to them. The built-in types and directives of the
+    /// [`PRELUDE`](crate::schema::PRELUDE) are left out, and so is the schema
+    /// definition when the root operation types have their conventional names
+    /// and it has no description or directives.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_compiler::SchemaBuilder;
+    /// use apollo_parser::Parser;
+    /// use indoc::indoc;
+    ///
+    /// let mut builder = SchemaBuilder::new();
+    /// builder.document(&Parser::new("type Query { a: Int }").parse().document());
+    /// builder.document(&Parser::new("extend type Query { b: [String!] }").parse().document());
+    /// let schema = builder.build();
+    ///
+    /// assert_eq!(
+    ///     schema.to_document().to_string(),
+    ///     indoc! { r#"
+    ///         type Query {
+    ///           a: Int
+    ///           b: [String!]
+    ///         }
+    ///     "#}
+    /// );
+    /// ```
+    pub fn to_document(&self) -> encoder::Document {
+        let mut document = encoder::Document::new();
+
+        let roots = (
+            self.query_type(),
+            self.mutation_type(),
+            self.subscription_type(),
+        );
+        let schema = schema_definition(roots.0, roots.1, roots.2).or_else(|| {
+            (self.description.is_some() || !self.schema_directives.is_empty())
+                .then(|| root_operation_types(roots.0, roots.1, roots.2))
+        });
+        if let Some(mut schema) = schema {
+            schema.description(self.description.clone());
+            for directive in &self.schema_directives {
+                schema.directive(encoder_directive(directive));
+            }
+            document.schema(schema);
+        }
+
+        for ty in self.types().filter(|ty| !ty.is_built_in()) {
+            match ty {
+                TypeDefinition::Scalar(scalar) => {
+                    let mut def = encoder::ScalarDefinition::new(scalar.name.clone());
+                    def.description(scalar.description.clone());
+                    for directive in &scalar.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    document.scalar(def);
+                }
+                TypeDefinition::Object(object) => {
+                    let mut def = encoder::ObjectDefinition::new(object.name.clone());
+                    def.description(object.description.clone());
+                    for interface in &object.implements_interfaces {
+                        def.interface(interface.clone());
+                    }
+                    for directive in &object.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    for field in &object.fields {
+                        def.field(field_definition(field));
+                    }
+                    document.object(def);
+                }
+                TypeDefinition::Interface(interface) => {
+                    let mut def = encoder::InterfaceDefinition::new(interface.name.clone());
+                    def.description(interface.description.clone());
+                    for implements in &interface.implements_interfaces {
+                        def.interface(implements.clone());
+                    }
+                    for directive in &interface.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    for field in &interface.fields {
+                        def.field(field_definition(field));
+                    }
+                    document.interface(def);
+                }
+                TypeDefinition::Union(union) => {
+                    let mut def = encoder::UnionDefinition::new(union.name.clone());
+                    def.description(union.description.clone());
+                    for directive in &union.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    for member in &union.members {
+                        def.member(member.clone());
+                    }
+                    document.union(def);
+                }
+                TypeDefinition::Enum(enum_ty) => {
+                    let mut def = encoder::EnumDefinition::new(enum_ty.name.clone());
+                    def.description(enum_ty.description.clone());
+                    for directive in &enum_ty.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    for value in &enum_ty.values {
+                        let mut enum_value = encoder::EnumValue::new(value.value.clone());
+                        enum_value.description(value.description.clone());
+                        for directive in &value.directives {
+                            enum_value.directive(encoder_directive(directive));
+                        }
+                        def.value(enum_value);
+                    }
+                    document.enum_(def);
+                }
+                TypeDefinition::InputObject(input) => {
+                    let mut def = encoder::InputObjectDefinition::new(input.name.clone());
+                    def.description(input.description.clone());
+                    for directive in &input.directives {
+                        def.directive(encoder_directive(directive));
+                    }
+                    for field in &input.fields {
+                        let mut input_field =
+                            encoder::InputField::new(field.name.clone(), encoder_type(&field.ty));
+                        input_field.description(field.description.clone());
+                        input_field.default(field.default_value.as_ref().map(Value::to_string));
+                        for directive in &field.directives {
+                            input_field.directive(encoder_directive(directive));
+                        }
+                        def.field(input_field);
+                    }
+                    document.input_object(def);
+                }
+            }
+        }
+
+        for directive in self
+            .directives()
+            .filter(|directive| !directive.is_built_in())
+        {
+            let mut def = encoder::DirectiveDefinition::new(directive.name.clone());
+            def.description(directive.description.clone());
+            for arg in &directive.arguments {
+                def.arg(input_value_definition(arg));
+            }
+            if directive.repeatable {
+                def.repeatable();
+            }
+            for location in &directive.locations {
+                def.location(location.name().to_string());
+            }
+            document.directive(def);
+        }
+
+        document
+    }
+}
+
+pub(crate) fn field_definition(field: &FieldDefinition) -> encoder::FieldDefinition {
+    let mut def = encoder::FieldDefinition::new(field.name.clone(), encoder_type(&field.ty));
+    def.description(field.description.clone());
+    for arg in &field.arguments {
+        def.arg(input_value_definition(arg));
+    }
+    for directive in &field.directives {
+        def.directive(encoder_directive(directive));
+    }
+    def
+}
+
+fn input_value_definition(input: &InputValueDefinition) -> encoder::InputValueDefinition {
+    let mut def = encoder::InputValueDefinition::new(input.name.clone(), encoder_type(&input.ty));
+    def.description(input.description.clone());
+    def.default(input.default_value.as_ref().map(Value::to_string));
+    for directive in &input.directives {
+        def.directive(encoder_directive(directive));
+    }
+    def
+}
+
+/// Get the schema definition for the given root operation types, or `None`
+/// when they all have their conventional names and it can be left out.
+pub(crate) fn schema_definition(
+    query: Option<&str>,
+    mutation: Option<&str>,
+    subscription: Option<&str>,
+) -> Option<encoder::SchemaDefinition> {
+    let roots = [
+        (query, "Query"),
+        (mutation, "Mutation"),
+        (subscription, "Subscription"),
+    ];
+    if roots
+        .iter()
+        .all(|(root, conventional)| root.map_or(true, |root| root == *conventional))
+    {
+        return None;
+    }
+    Some(root_operation_types(query, mutation, subscription))
+}
+
+/// Get a schema definition setting the given root operation types.
+fn root_operation_types(
+    query: Option<&str>,
+    mutation: Option<&str>,
+    subscription: Option<&str>,
+) -> encoder::SchemaDefinition {
+    let mut schema = encoder::SchemaDefinition::new();
+    if let Some(query) = query {
+        schema.query(query.to_string());
+    }
+    if let Some(mutation) = mutation {
+        schema.mutation(mutation.to_string());
+    }
+    if let Some(subscription) = subscription {
+        schema.subscription(subscription.to_string());
+    }
+    schema
+}
+
+/// Convert a directive applied in the schema.
+fn encoder_directive(directive: &Directive) -> encoder::Directive {
+    let mut encoded = encoder::Directive::new(directive.name.clone());
+    for (name, value) in &directive.arguments {
+        encoded.arg(encoder::Argument::new(name.clone(), encoder_value(value)));
+    }
+    encoded
+}
+
+/// Get a `@deprecated` directive. The `reason` argument is left out when it
+/// is the default reason.
+pub(crate) fn deprecated(reason: &str) -> encoder::Directive {
+    let mut directive = encoder::Directive::new("deprecated".to_string());
+    if reason != DEFAULT_DEPRECATION_REASON {
+        directive.arg(encoder::Argument::new(
+            "reason".to_string(),
+            encoder::Value::String(reason.to_string()),
+        ));
+    }
+    directive
+}
+
+pub(crate) fn specified_by(url: &str) -> encoder::Directive {
+    let mut directive = encoder::Directive::new("specifiedBy".to_string());
+    directive.arg(encoder::Argument::new(
+        "url".to_string(),
+        encoder::Value::String(url.to_string()),
+    ));
+    directive
+}
+
+pub(crate) fn encoder_type(ty: &Type) -> encoder::Type_ {
+    match ty {
+        Type::Named(name) => encoder::Type_::NamedType { name: name.clone() },
+        Type::List(ty) => encoder::Type_::List {
+            ty: Box::new(encoder_type(ty)),
+        },
+        Type::NonNull(ty) => encoder::Type_::NonNull {
+            ty: Box::new(encoder_type(ty)),
+        },
+    }
+}
+
+pub(crate) fn encoder_value(value: &Value) -> encoder::Value {
+    match value {
+        Value::Variable(name) => encoder::Value::Variable(name.clone()),
+        Value::Int(int) => encoder::Value::Int(*int),
+        Value::Float(float) => encoder::Value::Float(*float),
+        Value::String(string) => encoder::Value::String(string.clone()),
+        Value::Boolean(boolean) => encoder::Value::Boolean(*boolean),
+        Value::Null => encoder::Value::Null,
+        Value::Enum(value) => encoder::Value::Enum(value.clone()),
+        Value::List(values) => encoder::Value::List(values.iter().map(encoder_value).collect()),
+        Value::Object(fields) => encoder::Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), encoder_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::SchemaBuilder;
+
+    #[test]
+    fn it_prints_a_merged_schema() {
+        let base = r#"
+schema { query: Root }
+"The root"
+type Root implements Node { id: ID! }
+interface Node { id: ID! }
+enum Size { SMALL }
+input Filter { size: Size = SMALL }
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+"#;
+        let extension = "
+extend type Root { search(filter: Filter, first: Int = 10): [Result!]! }
+union Result = Root
+extend enum Size { LARGE }
+scalar Date
+";
+        let mut builder = SchemaBuilder::new();
+        builder.document(&Parser::new(base).parse().document());
+        builder.document(&Parser::new(extension).parse().document());
+        let schema = builder.build();
+        assert_eq!(schema.errors(), []);
+
+        let printed = schema.to_document().to_string();
+        assert_eq!(
+            printed,
+            indoc! { r#"
+                schema {
+                  query: Root
+                }
+                scalar Date
+                "The root"
+                type Root implements Node {
+                  id: ID!
+                  search(filter: Filter, first: Int = 10): [Result!]!
+                }
+                interface Node {
+                  id: ID!
+                }
+                union Result = Root
+                enum Size {
+                  SMALL
+                  LARGE
+                }
+                input Filter {
+                  size: Size = SMALL
+                }
+                directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+            "#}
+        );
+
+        // The printed schema parses back into the same schema.
+        let reparsed = crate::Schema::new(&Parser::new(&printed).parse().document());
+        assert_eq!(reparsed.errors(), []);
+        assert_eq!(reparsed.to_document().to_string(), printed);
+    }
+
+    #[test]
+    fn it_prints_applied_directives() {
+        let base = r#"
+"The schema"
+schema @link(url: "https://example.com/a") { query: Query }
+type Query @key(fields: "id") {
+  id: ID!
+  search(term: String @tag(name: "term")): [Size] @tag(name: "search")
+  old: Int @deprecated
+}
+enum Size { SMALL @tag(name: "small") LARGE @deprecated(reason: "Too large") }
+input Filter @tag(name: "filter") { size: Size @tag(name: "size") }
+scalar Url @specifiedBy(url: "https://url.spec.whatwg.org/")
+directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+directive @tag(name: String!) on FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+directive @link(url: String!) repeatable on SCHEMA
+"#;
+        let extension = r#"
+extend schema @link(url: "https://example.com/b")
+extend type Query @key(fields: "name") { name: String }
+"#;
+        let mut builder = SchemaBuilder::new();
+        builder.document(&Parser::new(base).parse().document());
+        builder.document(&Parser::new(extension).parse().document());
+        let schema = builder.build();
+        assert_eq!(schema.errors(), []);
+
+        let printed = schema.to_document().to_string();
+        assert_eq!(
+            printed,
+            indoc! { r#"
+                "The schema"
+                schema @link(url: "https://example.com/a") @link(url: "https://example.com/b") {
+                  query: Query
+                }
+                scalar Url @specifiedBy(url: "https://url.spec.whatwg.org/")
+                type Query @key(fields: "id") @key(fields: "name") {
+                  id: ID!
+                  search(term: String @tag(name: "term")): [Size] @tag(name: "search")
+                  old: Int @deprecated
+                  name: String
+                }
+                enum Size {
+                  SMALL @tag(name: "small")
+                  LARGE @deprecated(reason: "Too large")
+                }
+                input Filter @tag(name: "filter") {
+                  size: Size @tag(name: "size")
+                }
+                directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+                directive @tag(name: String!) on FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+                directive @link(url: String!) repeatable on SCHEMA
+            "#}
+        );
+
+        // The printed schema parses back into the same schema.
+        let reparsed = crate::Schema::new(&Parser::new(&printed).parse().document());
+        assert_eq!(reparsed.errors(), []);
+        assert_eq!(reparsed.to_document().to_string(), printed);
+    }
+}
@@ -0,0 +1,411 @@
+//! A semantic model of a GraphQL type system.
+//!
+//! The [`Schema`] is built from the type system definitions of a parsed
+//! `ast::Document`. Unlike the syntax tree, it is keyed by name, so finding a
+//! type, the fields of a type or the possible types of an abstract type does
+//! not require walking the whole document.
+mod builder;
+mod definitions;
+pub(crate) mod encode;
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::diagnostics::Diagnostic;
+
+pub use builder::SchemaBuilder;
+pub use definitions::*;
+
+/// The type system definitions every schema includes: the built-in `Int`,
+/// `Float`, `String`, `Boolean` and `ID` scalars, the `@skip`, `@include`,
+/// `@deprecated` and `@specifiedBy` directives, and the `__Schema`, `__Type`
+/// and other introspection types.
+///
+/// The prelude is added to every schema built with [`Schema::new`] or a
+/// [`SchemaBuilder`], and its definitions cannot be redefined. The
+/// `__typename`, `__schema` and `__type` meta-fields cannot be written as SDL,
+/// so they are not part of the prelude: use [`Schema::field_definition`] to
+/// find them.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+pub const PRELUDE: &str = include_str!("prelude.graphql");
+
+/// The reason of a `@deprecated` directive without a `reason` argument.
+pub(crate) const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// A GraphQL type system: its named types, directives and root operation
+/// types.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::Schema;
+/// use apollo_parser::Parser;
+///
+/// let input = "
+/// interface Pet { name: String }
+/// type Dog implements Pet { name: String }
+/// type Cat implements Pet { name: String }
+/// type Query { pets: [Pet] }
+/// ";
+/// let document = Parser::new(input).parse().document();
+/// let schema = Schema::new(&document);
+///
+/// assert_eq!(schema.errors().len(), 0);
+/// assert_eq!(schema.query_type(), Some("Query"));
+/// assert_eq!(schema.possible_types("Pet"), vec!["Dog", "Cat"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub(crate) description: Option<String>,
+    pub(crate) schema_directives: Vec<Directive>,
+    pub(crate) query_type: Option<String>,
+    pub(crate) mutation_type: Option<String>,
+    pub(crate) subscription_type: Option<String>,
+    pub(crate) types: IndexMap<String, TypeDefinition>,
+    pub(crate) directives: IndexMap<String, DirectiveDefinition>,
+    pub(crate) meta_fields: Vec<FieldDefinition>,
+    pub(crate) errors: Vec<Diagnostic>,
+}
+
+impl Schema {
+    /// Build a schema from the type system definitions and extensions in
+    /// `document`. Use a [`SchemaBuilder`] to build a schema from several
+    /// documents.
+    ///
+    /// Executable definitions are ignored. The definitions of the [`PRELUDE`]
+    /// are always added. Problems found while building the schema, such as a
+    /// type being defined twice, are available with [`Schema::errors`].
+    pub fn new(document: &ast::Document) -> Self {
+        let mut builder = SchemaBuilder::new();
+        builder.document(document);
+        builder.build()
+    }
+
+    /// Get the description of the schema definition, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the directives applied to the schema definition and its
+    /// extensions.
+    pub fn schema_directives(&self) -> &[Directive] {
+        self.schema_directives.as_ref()
+    }
+
+    /// Get the name of the query root operation type.
+    pub fn query_type(&self) -> Option<&str> {
+        self.query_type.as_deref()
+    }
+
+    /// Get the name of the mutation root operation type.
+    pub fn mutation_type(&self) -> Option<&str> {
+        self.mutation_type.as_deref()
+    }
+
+    /// Get the name of the subscription root operation type.
+    pub fn subscription_type(&self) -> Option<&str> {
+        self.subscription_type.as_deref()
+    }
+
+    /// Get an iterator over all named types in definition order, starting
+    /// with the built-in types of the [`PRELUDE`].
+    pub fn types(&self) -> impl Iterator<Item = &TypeDefinition> {
+        self.types.values()
+    }
+
+    /// Find a named type.
+    pub fn type_definition(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types.get(name)
+    }
+
+    /// Find the definition of the field `field` selected on the type `ty`.
+    ///
+    /// This includes the meta-fields: `__typename` on every object,
+    /// interface and union type, and `__schema` and `__type` on the query
+    /// root operation type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    /// use apollo_parser::Parser;
+    ///
+    /// let document = Parser::new("type Query { me: String }").parse().document();
+    /// let schema = Schema::new(&document);
+    ///
+    /// let ty = schema.field_definition("Query", "__type").unwrap().ty();
+    /// assert_eq!(ty.to_string(), "__Type");
+    /// assert!(schema.field_definition("__Type", "__typename").is_some());
+    /// assert!(schema.field_definition("__Type", "__schema").is_none());
+    /// ```
+    pub fn field_definition(&self, ty: &str, field: &str) -> Option<&FieldDefinition> {
+        let definition = self.types.get(ty)?;
+        let meta_field = match field {
+            "__typename" => definition.is_composite(),
+            "__schema" | "__type" => self.query_type.as_deref() == Some(ty),
+            _ => false,
+        };
+        if meta_field {
+            self.meta_fields.iter().find(|meta| meta.name == field)
+        } else {
+            definition.field(field)
+        }
+    }
+
+    /// Get an iterator over all directive definitions, including the
+    /// built-in directives.
+    pub fn directives(&self) -> impl Iterator<Item = &DirectiveDefinition> {
+        self.directives.values()
+    }
+
+    /// Find a directive definition by name, without the `@`.
+    pub fn directive_definition(&self, name: &str) -> Option<&DirectiveDefinition> {
+        self.directives.get(name)
+    }
+
+    /// Get the names of the object types that can be used where `name` is
+    /// expected: the type itself for an object type, the implementations of
+    /// an interface, or the members of a union.
+    pub fn possible_types(&self, name: &str) -> Vec<&str> {
+        match self.types.get(name) {
+            Some(TypeDefinition::Object(ty)) => vec![ty.name.as_str()],
+            Some(TypeDefinition::Interface(_)) => self
+                .types
+                .values()
+                .filter_map(|ty| match ty {
+                    TypeDefinition::Object(object)
+                        if object.implements_interfaces.iter().any(|i| i == name) =>
+                    {
+                        Some(object.name.as_str())
+                    }
+                    _ => None,
+                })
+                .collect(),
+            Some(TypeDefinition::Union(ty)) => ty
+                .members
+                .iter()
+                .filter(|member| matches!(self.types.get(*member), Some(TypeDefinition::Object(_))))
+                .map(String::as_str)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Check whether an object of type `possible` can be used where
+    /// `abstract_ty` is expected.
+    pub fn is_possible_type(&self, abstract_ty: &str, possible: &str) -> bool {
+        self.possible_types(abstract_ty).contains(&possible)
+    }
+
+    /// Get a reference to the errors found while building the schema.
+    pub fn errors(&self) -> &[Diagnostic] {
+        self.errors.as_ref()
+    }
+}
+
+pub(crate) fn name(node: Option<ast::Name>) -> Option<String> {
+    Some(node?.text().to_string())
+}
+
+pub(crate) fn ty(node: ast::Type) -> Option<Type> {
+    match node {
+        ast::Type::NamedType(named) => Some(Type::Named(name(named.name())?)),
+        ast::Type::ListType(list) => Some(Type::List(Box::new(ty(list.ty()?)?))),
+        ast::Type::NonNullType(non_null) => {
+            let inner = if let Some(named) = non_null.named_type() {
+                Type::Named(name(named.name())?)
+            } else {
+                Type::List(Box::new(ty(non_null.list_type()?.ty()?)?))
+            };
+            Some(Type::NonNull(Box::new(inner)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_builds_types_from_definitions() {
+        let input = r#"
+"A dog"
+type Dog implements Pet & Animal {
+  name(short: Boolean): String!
+  friends: [Dog!]
+}
+union Search = Dog | Cat
+enum Size { SMALL LARGE }
+input Filter { size: Size = SMALL }
+"#;
+        let document = Parser::new(input).parse().document();
+        let schema = Schema::new(&document);
+        assert_eq!(schema.errors().len(), 0);
+
+        let dog = schema.type_definition("Dog").unwrap();
+        assert_eq!(dog.kind(), TypeKind::Object);
+        assert_eq!(dog.description(), Some("A dog"));
+        assert_eq!(
+            &input[dog.range()],
+            &input[1..input.find("union").unwrap() - 1]
+        );
+
+        let name = dog.field("name").unwrap();
+        assert_eq!(name.ty().to_string(), "String!");
+        assert_eq!(name.arguments()[0].ty().to_string(), "Boolean");
+        assert_eq!(dog.field("friends").unwrap().ty().to_string(), "[Dog!]");
+
+        assert_eq!(schema.possible_types("Search"), vec!["Dog"]);
+        assert_eq!(
+            schema.type_definition("Size").unwrap().kind(),
+            TypeKind::Enum
+        );
+        assert_eq!(schema.query_type(), None);
+    }
+
+    #[test]
+    fn it_reports_duplicate_types() {
+        let input = "
+type Query { a: Int }
+schema { query: Query }
+scalar Query
+schema { query: Query }
+";
+        let document = Parser::new(input).parse().document();
+        let schema = Schema::new(&document);
+
+        let messages: Vec<_> = schema.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "the type `Query` is defined multiple times",
+                "a schema can only be defined once"
+            ]
+        );
+        assert_eq!(schema.query_type(), Some("Query"));
+    }
+
+    #[test]
+    fn it_builds_directive_definitions() {
+        let input = "
+directive @tag(name: String!, weight: Int = 1) repeatable on FIELD_DEFINITION | OBJECT
+directive @tag on SCALAR
+";
+        let document = Parser::new(input).parse().document();
+        let schema = Schema::new(&document);
+
+        let messages: Vec<_> = schema.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages, ["the directive `@tag` is defined multiple times"]);
+
+        let tag = schema.directive_definition("tag").unwrap();
+        assert!(tag.is_repeatable());
+        assert_eq!(
+            tag.locations(),
+            [
+                DirectiveLocation::FieldDefinition,
+                DirectiveLocation::Object
+            ]
+        );
+        assert!(tag.argument("name").unwrap().is_required());
+        assert_eq!(
+            tag.argument("weight").unwrap().default_value(),
+            Some(&crate::Value::Int(1))
+        );
+
+        let deprecated = schema.directive_definition("deprecated").unwrap();
+        assert!(!deprecated.is_repeatable());
+        assert_eq!(
+            deprecated.argument("reason").unwrap().default_value(),
+            Some(&crate::Value::String("No longer supported".into()))
+        );
+        assert!(deprecated.is_built_in());
+        assert!(schema.directive_definition("include").is_some());
+        assert!(schema.directive_definition("specifiedBy").is_some());
+    }
+
+    #[test]
+    fn it_includes_the_prelude() {
+        assert_eq!(Parser::new(PRELUDE).parse().errors().len(), 0);
+
+        let document = Parser::new("type Query { a: Int }").parse().document();
+        let schema = Schema::new(&document);
+        assert_eq!(schema.errors().len(), 0);
+
+        for name in ["Int", "Float", "String", "Boolean", "ID"] {
+            let scalar = schema.type_definition(name).unwrap();
+            assert_eq!(scalar.kind(), TypeKind::Scalar);
+            assert!(scalar.is_built_in());
+        }
+        let introspection: Vec<_> = schema
+            .types()
+            .filter(|ty| ty.is_built_in() && ty.name().starts_with("__"))
+            .map(|ty| ty.name())
+            .collect();
+        assert_eq!(
+            introspection,
+            [
+                "__Schema",
+                "__Type",
+                "__TypeKind",
+                "__Field",
+                "__InputValue",
+                "__EnumValue",
+                "__Directive",
+                "__DirectiveLocation"
+            ]
+        );
+        assert!(!schema.type_definition("Query").unwrap().is_built_in());
+
+        let fields = schema.field_definition("__Type", "fields").unwrap();
+        assert_eq!(fields.ty().to_string(), "[__Field!]");
+        assert_eq!(
+            fields
+                .argument("includeDeprecated")
+                .unwrap()
+                .default_value(),
+            Some(&crate::Value::Boolean(false))
+        );
+        assert_eq!(
+            schema
+                .field_definition("Query", "__schema")
+                .unwrap()
+                .ty()
+                .to_string(),
+            "__Schema!"
+        );
+        assert!(schema.field_definition("Query", "__typename").is_some());
+        assert!(schema.field_definition("Int", "__typename").is_none());
+    }
+
+    #[test]
+    fn it_reports_redefined_built_ins() {
+        let input = "
+type Query { a: Int }
+scalar String
+type __Schema { types: [String] }
+directive @skip(if: Boolean!) on FIELD
+extend scalar Int
+";
+        let document = Parser::new(input).parse().document();
+        let schema = Schema::new(&document);
+
+        let messages: Vec<_> = schema.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "the built-in type `String` cannot be redefined",
+                "the built-in type `__Schema` cannot be redefined",
+                "the built-in directive `@skip` cannot be redefined",
+                "cannot extend the built-in type `Int`",
+            ]
+        );
+
+        // The built-in definitions are kept.
+        let skip = schema.directive_definition("skip").unwrap();
+        assert!(skip.is_built_in());
+        assert_eq!(skip.locations().len(), 3);
+        assert!(schema.type_definition("__Schema").unwrap().is_built_in());
+    }
+}
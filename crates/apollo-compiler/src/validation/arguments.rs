@@ -0,0 +1,91 @@
+//! See: https://spec.graphql.org/October2021/#sec-Validation.Arguments
+use std::{collections::HashMap, ops::Range};
+
+use apollo_parser::ast;
+
+use crate::{
+    diagnostics::{self, Diagnostic},
+    schema::InputValueDefinition,
+};
+
+/// Argument Names, Argument Uniqueness and Required Arguments for the
+/// `arguments` given to a field or a directive. `coordinate` is the schema
+/// coordinate of the field or directive, e.g. `Query.user` or `@skip`, and
+/// `range` is where it is used.
+///
+/// The value of each defined argument is passed to `value`, which checks
+/// that it is valid for the argument's type.
+pub(crate) fn validate(
+    coordinate: &str,
+    range: Range<usize>,
+    definitions: &[InputValueDefinition],
+    arguments: Option<ast::Arguments>,
+    diagnostics: &mut Vec<Diagnostic>,
+    mut value: impl FnMut(&InputValueDefinition, &ast::Value, &mut Vec<Diagnostic>),
+) {
+    let mut provided: HashMap<String, Range<usize>> = HashMap::new();
+
+    for argument in arguments.iter().flat_map(|args| args.arguments()) {
+        let name = match argument.name() {
+            Some(name) => name.text().to_string(),
+            None => continue,
+        };
+        let arg_range = diagnostics::range(&argument);
+
+        if let Some(original) = provided.get(&name) {
+            diagnostics.push(
+                Diagnostic::new(format!(
+                    "the argument `{}` is provided multiple times",
+                    name
+                ))
+                .label(original.clone(), "previously provided here")
+                .label(arg_range, "provided again here"),
+            );
+            continue;
+        }
+        provided.insert(name.clone(), arg_range.clone());
+
+        match (
+            definitions.iter().find(|def| def.name() == name),
+            argument.value(),
+        ) {
+            (Some(definition), Some(argument_value)) => {
+                value(definition, &argument_value, diagnostics)
+            }
+            (Some(_), None) => (),
+            (None, _) => diagnostics.push(
+                Diagnostic::new(format!(
+                    "{} has no argument `{}`",
+                    describe(coordinate),
+                    name
+                ))
+                .label(arg_range, "unknown argument"),
+            ),
+        }
+    }
+
+    for definition in definitions {
+        if definition.is_required() && !provided.contains_key(definition.name()) {
+            diagnostics.push(
+                Diagnostic::new(format!(
+                    "the required argument `{}({}:)` is not provided",
+                    coordinate,
+                    definition.name()
+                ))
+                .label(
+                    range.clone(),
+                    format!("missing `{}: {}`", definition.name(), definition.ty()),
+                ),
+            );
+        }
+    }
+}
+
+/// Describe the field or directive at `coordinate` in a message.
+fn describe(coordinate: &str) -> String {
+    if coordinate.starts_with('@') {
+        format!("the directive `{}`", coordinate)
+    } else {
+        format!("the field `{}`", coordinate)
+    }
+}
@@ -0,0 +1,289 @@
+//! See: https://spec.graphql.org/October2021/#sec-Validation.Directives
+use std::{collections::HashMap, ops::Range};
+
+use apollo_parser::{
+    ast::{self, AstNode},
+    SyntaxKind, SyntaxNode,
+};
+
+use crate::{
+    coercion,
+    diagnostics::{self, Diagnostic},
+    validation, DirectiveDefinition, DirectiveLocation, Schema,
+};
+
+pub(crate) fn validate(
+    schema: &Schema,
+    document: &ast::Document,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // Every construct that accepts directives holds them in a `Directives`
+    // node, so walking those covers both executable and type system
+    // definitions.
+    for directives in document
+        .syntax()
+        .descendants()
+        .filter_map(ast::Directives::cast)
+    {
+        let location = directives
+            .syntax()
+            .parent()
+            .and_then(|parent| location(&parent));
+        let mut seen: HashMap<String, Range<usize>> = HashMap::new();
+
+        for directive in directives.directives() {
+            let name = match directive.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let range = diagnostics::range(&directive);
+
+            let definition = match self::definition(schema, &directive, diagnostics) {
+                Some(definition) => definition,
+                None => continue,
+            };
+
+            // Directives Are In Valid Locations
+            if let Some(location) = location {
+                if !definition.locations().contains(&location) {
+                    let valid: Vec<&str> = definition
+                        .locations()
+                        .iter()
+                        .map(|location| location.name())
+                        .collect();
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "the directive `@{}` cannot be used at the {} location",
+                            name, location
+                        ))
+                        .label(range.clone(), format!("not valid on {}", location))
+                        .help(format!(
+                            "`@{}` can only be used on {}",
+                            name,
+                            valid.join(", ")
+                        )),
+                    );
+                }
+            }
+
+            // Directives Are Unique Per Location
+            if let Some(original) = seen.get(&name) {
+                if !definition.is_repeatable() {
+                    diagnostics.push(
+                        Diagnostic::new(format!(
+                            "the directive `@{}` can only be used once at this location",
+                            name
+                        ))
+                        .label(original.clone(), "first used here")
+                        .label(range.clone(), "used again here")
+                        .help(format!(
+                            "mark the directive as `repeatable` to use it more than once: `directive @{} repeatable on ...`",
+                            name
+                        )),
+                    );
+                }
+            } else {
+                seen.insert(name.clone(), range.clone());
+            }
+
+            arguments(schema, &directive, definition, range, diagnostics);
+        }
+    }
+}
+
+/// Find the definition of `directive` in `schema`, reporting it when it is
+/// not defined.
+pub(crate) fn definition<'a>(
+    schema: &'a Schema,
+    directive: &ast::Directive,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<&'a DirectiveDefinition> {
+    let name = directive.name()?.text().to_string();
+    let definition = schema.directive_definition(&name);
+    if definition.is_none() {
+        diagnostics.push(
+            Diagnostic::new(format!(
+                "cannot find directive `@{}` in this document",
+                name
+            ))
+            .label(diagnostics::range(directive), "directive not defined")
+            .help(format!(
+                "define the directive with `directive @{} on ...`",
+                name
+            )),
+        );
+    }
+    definition
+}
+
+/// Argument Names, Argument Uniqueness, Required Arguments and Values of
+/// Correct Type for the arguments of one directive.
+fn arguments(
+    schema: &Schema,
+    directive: &ast::Directive,
+    definition: &DirectiveDefinition,
+    range: Range<usize>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    validation::arguments::validate(
+        &format!("@{}", definition.name()),
+        range,
+        definition.arguments(),
+        directive.arguments(),
+        diagnostics,
+        |definition, value, diagnostics| {
+            coercion::validate_literal(schema, definition.ty(), value, diagnostics)
+        },
+    );
+}
+
+/// Find the directive location of the node a `Directives` node belongs to.
+fn location(node: &SyntaxNode) -> Option<DirectiveLocation> {
+    let location = match node.kind() {
+        SyntaxKind::OPERATION_DEFINITION => {
+            let operation = ast::OperationDefinition::cast(node.clone())?;
+            match operation.operation_type() {
+                Some(ty) if ty.mutation_token().is_some() => DirectiveLocation::Mutation,
+                Some(ty) if ty.subscription_token().is_some() => DirectiveLocation::Subscription,
+                _ => DirectiveLocation::Query,
+            }
+        }
+        SyntaxKind::FIELD => DirectiveLocation::Field,
+        SyntaxKind::FRAGMENT_DEFINITION => DirectiveLocation::FragmentDefinition,
+        SyntaxKind::FRAGMENT_SPREAD => DirectiveLocation::FragmentSpread,
+        SyntaxKind::INLINE_FRAGMENT => DirectiveLocation::InlineFragment,
+        SyntaxKind::VARIABLE_DEFINITION => DirectiveLocation::VariableDefinition,
+        SyntaxKind::SCHEMA_DEFINITION | SyntaxKind::SCHEMA_EXTENSION => DirectiveLocation::Schema,
+        SyntaxKind::SCALAR_TYPE_DEFINITION | SyntaxKind::SCALAR_TYPE_EXTENSION => {
+            DirectiveLocation::Scalar
+        }
+        SyntaxKind::OBJECT_TYPE_DEFINITION | SyntaxKind::OBJECT_TYPE_EXTENSION => {
+            DirectiveLocation::Object
+        }
+        SyntaxKind::FIELD_DEFINITION => DirectiveLocation::FieldDefinition,
+        SyntaxKind::INPUT_VALUE_DEFINITION => match node.parent()?.kind() {
+            SyntaxKind::INPUT_FIELDS_DEFINITION => DirectiveLocation::InputFieldDefinition,
+            _ => DirectiveLocation::ArgumentDefinition,
+        },
+        SyntaxKind::INTERFACE_TYPE_DEFINITION | SyntaxKind::INTERFACE_TYPE_EXTENSION => {
+            DirectiveLocation::Interface
+        }
+        SyntaxKind::UNION_TYPE_DEFINITION | SyntaxKind::UNION_TYPE_EXTENSION => {
+            DirectiveLocation::Union
+        }
+        SyntaxKind::ENUM_TYPE_DEFINITION | SyntaxKind::ENUM_TYPE_EXTENSION => {
+            DirectiveLocation::Enum
+        }
+        SyntaxKind::ENUM_VALUE_DEFINITION => DirectiveLocation::EnumValue,
+        SyntaxKind::INPUT_OBJECT_TYPE_DEFINITION | SyntaxKind::INPUT_OBJECT_TYPE_EXTENSION => {
+            DirectiveLocation::InputObject
+        }
+        _ => return None,
+    };
+    Some(location)
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use crate::{validate, Schema};
+
+    const SCHEMA: &str = r#"
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+directive @cache(maxAge: Int, scope: Scope = PUBLIC) on FIELD_DEFINITION | QUERY
+directive @filter(where: Filter!) on FIELD
+
+enum Scope { PUBLIC PRIVATE }
+input Filter { name: String! size: [Int] }
+
+type Query @tag(name: "query") @tag(name: "root") {
+  name: String @cache(maxAge: 60) @tag(name: "name")
+}
+"#;
+
+    fn messages(operation: &str) -> Vec<String> {
+        let input = format!("{}{}", SCHEMA, operation);
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        validate(&schema, &document)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_valid_directives() {
+        let operation = r#"
+query Q($skip: Boolean!) @cache(maxAge: 10, scope: PRIVATE) {
+  name @skip(if: $skip) @include(if: true) @filter(where: { name: "a", size: 1 })
+}
+"#;
+        assert_eq!(messages(operation), Vec::<String>::new());
+    }
+
+    #[test]
+    fn it_reports_undefined_directives() {
+        let input = format!("{}{}", SCHEMA, "query { name @unknown }");
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        let diagnostics = validate(&schema, &document);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "cannot find directive `@unknown` in this document"
+        );
+        assert_eq!(&input[diagnostics[0].labels()[0].range()], "@unknown");
+    }
+
+    #[test]
+    fn it_reports_directives_in_invalid_locations() {
+        let operation = r#"
+query @skip(if: true) { name }
+scalar Date @deprecated
+type Other { field(arg: Int @deprecated): Int @specifiedBy(url: "x") }
+"#;
+        assert_eq!(
+            messages(operation),
+            [
+                "the directive `@skip` cannot be used at the QUERY location",
+                "the directive `@deprecated` cannot be used at the SCALAR location",
+                "the directive `@specifiedBy` cannot be used at the FIELD_DEFINITION location",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_repeated_directives() {
+        let operation = "query { name @skip(if: true) @skip(if: false) }";
+        assert_eq!(
+            messages(operation),
+            ["the directive `@skip` can only be used once at this location"]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_arguments() {
+        let operation = r#"
+query @cache(maxAge: "long", scope: ANYONE, maxAge: 1) {
+  name @skip @include(if: true, unless: false)
+    @filter(where: { size: [1, 2147483648], extra: 1 })
+}
+"#;
+        assert_eq!(
+            messages(operation),
+            [
+                "expected a value of type `Int`, found a string",
+                "the enum `Scope` has no value `ANYONE`",
+                "the argument `maxAge` is provided multiple times",
+                "the required argument `@skip(if:)` is not provided",
+                "the directive `@include` has no argument `unless`",
+                "the input object `Filter` has no field `extra`",
+                "the required field `Filter.name` is not provided",
+                "Int cannot represent non 32-bit signed integer value: 2147483648",
+            ]
+        );
+    }
+}
@@ -0,0 +1,562 @@
+//! See: https://spec.graphql.org/October2021/#sec-Validation.Fragments
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::{
+    diagnostics::{self, Diagnostic},
+    validation, Schema, TypeDefinition,
+};
+
+/// A fragment spread, and the byte range it was found at.
+struct Spread {
+    name: String,
+    range: Range<usize>,
+}
+
+struct Fragments<'a> {
+    schema: &'a Schema,
+    definitions: IndexMap<String, ast::FragmentDefinition>,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+pub(crate) fn validate(
+    schema: &Schema,
+    document: &ast::Document,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut fragments = Fragments {
+        schema,
+        definitions: IndexMap::new(),
+        diagnostics,
+    };
+
+    for definition in document.definitions() {
+        if let ast::Definition::FragmentDefinition(fragment) = definition {
+            fragments.definition(&fragment);
+        }
+    }
+
+    for definition in document.definitions() {
+        match definition {
+            ast::Definition::OperationDefinition(operation) => {
+                let root = validation::root_type(schema, &operation);
+                if let Some(selection_set) = operation.selection_set() {
+                    fragments.selection_set(&selection_set, root);
+                }
+            }
+            ast::Definition::FragmentDefinition(fragment) => {
+                let type_condition = fragments.type_condition(fragment.type_condition());
+                if let Some(selection_set) = fragment.selection_set() {
+                    fragments.selection_set(&selection_set, type_condition);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fragments.unused(document);
+    fragments.cycles();
+}
+
+impl<'a> Fragments<'a> {
+    /// Fragment Name Uniqueness.
+    fn definition(&mut self, fragment: &ast::FragmentDefinition) {
+        let name = match fragment.fragment_name().and_then(|n| n.name()) {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+
+        if let Some(original) = self.definitions.get(&name) {
+            let diagnostic =
+                Diagnostic::new(format!("the fragment `{}` is defined multiple times", name))
+                    .label(diagnostics::range(original), "previous definition here")
+                    .label(diagnostics::range(fragment), "redefined here")
+                    .help("fragment names must be unique within a document");
+            self.diagnostics.push(diagnostic);
+        } else {
+            self.definitions.insert(name, fragment.clone());
+        }
+    }
+
+    /// Fragment Spread Type Existence and Fragments On Composite Types: check
+    /// that a type condition names a composite type, returning the type's
+    /// name when it does.
+    fn type_condition(&mut self, type_condition: Option<ast::TypeCondition>) -> Option<&'a str> {
+        let named_type = type_condition?.named_type()?;
+        let name = named_type.name()?.text();
+        let schema = self.schema;
+
+        match schema.type_definition(&name) {
+            Some(ty) if ty.is_composite() => Some(ty.name()),
+            Some(ty) => {
+                self.diagnostics.push(
+                    Diagnostic::new(format!(
+                        "fragments cannot be defined on the {} type `{}`",
+                        ty.kind(),
+                        name
+                    ))
+                    .label(
+                        diagnostics::range(&named_type),
+                        "not an object, interface or union",
+                    )
+                    .help("fragments can only be defined on object, interface or union types"),
+                );
+                None
+            }
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::new(format!("unknown type `{}` in type condition", name))
+                        .label(diagnostics::range(&named_type), "not defined in the schema"),
+                );
+                None
+            }
+        }
+    }
+
+    /// Walk a selection set, checking every spread against `parent`: the
+    /// name of the type the selection set is selected on, when it is known.
+    fn selection_set(&mut self, selection_set: &ast::SelectionSet, parent: Option<&'a str>) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let ty = validation::field_type(self.schema, parent, &field);
+                    if let Some(selection_set) = field.selection_set() {
+                        self.selection_set(&selection_set, ty);
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => self.fragment_spread(&spread, parent),
+                ast::Selection::InlineFragment(inline) => {
+                    let type_condition = match inline.type_condition() {
+                        Some(type_condition) => {
+                            let type_condition = self.type_condition(Some(type_condition));
+                            if let (Some(parent), Some(type_condition)) = (parent, type_condition) {
+                                self.inline_fragment(&inline, parent, type_condition);
+                            }
+                            type_condition
+                        }
+                        None => parent,
+                    };
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.selection_set(&selection_set, type_condition);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fragment Spread Is Possible for an inline fragment.
+    fn inline_fragment(
+        &mut self,
+        inline: &ast::InlineFragment,
+        parent: &str,
+        type_condition: &str,
+    ) {
+        if !self.is_possible_spread(parent, type_condition) {
+            self.diagnostics.push(
+                Diagnostic::new(format!(
+                    "fragment cannot be spread here as objects of type `{}` can never be of type `{}`",
+                    parent, type_condition
+                ))
+                .label(diagnostics::range(inline), "inline fragment spread here"),
+            );
+        }
+    }
+
+    /// Fragment Spread Target Defined and Fragment Spread Is Possible.
+    fn fragment_spread(&mut self, spread: &ast::FragmentSpread, parent: Option<&str>) {
+        let name = match spread.fragment_name().and_then(|n| n.name()) {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+
+        let fragment = match self.definitions.get(&name).cloned() {
+            Some(fragment) => fragment,
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::new(format!("unknown fragment `{}`", name))
+                        .label(diagnostics::range(spread), "spread here")
+                        .help(format!(
+                            "define the fragment with `fragment {} on ...`",
+                            name
+                        )),
+                );
+                return;
+            }
+        };
+
+        let type_condition = fragment
+            .type_condition()
+            .and_then(|tc| tc.named_type())
+            .and_then(|ty| ty.name())
+            .map(|name| name.text().to_string());
+        if let (Some(parent), Some(type_condition)) = (parent, type_condition) {
+            let known = |name: &str| {
+                self.schema
+                    .type_definition(name)
+                    .map_or(false, TypeDefinition::is_composite)
+            };
+            if known(parent)
+                && known(&type_condition)
+                && !self.is_possible_spread(parent, &type_condition)
+            {
+                self.diagnostics.push(
+                    Diagnostic::new(format!(
+                        "fragment `{}` cannot be spread here as objects of type `{}` can never be of type `{}`",
+                        name, parent, type_condition
+                    ))
+                    .label(diagnostics::range(spread), "spread here")
+                    .label(
+                        diagnostics::range(&fragment),
+                        format!("`{}` is defined on `{}` here", name, type_condition),
+                    ),
+                );
+            }
+        }
+    }
+
+    /// A spread is possible when the possible types of the parent type and of
+    /// the type condition intersect.
+    fn is_possible_spread(&self, parent: &str, type_condition: &str) -> bool {
+        let parent_types = self.schema.possible_types(parent);
+        self.schema
+            .possible_types(type_condition)
+            .iter()
+            .any(|ty| parent_types.contains(ty))
+    }
+
+    /// Fragments Must Be Used: every fragment has to be reachable from an
+    /// operation, directly or through other fragments.
+    fn unused(&mut self, document: &ast::Document) {
+        let mut used = HashSet::new();
+        let mut queue: Vec<String> = document
+            .definitions()
+            .filter_map(|definition| match definition {
+                ast::Definition::OperationDefinition(operation) => operation.selection_set(),
+                _ => None,
+            })
+            .flat_map(|selection_set| spreads(&selection_set))
+            .map(|spread| spread.name)
+            .collect();
+
+        while let Some(name) = queue.pop() {
+            if !used.insert(name.clone()) {
+                continue;
+            }
+            if let Some(selection_set) = self
+                .definitions
+                .get(&name)
+                .and_then(|fragment| fragment.selection_set())
+            {
+                queue.extend(
+                    spreads(&selection_set)
+                        .into_iter()
+                        .map(|spread| spread.name),
+                );
+            }
+        }
+
+        for (name, fragment) in &self.definitions {
+            if !used.contains(name) {
+                self.diagnostics.push(
+                    Diagnostic::new(format!("fragment `{}` is never used", name))
+                        .label(diagnostics::range(fragment), "defined here")
+                        .help("remove the fragment or spread it in an operation"),
+                );
+            }
+        }
+    }
+
+    /// Fragment spreads must not form cycles. Each cycle is reported once,
+    /// with a label on every spread along the cycle's path.
+    fn cycles(&mut self) {
+        let mut visited = HashSet::new();
+        let mut path = Vec::new();
+        let mut path_index = HashMap::new();
+
+        let names: Vec<String> = self.definitions.keys().cloned().collect();
+        for name in names {
+            self.detect_cycles(&name, &mut visited, &mut path, &mut path_index);
+        }
+    }
+
+    fn detect_cycles(
+        &mut self,
+        name: &str,
+        visited: &mut HashSet<String>,
+        path: &mut Vec<(String, Spread)>,
+        path_index: &mut HashMap<String, usize>,
+    ) {
+        if !visited.insert(name.to_string()) {
+            return;
+        }
+        let direct_spreads = match self
+            .definitions
+            .get(name)
+            .and_then(|fragment| fragment.selection_set())
+        {
+            Some(selection_set) => spreads(&selection_set),
+            None => return,
+        };
+
+        path_index.insert(name.to_string(), path.len());
+        for spread in direct_spreads {
+            let target = spread.name.clone();
+            let cycle_start = path_index.get(&target).copied();
+            path.push((name.to_string(), spread));
+
+            match cycle_start {
+                None => {
+                    if self.definitions.contains_key(&target) {
+                        self.detect_cycles(&target, visited, path, path_index);
+                    }
+                }
+                Some(start) => {
+                    let cycle = &path[start..];
+                    let via: Vec<String> = cycle[..cycle.len() - 1]
+                        .iter()
+                        .map(|(_, spread)| format!("`{}`", spread.name))
+                        .collect();
+                    let message = if via.is_empty() {
+                        format!("cannot spread fragment `{}` within itself", target)
+                    } else {
+                        format!(
+                            "cannot spread fragment `{}` within itself via {}",
+                            target,
+                            via.join(", ")
+                        )
+                    };
+                    let diagnostic = cycle.iter().fold(
+                        Diagnostic::new(message),
+                        |diagnostic, (container, spread)| {
+                            diagnostic.label(
+                                spread.range.clone(),
+                                format!("`{}` spreads `{}` here", container, spread.name),
+                            )
+                        },
+                    );
+                    self.diagnostics
+                        .push(diagnostic.help("fragment spreads must not form cycles"));
+                }
+            }
+
+            path.pop();
+        }
+        path_index.remove(name);
+    }
+}
+
+/// Collect the fragment spreads directly inside a selection set, including
+/// those nested in fields and inline fragments, but not following spreads.
+fn spreads(selection_set: &ast::SelectionSet) -> Vec<Spread> {
+    let mut acc = Vec::new();
+    collect_spreads(selection_set, &mut acc);
+    acc
+}
+
+fn collect_spreads(selection_set: &ast::SelectionSet, acc: &mut Vec<Spread>) {
+    for selection in selection_set.selections() {
+        match selection {
+            ast::Selection::Field(field) => {
+                if let Some(selection_set) = field.selection_set() {
+                    collect_spreads(&selection_set, acc);
+                }
+            }
+            ast::Selection::FragmentSpread(spread) => {
+                if let Some(name) = spread.fragment_name().and_then(|n| n.name()) {
+                    acc.push(Spread {
+                        name: name.text().to_string(),
+                        range: diagnostics::range(&spread),
+                    });
+                }
+            }
+            ast::Selection::InlineFragment(inline) => {
+                if let Some(selection_set) = inline.selection_set() {
+                    collect_spreads(&selection_set, acc);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use crate::{validate, Diagnostic, Schema};
+
+    const SCHEMA: &str = r#"
+type Query {
+  pet: Pet
+  dog: Dog
+  search: [SearchResult]
+}
+
+interface Pet {
+  name: String
+}
+
+type Dog implements Pet {
+  name: String
+  barks: Boolean
+}
+
+type Cat implements Pet {
+  name: String
+  meows: Boolean
+}
+
+type Human {
+  name: String
+}
+
+union SearchResult = Dog | Human
+
+scalar Date
+"#;
+
+    fn diagnostics(input: &str) -> Vec<Diagnostic> {
+        let input = format!("{}{}", SCHEMA, input);
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        assert_eq!(schema.errors().len(), 0);
+        validate(&schema, &document)
+    }
+
+    fn messages(input: &str) -> Vec<String> {
+        diagnostics(input)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_valid_fragments() {
+        let input = "
+query {
+  pet { ...petFields ... on Dog { barks } }
+  search { ...dogFields ... on Human { name } }
+}
+fragment petFields on Pet { name ...dogFields }
+fragment dogFields on Dog { barks }
+";
+        assert_eq!(messages(input), Vec::<String>::new());
+    }
+
+    #[test]
+    fn it_reports_duplicate_and_unknown_fragments() {
+        let input = "
+query { dog { ...dogFields ...missing } }
+fragment dogFields on Dog { name }
+fragment dogFields on Dog { barks }
+";
+        assert_eq!(
+            messages(input),
+            [
+                "the fragment `dogFields` is defined multiple times",
+                "unknown fragment `missing`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_unused_fragments() {
+        let input = "
+query { dog { ...used } }
+fragment used on Dog { ...usedTransitively }
+fragment usedTransitively on Dog { name }
+fragment unused on Dog { ...alsoUnused }
+fragment alsoUnused on Dog { name }
+";
+        assert_eq!(
+            messages(input),
+            [
+                "fragment `unused` is never used",
+                "fragment `alsoUnused` is never used",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_fragment_cycles_with_their_path() {
+        let input = "
+query { dog { ...A } }
+fragment A on Dog { name ...B }
+fragment B on Dog { ...C }
+fragment C on Dog { barks ...A }
+";
+        let diagnostics = diagnostics(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "cannot spread fragment `A` within itself via `B`, `C`"
+        );
+
+        let source = format!("{}{}", SCHEMA, input);
+        let labels: Vec<_> = diagnostics[0]
+            .labels()
+            .iter()
+            .map(|label| (&source[label.range()], label.text()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("...B", "`A` spreads `B` here"),
+                ("...C", "`B` spreads `C` here"),
+                ("...A", "`C` spreads `A` here"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_a_fragment_spreading_itself() {
+        let input = "
+query { dog { ...A } }
+fragment A on Dog { ... on Dog { ...A } }
+";
+        assert_eq!(
+            messages(input),
+            ["cannot spread fragment `A` within itself"]
+        );
+    }
+
+    #[test]
+    fn it_reports_impossible_spreads() {
+        let input = "
+query {
+  dog { ...catFields ... on Human { name } ...petFields }
+  search { ... on Cat { meows } ... on Pet { name } }
+}
+fragment catFields on Cat { meows }
+fragment petFields on Pet { name }
+";
+        assert_eq!(
+            messages(input),
+            [
+                "fragment `catFields` cannot be spread here as objects of type `Dog` can never be of type `Cat`",
+                "fragment cannot be spread here as objects of type `Dog` can never be of type `Human`",
+                "fragment cannot be spread here as objects of type `SearchResult` can never be of type `Cat`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_type_conditions() {
+        let input = "
+query { dog { ...dateFields ... on Unknown { name } } }
+fragment dateFields on Date { name }
+";
+        assert_eq!(
+            messages(input),
+            [
+                "unknown type `Unknown` in type condition",
+                "fragments cannot be defined on the scalar type `Date`",
+            ]
+        );
+    }
+}
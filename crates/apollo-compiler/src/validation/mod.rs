@@ -0,0 +1,67 @@
+//! Validation of GraphQL documents against a [`Schema`].
+//!
+//! Each submodule implements a group of rules from the [Validation] section
+//! of the GraphQL spec. Rules push [`Diagnostic`]s instead of stopping at the
+//! first problem, so a single call to [`validate`] reports everything it can.
+//!
+//! [Validation]: https://spec.graphql.org/October2021/#sec-Validation
+pub(crate) mod arguments;
+pub(crate) mod directives;
+mod fragments;
+mod operations;
+
+use apollo_parser::ast;
+
+use crate::{Diagnostic, Schema};
+
+/// Validate `document` against `schema`, returning all the diagnostics found.
+///
+/// The document may contain both executable and type system definitions. An
+/// empty `Vec` means the document is valid.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{validate, Schema};
+/// use apollo_parser::Parser;
+///
+/// let input = "
+/// type Query { name: String }
+///
+/// query { ...name }
+/// fragment name on Query { name }
+/// ";
+/// let document = Parser::new(input).parse().document();
+/// let schema = Schema::new(&document);
+///
+/// assert_eq!(validate(&schema, &document).len(), 0);
+/// ```
+pub fn validate(schema: &Schema, document: &ast::Document) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    fragments::validate(schema, document, &mut diagnostics);
+    directives::validate(schema, document, &mut diagnostics);
+    operations::validate(document, &mut diagnostics);
+    diagnostics
+}
+
+/// Get the name of the root operation type for an operation definition.
+pub(crate) fn root_type<'a>(
+    schema: &'a Schema,
+    operation: &ast::OperationDefinition,
+) -> Option<&'a str> {
+    match operation.operation_type() {
+        Some(ty) if ty.mutation_token().is_some() => schema.mutation_type(),
+        Some(ty) if ty.subscription_token().is_some() => schema.subscription_type(),
+        _ => schema.query_type(),
+    }
+}
+
+/// Get the output type name of `field` selected on `parent`, if both are
+/// known.
+pub(crate) fn field_type<'a>(
+    schema: &'a Schema,
+    parent: Option<&str>,
+    field: &ast::Field,
+) -> Option<&'a str> {
+    let name = field.name()?.text();
+    Some(schema.field_definition(parent?, &name)?.ty().name())
+}
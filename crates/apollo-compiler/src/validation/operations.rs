@@ -0,0 +1,173 @@
+//! See: https://spec.graphql.org/October2021/#sec-Validation.Operations
+use std::collections::{HashMap, HashSet};
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::diagnostics::{self, Diagnostic};
+
+pub(crate) fn validate(document: &ast::Document, diagnostics: &mut Vec<Diagnostic>) {
+    let fragments: HashMap<String, ast::FragmentDefinition> = document
+        .definitions()
+        .filter_map(|def| match def {
+            ast::Definition::FragmentDefinition(fragment) => {
+                let name = fragment.fragment_name()?.name()?.text().to_string();
+                Some((name, fragment))
+            }
+            _ => None,
+        })
+        .collect();
+
+    for def in document.definitions() {
+        if let ast::Definition::OperationDefinition(operation) = def {
+            let is_subscription = operation
+                .operation_type()
+                .map_or(false, |ty| ty.subscription_token().is_some());
+            if is_subscription {
+                single_root_field(&operation, &fragments, diagnostics);
+            }
+        }
+    }
+}
+
+/// Single root field: a subscription selects exactly one root field, which
+/// is not an introspection field, once its fragments are expanded.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Single-root-field
+fn single_root_field(
+    operation: &ast::OperationDefinition,
+    fragments: &HashMap<String, ast::FragmentDefinition>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let subscription = match operation.name() {
+        Some(name) => format!("the subscription `{}`", name.text()),
+        None => "an anonymous subscription".to_string(),
+    };
+    let mut grouped = IndexMap::new();
+    if let Some(selection_set) = operation.selection_set() {
+        collect_fields(&selection_set, fragments, &mut grouped, &mut HashSet::new());
+    }
+
+    if grouped.len() > 1 {
+        let mut diagnostic =
+            Diagnostic::new(format!("{} must select only one root field", subscription));
+        for field in grouped.values().skip(1) {
+            diagnostic = diagnostic.label(diagnostics::range(field), "additional root field");
+        }
+        diagnostics
+            .push(diagnostic.help("split the subscription into one operation per root field"));
+    }
+    for field in grouped.values() {
+        let name = match field.name() {
+            Some(name) => name.text().to_string(),
+            None => continue,
+        };
+        if name.starts_with("__") {
+            diagnostics.push(
+                Diagnostic::new(format!(
+                    "{} must not select the introspection field `{}` as a root field",
+                    subscription, name
+                ))
+                .label(diagnostics::range(field), "introspection field"),
+            );
+        }
+    }
+}
+
+/// Collect the first field of each response key selected by `selection_set`,
+/// expanding fragments. Directives are ignored, since which fields they
+/// select depends on variables.
+fn collect_fields(
+    selection_set: &ast::SelectionSet,
+    fragments: &HashMap<String, ast::FragmentDefinition>,
+    grouped: &mut IndexMap<String, ast::Field>,
+    visited: &mut HashSet<String>,
+) {
+    for selection in selection_set.selections() {
+        match selection {
+            ast::Selection::Field(field) => {
+                let key = field
+                    .alias()
+                    .and_then(|alias| alias.name())
+                    .or_else(|| field.name());
+                if let Some(key) = key {
+                    grouped.entry(key.text().to_string()).or_insert(field);
+                }
+            }
+            ast::Selection::FragmentSpread(spread) => {
+                let name = match spread.fragment_name().and_then(|n| n.name()) {
+                    Some(name) => name.text().to_string(),
+                    None => continue,
+                };
+                // Cycles are reported by the fragment rules.
+                if !visited.insert(name.clone()) {
+                    continue;
+                }
+                let selection_set = fragments
+                    .get(&name)
+                    .and_then(|fragment| fragment.selection_set());
+                if let Some(selection_set) = selection_set {
+                    collect_fields(&selection_set, fragments, grouped, visited);
+                }
+            }
+            ast::Selection::InlineFragment(inline) => {
+                if let Some(selection_set) = inline.selection_set() {
+                    collect_fields(&selection_set, fragments, grouped, visited);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::Parser;
+    use pretty_assertions::assert_eq;
+
+    use crate::{validate, Schema};
+
+    const SCHEMA: &str = r#"
+type Query { name: String }
+type Subscription { newMessage: String disconnected: Boolean }
+"#;
+
+    fn messages(operation: &str) -> Vec<String> {
+        let input = format!("{}{}", SCHEMA, operation);
+        let document = Parser::new(&input).parse().document();
+        let schema = Schema::new(&document);
+        validate(&schema, &document)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_single_root_fields() {
+        let operation = r#"
+subscription A { newMessage }
+subscription B { ...Message }
+subscription C { message: newMessage ... on Subscription { message: newMessage } }
+fragment Message on Subscription { newMessage }
+query { name __typename }
+"#;
+        assert_eq!(messages(operation), Vec::<String>::new());
+    }
+
+    #[test]
+    fn it_reports_several_root_fields() {
+        let operation = r#"
+subscription A { newMessage disconnected }
+subscription { ...Message }
+subscription C { __typename }
+fragment Message on Subscription { newMessage ... on Subscription { disconnected } }
+"#;
+        assert_eq!(
+            messages(operation),
+            [
+                "the subscription `A` must select only one root field",
+                "an anonymous subscription must select only one root field",
+                "the subscription `C` must not select the introspection field `__typename` as a root field",
+            ]
+        );
+    }
+}
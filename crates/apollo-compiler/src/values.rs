@@ -0,0 +1,187 @@
+use std::fmt;
+
+use apollo_parser::{
+    ast::{self, AstNode},
+    SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use indexmap::IndexMap;
+
+/// An input value, such as an argument or a default value.
+///
+/// See: https://spec.graphql.org/October2021/#sec-Input-Values
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A variable, e.g. `$id`.
+    Variable(String),
+    /// An integer, e.g. `42`.
+    Int(i64),
+    /// A floating point number, e.g. `4.2`.
+    Float(f64),
+    /// A string, with escape sequences resolved.
+    String(String),
+    /// A boolean, `true` or `false`.
+    Boolean(bool),
+    /// `null`.
+    Null,
+    /// An enum value, e.g. `SMALL`.
+    Enum(String),
+    /// A list of values, e.g. `[1, 2]`.
+    List(Vec<Value>),
+    /// An input object, e.g. `{ size: SMALL }`.
+    Object(IndexMap<String, Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Variable(name) => write!(f, "${}", name),
+            Value::Int(int) => write!(f, "{}", int),
+            Value::Float(float) => write!(f, "{:?}", float),
+            Value::String(string) => {
+                write!(f, "\"")?;
+                for c in string.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::Boolean(boolean) => write!(f, "{}", boolean),
+            Value::Null => write!(f, "null"),
+            Value::Enum(value) => write!(f, "{}", value),
+            Value::List(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {}: {}", name, value)?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+/// Lower a syntax tree value into a [`Value`]. Returns `None` if the value is
+/// incomplete, for example an object field without a value.
+pub(crate) fn value(node: ast::Value) -> Option<Value> {
+    let value = match node {
+        ast::Value::Variable(var) => Value::Variable(var.name()?.text().to_string()),
+        ast::Value::StringValue(string) => Value::String(string_value(&string)),
+        ast::Value::FloatValue(float) => Value::Float(token(float.syntax())?.text().parse().ok()?),
+        ast::Value::IntValue(int) => {
+            let text = token(int.syntax())?;
+            // Integers too large for an `i64` are kept as floats, rather than
+            // being dropped.
+            match text.text().parse() {
+                Ok(int) => Value::Int(int),
+                Err(_) => Value::Float(text.text().parse().ok()?),
+            }
+        }
+        ast::Value::BooleanValue(boolean) => Value::Boolean(boolean.true_token().is_some()),
+        ast::Value::NullValue(_) => Value::Null,
+        ast::Value::EnumValue(value) => Value::Enum(value.name()?.text().to_string()),
+        ast::Value::ListValue(list) => {
+            Value::List(list.values().map(value).collect::<Option<_>>()?)
+        }
+        ast::Value::ObjectValue(object) => Value::Object(
+            object
+                .object_fields()
+                .map(|field| Some((field.name()?.text().to_string(), value(field.value()?)?)))
+                .collect::<Option<_>>()?,
+        ),
+    };
+    Some(value)
+}
+
+/// Get the semantic value of a `StringValue`: quotes removed, escape
+/// sequences resolved, and block strings dedented.
+pub(crate) fn string_value(node: &ast::StringValue) -> String {
+    node.clone().into()
+}
+
+/// Get the semantic value of a `Description`: quotes removed, escape
+/// sequences resolved, and block strings dedented as per the
+/// [`BlockStringValue()`] algorithm.
+///
+/// [`BlockStringValue()`]: https://spec.graphql.org/October2021/#BlockStringValue()
+pub(crate) fn description(node: Option<ast::Description>) -> Option<String> {
+    Some(node?.string_value().map(Into::into).unwrap_or_default())
+}
+
+/// Get the first token of a node that is not whitespace or a comment.
+fn token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| !matches!(token.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMENT))
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::{ast, Parser};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn first_description(input: &str) -> Option<String> {
+        let doc = Parser::new(input).parse().document();
+        match doc.definitions().next() {
+            Some(ast::Definition::ScalarTypeDefinition(scalar)) => {
+                description(scalar.description())
+            }
+            _ => panic!("expected a scalar type definition"),
+        }
+    }
+
+    #[test]
+    fn it_reads_descriptions() {
+        assert_eq!(
+            first_description(r#""A scalar" scalar S"#).unwrap(),
+            "A scalar"
+        );
+        assert_eq!(
+            first_description(
+                "\"\"\"\n    Common indentation\n      is removed.\n\n\"\"\"\nscalar S"
+            )
+            .unwrap(),
+            "Common indentation\n  is removed."
+        );
+        assert_eq!(first_description("scalar S"), None);
+    }
+
+    #[test]
+    fn it_lowers_values() {
+        let input = r#"query { a(arg: { list: [1, 2.5, "s", true, null, SMALL, $var], big: 99999999999999999999 }) }"#;
+        let doc = Parser::new(input).parse().document();
+        let value = doc
+            .syntax()
+            .descendants()
+            .find_map(ast::Argument::cast)
+            .and_then(|arg| arg.value())
+            .map(value)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            r#"{ list: [1, 2.5, "s", true, null, SMALL, $var], big: 1e20 }"#
+        );
+    }
+}
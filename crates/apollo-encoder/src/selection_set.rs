@@ -159,6 +159,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_encodes_aliases_in_selection_set() {
+        let mut aliased = Field::new("name".to_string());
+        aliased.alias(Some("displayName".to_string()));
+        let mut field = Field::new("me".to_string());
+        field.alias(Some("user".to_string()));
+        field.selection_set(Some(SelectionSet::with_selections(vec![Selection::Field(
+            aliased,
+        )])));
+
+        assert_eq!(
+            SelectionSet::with_selections(vec![Selection::Field(field)]).to_string(),
+            indoc! {r#"
+                {
+                  user: me {
+                    displayName: name
+                  }
+                }
+            "#}
+        )
+    }
+
     #[test]
     fn it_encodes_deeper_selection_set() {
         let fourth_field = Field::new("fourth".to_string());
@@ -35,48 +35,49 @@ impl fmt::Display for StringValue {
         match self {
             StringValue::Top { source } => {
                 if let Some(description) = source {
-                    if is_block_string_character(description) {
-                        writeln!(f, "\"\"\"\n{}\n\"\"\"", trim_double_quotes(description))?
+                    let desc = trim_double_quotes(description);
+                    if is_block_string(description, &desc) {
+                        writeln!(f, "\"\"\"\n{}\n\"\"\"", escape_block_string(&desc))?
                     } else {
-                        writeln!(f, "\"{}\"", description)?
+                        writeln!(f, "\"{}\"", escape_string(&desc))?
                     }
                 }
             }
             StringValue::Field { source } => {
                 if let Some(description) = source {
-                    if is_block_string_character(description) {
+                    let desc = trim_double_quotes(description);
+                    if is_block_string(description, &desc) {
                         write!(f, "  \"\"\"")?;
-                        let desc = trim_double_quotes(description);
-                        for line in desc.lines() {
+                        for line in escape_block_string(&desc).lines() {
                             write!(f, "\n  {}", line)?;
                         }
                         writeln!(f, "\n  \"\"\"")?;
                     } else {
-                        writeln!(f, "  \"{}\"", description)?
+                        writeln!(f, "  \"{}\"", escape_string(&desc))?
                     }
                 }
             }
             StringValue::Input { source } => {
                 if let Some(description) = source {
-                    if is_block_string_character(description) {
-                        write!(f, "\"\"\"\n{}\n\"\"\" ", trim_double_quotes(description))?
+                    let desc = trim_double_quotes(description);
+                    if is_block_string(description, &desc) {
+                        write!(f, "\"\"\"\n{}\n\"\"\" ", escape_block_string(&desc))?
                     } else {
-                        write!(f, "\"{}\" ", description)?
+                        write!(f, "\"{}\" ", escape_string(&desc))?
                     }
                 }
             }
             StringValue::Reason { source } => {
                 if let Some(description) = source {
-                    if is_block_string_character(description) {
+                    let desc = trim_double_quotes(description);
+                    if is_block_string(description, &desc) {
                         write!(f, "\n  \"\"\"")?;
-
-                        let desc = trim_double_quotes(description);
-                        for line in desc.lines() {
+                        for line in escape_block_string(&desc).lines() {
                             write!(f, "\n  {}", line)?;
                         }
                         write!(f, "\n  \"\"\"\n  ")?
                     } else {
-                        write!(f, " \"{}\"", description)?
+                        write!(f, " \"{}\"", escape_string(&desc))?
                     }
                 }
             }
@@ -85,31 +86,92 @@ impl fmt::Display for StringValue {
     }
 }
 
-#[allow(clippy::nonminimal_bool)]
 fn trim_double_quotes(description: &str) -> String {
-    let desc_len = description.len();
-    if desc_len < 2 {
-        return description.to_string();
-    }
-    if &description[..1] != "\"" || &description[desc_len - 1..] != "\"" {
-        return description.to_string();
-    }
     description
-        .chars()
-        .enumerate()
-        .filter_map(|(i, c)| {
-            if (i == 0 && c == '"') || (i == desc_len - 1 && c == '"') {
-                None
-            } else {
-                Some(c)
-            }
-        })
-        .collect()
+        .strip_prefix('"')
+        .and_then(|desc| desc.strip_suffix('"'))
+        .unwrap_or(description)
+        .to_string()
+}
+
+/// Whether the description is printed as a block string: it needs one, and the
+/// [`BlockStringValue()`] of `desc` printed between lines of its own is `desc` itself.
+///
+/// [`BlockStringValue()`]: https://spec.graphql.org/October2021/#BlockStringValue()
+fn is_block_string(description: &str, desc: &str) -> bool {
+    is_block_string_character(description) && is_printable_as_block_string(desc)
 }
 
 fn is_block_string_character(s: &str) -> bool {
     s.contains('\n') || s.contains('"') || s.contains('\r')
 }
+
+/// Whether `s` printed between lines of its own in a block string reads back as `s`: block
+/// strings cannot hold carriage returns, control characters or characters outside of the Basic
+/// Multilingual Plane, and lose their leading and trailing blank lines and their common
+/// indentation.
+pub(crate) fn is_printable_as_block_string(s: &str) -> bool {
+    let lines: Vec<&str> = s.split('\n').collect();
+
+    has_block_string_lines(s, &lines) && has_unindented_line(&lines)
+}
+
+/// Whether `s` printed right after the opening quotes of a block string reads back as `s`: its
+/// first line is not dedented, and its last character must not end the block string early.
+pub(crate) fn is_printable_as_inline_block_string(s: &str) -> bool {
+    let lines: Vec<&str> = s.split('\n').collect();
+
+    has_block_string_lines(s, &lines)
+        && (lines.len() == 1 || has_unindented_line(&lines[1..]))
+        && !s.ends_with(['"', '\\'])
+}
+
+fn has_block_string_lines(s: &str, lines: &[&str]) -> bool {
+    !s.contains(|c: char| c.is_control() && c != '\n' && c != '\t' || c > '\u{FFFF}')
+        && !lines.first().map_or(false, is_blank)
+        && !lines.last().map_or(false, is_blank)
+}
+
+fn has_unindented_line(lines: &[&str]) -> bool {
+    lines
+        .iter()
+        .any(|line| !is_blank(line) && !line.starts_with([' ', '\t']))
+}
+
+fn is_blank(line: &&str) -> bool {
+    line.trim_start_matches([' ', '\t']).is_empty()
+}
+
+/// Escape the closing quotes of a block string in `s`
+pub(crate) fn escape_block_string(s: &str) -> String {
+    s.replace(r#"""""#, r#"\""""#)
+}
+
+/// Escape `s` to print it between the double quotes of a string
+pub(crate) fn escape_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\u{0008}' => escaped.push_str("\\b"),
+            '\u{000c}' => escaped.push_str("\\f"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            // Source characters stop at U+FFFF, other characters are escaped as surrogate pairs
+            c if c > '\u{FFFF}' => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -177,7 +239,7 @@ plant corner, pile of clothes.
         assert_eq!(
             desc.to_string(),
             String::from(
-                "\"\"\"\nFavourite cat nap spots include:\rplant corner,\rpile of clothes.\n\"\"\"\n"
+                "\"Favourite cat nap spots include:\\rplant corner,\\rpile of clothes.\"\n"
             )
         );
     }
@@ -193,7 +255,7 @@ plant corner, pile of clothes.
         assert_eq!(
             desc.to_string(),
             String::from(
-                "  \"\"\"\n  Favourite cat nap spots include:\r  plant corner,\r  pile of clothes.\n  \"\"\"\n"
+                "  \"Favourite cat nap spots include:\\r  plant corner,\\r  pile of clothes.\"\n"
             )
         );
     }
@@ -206,7 +268,43 @@ plant corner, pile of clothes.
 
         assert_eq!(
             desc.to_string(),
-            String::from("\n  \"\"\"\n  One of my cat is called:\r \"Mozart\"\n  \"\"\"\n  ")
+            String::from(" \"One of my cat is called:\\r \\\"Mozart\\\"\"")
+        );
+    }
+
+    #[test]
+    fn it_encodes_description_with_block_string_quotes() {
+        let desc = StringValue::Field {
+            source: Some("Cats say:\n\"\"\"meow\"\"\"".to_string()),
+        };
+
+        assert_eq!(
+            desc.to_string(),
+            String::from("  \"\"\"\n  Cats say:\n  \\\"\"\"meow\\\"\"\"\n  \"\"\"\n")
+        );
+    }
+
+    #[test]
+    fn it_encodes_description_with_supplementary_characters() {
+        let desc = StringValue::Top {
+            source: Some("Cats say:\n\u{1F431} meow".to_string()),
+        };
+
+        assert_eq!(
+            desc.to_string(),
+            String::from("\"Cats say:\\n\\uD83D\\uDC31 meow\"\n")
+        );
+    }
+
+    #[test]
+    fn it_encodes_description_with_common_indentation_as_string() {
+        let desc = StringValue::Top {
+            source: Some("  Favourite cat nap spots include:\n  plant corner.".to_string()),
+        };
+
+        assert_eq!(
+            desc.to_string(),
+            String::from("\"  Favourite cat nap spots include:\\n  plant corner.\"\n")
         );
     }
 }
@@ -1,5 +1,9 @@
 use std::fmt;
 
+use crate::string_value::{
+    escape_block_string, escape_string, is_printable_as_inline_block_string,
+};
+
 /// The Value type represents available values you could give as an input.
 ///
 /// *Value*:
@@ -34,12 +38,12 @@ impl fmt::Display for Value {
         match self {
             Self::Variable(v) => write!(f, "${v}"),
             Self::Int(i) => write!(f, "{i}"),
-            Self::Float(fl) => write!(f, "{fl}"),
+            Self::Float(fl) => write!(f, "{fl:?}"),
             Self::String(s) => {
-                if s.contains('"') | s.contains('\n') | s.contains('\r') {
-                    write!(f, r#""""{s}""""#)
+                if (s.contains('"') | s.contains('\n')) && is_printable_as_inline_block_string(s) {
+                    write!(f, r#""""{}""""#, escape_block_string(s))
                 } else {
-                    write!(f, r#""{s}""#)
+                    write!(f, r#""{}""#, escape_string(s))
                 }
             }
             Self::Boolean(b) => write!(f, "{b}"),
@@ -26,12 +26,22 @@ impl ast::EnumValue {
     }
 }
 
+/// The string value, as per the [`StringValue`] algorithm: quotes removed, escape sequences
+/// resolved, and block strings dedented.
+///
+/// [`StringValue`]: https://spec.graphql.org/October2021/#sec-String-Value.Semantics
 impl Into<String> for ast::StringValue {
     fn into(self) -> String {
         let text = text_of_first_token(self.syntax());
-        text.trim_start_matches('"')
-            .trim_end_matches('"')
-            .to_string()
+        if let Some(raw) = text
+            .strip_prefix("\"\"\"")
+            .map(|s| s.strip_suffix("\"\"\"").unwrap_or(s))
+        {
+            block_string_value(&raw.replace("\\\"\"\"", "\"\"\""))
+        } else {
+            let raw = text.strip_prefix('"').unwrap_or(&text);
+            unescape(raw.strip_suffix('"').unwrap_or(raw))
+        }
     }
 }
 
@@ -67,3 +77,127 @@ fn text_of_first_token(node: &SyntaxNode) -> TokenText {
 
     TokenText(first_token)
 }
+
+fn block_string_value(raw: &str) -> String {
+    let lines: Vec<&str> = raw
+        .split("\r\n")
+        .flat_map(|l| l.split(['\n', '\r']))
+        .collect();
+
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+            (indent < line.len()).then(|| indent)
+        })
+        .min()
+        .unwrap_or(0);
+
+    let mut lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                line.get(common_indent..).unwrap_or("")
+            }
+        })
+        .collect();
+
+    let is_blank = |line: &&str| line.trim_start_matches([' ', '\t']).is_empty();
+    while lines.first().map_or(false, is_blank) {
+        lines.remove(0);
+    }
+    while lines.last().map_or(false, is_blank) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+fn unescape(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => value.push('"'),
+            Some('\\') => value.push('\\'),
+            Some('/') => value.push('/'),
+            Some('b') => value.push('\u{0008}'),
+            Some('f') => value.push('\u{000c}'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('u') => {
+                let code = hex_code_point(&mut chars);
+                match code {
+                    // A leading surrogate must be followed by an escaped
+                    // trailing surrogate to form a single code point.
+                    Some(lead @ 0xD800..=0xDBFF) => {
+                        let mut lookahead = chars.clone();
+                        let trail = match (lookahead.next(), lookahead.next()) {
+                            (Some('\\'), Some('u')) => hex_code_point(&mut lookahead),
+                            _ => None,
+                        };
+                        match trail {
+                            Some(trail @ 0xDC00..=0xDFFF) => {
+                                chars = lookahead;
+                                let code = 0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00);
+                                value.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                            }
+                            _ => value.push('\u{FFFD}'),
+                        }
+                    }
+                    Some(code) => value.push(char::from_u32(code).unwrap_or('\u{FFFD}')),
+                    None => value.push('\u{FFFD}'),
+                }
+            }
+            Some(other) => {
+                value.push('\\');
+                value.push(other);
+            }
+            None => value.push('\\'),
+        }
+    }
+
+    value
+}
+
+fn hex_code_point(chars: &mut std::str::Chars<'_>) -> Option<u32> {
+    let digits: String = chars.take(4).collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    u32::from_str_radix(&digits, 16).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_unescapes_string_values() {
+        assert_eq!(
+            unescape(r#"caf\u00e9 \"quoted\" \\ \uD83D\uDE00 \uD83D"#),
+            "café \"quoted\" \\ 😀 \u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn it_dedents_block_strings() {
+        let raw = "\r\n    Common indentation\r\n      is removed.\n\n    Lines are kept.\n  \n";
+        assert_eq!(
+            block_string_value(raw),
+            "Common indentation\n  is removed.\n\nLines are kept."
+        );
+    }
+}
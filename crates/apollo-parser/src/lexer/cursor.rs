@@ -34,6 +34,11 @@ impl<'a> Cursor<'a> {
         self.nth_char(1)
     }
 
+    /// Peeks the third char in input without consuming.
+    pub(crate) fn third(&self) -> char {
+        self.nth_char(2)
+    }
+
     /// Checks if there are chars to consume.
     pub(crate) fn is_eof(&self) -> bool {
         self.chars.as_str().is_empty()
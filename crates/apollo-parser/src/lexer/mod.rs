@@ -135,12 +135,18 @@ impl Cursor<'_> {
             '"' => self.block_string_value(buf, c),
             t => {
                 buf.push(t);
+                if t == '\\' {
+                    self.escaped_char(&mut buf);
+                }
 
                 while !self.is_eof() {
                     let c = self.bump().unwrap();
                     if c == '"' {
                         buf.push(c);
                         break;
+                    } else if c == '\\' {
+                        buf.push(c);
+                        self.escaped_char(&mut buf);
                     } else if is_escaped_char(c)
                         || is_source_char(c) && c != '\\' && c != '"' && !is_line_terminator(c)
                     {
@@ -156,6 +162,16 @@ impl Cursor<'_> {
         }
     }
 
+    /// Push the character following a backslash in a string value, which is part of the string
+    /// even if it is a quote
+    fn escaped_char(&mut self, buf: &mut String) {
+        let escaped = self.first();
+        if is_source_char(escaped) && !is_line_terminator(escaped) {
+            buf.push(escaped);
+            self.bump();
+        }
+    }
+
     fn block_string_value(&mut self, mut buf: String, char: char) -> Result<Token, Error> {
         buf.push(char); // the second " we already matched on
 
@@ -180,6 +196,15 @@ impl Cursor<'_> {
                         self.bump();
                         break;
                     }
+                } else if c == '\\'
+                    && ('"', '"', '"') == (self.first(), self.second(), self.third())
+                {
+                    // An escaped triple quote does not end the block string
+                    buf.push(c);
+                    for _ in 0..3 {
+                        buf.push('"');
+                        self.bump();
+                    }
                 } else if is_source_char(c) {
                     buf.push(c);
                 } else {
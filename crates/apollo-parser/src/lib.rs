@@ -8,11 +8,10 @@ pub mod ast;
 mod error;
 mod parser;
 
+pub use crate::error::Error;
 pub use crate::lexer::Lexer;
 pub use crate::lexer::{Token, TokenKind};
-pub(crate) use crate::parser::{
-    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeChildren, SyntaxToken, TokenText,
+pub use crate::parser::{
+    GraphQLLanguage, Parser, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeChildren,
+    SyntaxToken, SyntaxTree, TokenText,
 };
-
-pub use crate::error::Error;
-pub use crate::parser::{Parser, SyntaxTree};
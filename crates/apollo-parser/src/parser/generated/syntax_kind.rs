@@ -76,6 +76,7 @@ pub enum SyntaxKind {
     IDENT,
     WHITESPACE,
     COMMENT,
+    ERROR,
     NAME,
     INTEGER_PART,
     NEGATIVE_SIGN,
@@ -222,7 +223,9 @@ impl SyntaxKind {
                 | COLON
         )
     }
-    pub fn is_literal(self) -> bool { matches!(self, INT | FLOAT | STRING) }
+    pub fn is_literal(self) -> bool {
+        matches!(self, INT | FLOAT | STRING)
+    }
     pub fn from_keyword(ident: &str) -> Option<SyntaxKind> {
         let kw = match ident {
             "query" => query_KW,
@@ -42,8 +42,50 @@ pub(crate) fn arguments(p: &mut Parser) {
 /// *ArgumentsDefinition*:
 ///     **(** InputValueDefinition* **)**
 pub(crate) fn arguments_definition(p: &mut Parser) {
-    let _g = p.start_node(SyntaxKind::ARGUMENTS);
+    let _g = p.start_node(SyntaxKind::ARGUMENTS_DEFINITION);
     p.bump(S!['(']);
     input::input_value_definition(p, false);
     p.expect(T![')'], S![')']);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ast, Parser};
+
+    #[test]
+    fn it_accesses_arguments_definitions() {
+        let gql = r#"
+type Query {
+    user(id: ID!, name: String): User
+}
+
+directive @cache(maxAge: Int) on FIELD_DEFINITION
+        "#;
+
+        let parser = Parser::new(gql);
+        let ast = parser.parse();
+
+        assert!(ast.errors().len() == 0);
+
+        let doc = ast.document();
+
+        let mut names = Vec::new();
+        for definition in doc.definitions() {
+            let arguments_definition = match definition {
+                ast::Definition::ObjectTypeDefinition(obj_def) => obj_def
+                    .fields_definition()
+                    .unwrap()
+                    .field_definitions()
+                    .next()
+                    .unwrap()
+                    .arguments_definition(),
+                ast::Definition::DirectiveDefinition(dir_def) => dir_def.arguments_definition(),
+                _ => None,
+            };
+            for input_value in arguments_definition.unwrap().input_value_definitions() {
+                names.push(input_value.name().unwrap().text().to_string());
+            }
+        }
+        assert_eq!(names, ["id", "name", "maxAge"]);
+    }
+}
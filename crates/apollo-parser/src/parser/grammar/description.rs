@@ -6,5 +6,6 @@ use crate::{Parser, SyntaxKind};
 ///     StringValue
 pub(crate) fn description(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::DESCRIPTION);
-    p.bump(SyntaxKind::STRING_VALUE)
+    let _string_g = p.start_node(SyntaxKind::STRING_VALUE);
+    p.bump(SyntaxKind::STRING);
 }
@@ -88,7 +88,7 @@ pub(crate) fn directive_locations(p: &mut Parser, is_location: bool) {
             }
             "FRAGMENT_SPREAD" => {
                 let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::FRAGMENT_DEFINITION_KW);
+                p.bump(SyntaxKind::FRAGMENT_SPREAD_KW);
             }
             "INLINE_FRAGMENT" => {
                 let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
@@ -183,3 +183,43 @@ pub(crate) fn directives(p: &mut Parser) {
         directive(p);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ast, Parser};
+
+    #[test]
+    fn it_accesses_repeatable_and_locations() {
+        let gql = r#"
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | FRAGMENT_SPREAD
+directive @once on FIELD
+        "#;
+
+        let parser = Parser::new(gql);
+        let ast = parser.parse();
+
+        assert!(ast.errors().len() == 0);
+
+        let doc = ast.document();
+        let mut definitions = doc.definitions();
+
+        if let Some(ast::Definition::DirectiveDefinition(tag)) = definitions.next() {
+            assert!(tag.repeatable_token().is_some());
+            let locations: Vec<_> = tag
+                .directive_locations()
+                .unwrap()
+                .directive_locations()
+                .collect();
+            assert!(locations[0].field_definition_token().is_some());
+            assert!(locations[1].fragment_spread_token().is_some());
+        } else {
+            panic!("expected a directive definition");
+        }
+
+        if let Some(ast::Definition::DirectiveDefinition(once)) = definitions.next() {
+            assert!(once.repeatable_token().is_none());
+        } else {
+            panic!("expected a directive definition");
+        }
+    }
+}
@@ -27,9 +27,13 @@ pub(crate) fn document(p: &mut Parser) {
                 let def = p.peek_data().unwrap();
                 select_definition(def, p);
             }
-            _ => break,
+            TokenKind::Eof => break,
+            _ => p.err_and_pop("expected definition"),
         }
     }
+    // Keep the text of the lexer errors at the end of the input, the tree is
+    // lossless.
+    p.push_lexer_errors(usize::MAX);
 
     doc.finish_node();
 }
@@ -28,23 +28,26 @@ pub(crate) fn selection(p: &mut Parser) {
     while let Some(node) = p.peek() {
         match node {
             T![...] => {
-                let next_token = p.peek_token_n(2);
-                match next_token {
-                    Some(next_token) => {
-                        if next_token.kind() == TokenKind::Name && next_token.data() != "on" {
-                            fragment::fragment_spread(p);
-                        } else if matches!(
+                match p.peek_token_n(2) {
+                    Some(next_token)
+                        if next_token.kind() == TokenKind::Name && next_token.data() != "on" =>
+                    {
+                        fragment::fragment_spread(p)
+                    }
+                    Some(next_token)
+                        if matches!(
                             next_token.kind(),
                             TokenKind::At | TokenKind::Name | TokenKind::LCurly
-                        ) {
-                            fragment::inline_fragment(p);
-                        } else {
-                            p.err("expected an Inline Fragment or a Fragment Spread");
-                        }
-                        has_selection = true;
+                        ) =>
+                    {
+                        fragment::inline_fragment(p)
+                    }
+                    _ => {
+                        p.err_and_pop("expected an Inline Fragment or a Fragment Spread");
+                        continue;
                     }
-                    None => p.err("expected an Inline Fragment or a Fragment Spread"),
                 }
+                has_selection = true;
             }
             T!['{'] => {
                 break;
@@ -1,4 +1,4 @@
-use crate::{parser::grammar::name, Parser, SyntaxKind, Token, TokenKind, S, T};
+use crate::{parser::grammar::name, Parser, SyntaxKind, TokenKind, S, T};
 
 /// See: https://spec.graphql.org/October2021/#InputValueDefinition
 ///
@@ -15,157 +15,47 @@ use crate::{parser::grammar::name, Parser, SyntaxKind, Token, TokenKind, S, T};
 // rules in this library. The parent node type is determined based on what its
 // last possible NonNullType.
 //
-// To make this work, we first collect all types in a double ended queue, and
-// unwrap them once the last possible child has been parsed. Nodes are then
-// created in the processing stage of this parsing rule.
+// To make this work, we take a checkpoint before parsing a type, and wrap the
+// type in a NonNullType node at that checkpoint once we see a **!**.
 pub(crate) fn ty(p: &mut Parser) {
-    let ty = parse(p);
-    process(ty, p);
-}
-
-#[derive(Debug)]
-enum TokenTy {
-    List {
-        nullable: Option<Token>,
-        open_token: Token,
-        close_token: Option<Token>,
-        inner: Box<TokenTy>,
-        comma: Option<Token>,
-        trailing_ws: Option<Token>,
-    },
-    Named {
-        nullable: Option<Token>,
-        token: Token,
-        comma: Option<Token>,
-        trailing_ws: Option<Token>,
-    },
-}
-
-fn parse(p: &mut Parser) -> TokenTy {
-    let token = p.pop();
-    let mut types = match token.kind() {
-        T!['['] => {
-            let inner = parse(p);
-            let close_token = if let Some(T![']']) = p.peek() {
-                Some(p.pop())
+    let checkpoint = p.checkpoint_node();
+    match p.peek() {
+        Some(T!['[']) => {
+            let _list_g = p.start_node(SyntaxKind::LIST_TYPE);
+            p.bump(S!['[']);
+            match p.peek() {
+                Some(TokenKind::Name | T!['[']) => ty(p),
+                _ => p.err("expected a Type"),
+            }
+            if let Some(T![']']) = p.peek() {
+                // ignored tokens after the closing bracket are not part of the
+                // list.
+                p.eat(S![']']);
             } else {
-                None
-            };
-
-            TokenTy::List {
-                inner: Box::new(inner),
-                open_token: token,
-                close_token,
-                nullable: None,
-                comma: None,
-                trailing_ws: None,
+                p.err("expected ]");
             }
         }
-        TokenKind::Name => TokenTy::Named {
-            token,
-            nullable: None,
-            comma: None,
-            trailing_ws: None,
-        },
-        // TODO(@lrlna): this should not panic
-        token => panic!("unexpected token, {:?}", token),
-    };
-
-    // Deal with nullable types
-    if let Some(T![!]) = p.peek() {
-        match &mut types {
-            TokenTy::List { nullable, .. } => nullable.replace(p.pop()),
-            TokenTy::Named { nullable, .. } => nullable.replace(p.pop()),
-        };
-    }
-
-    // deal with ignored tokens
-    if let Some(T![,]) = p.peek() {
-        match &mut types {
-            TokenTy::List { comma, .. } => comma.replace(p.pop()),
-            TokenTy::Named { comma, .. } => comma.replace(p.pop()),
-        };
-    }
-
-    if let Some(TokenKind::Whitespace) = p.peek() {
-        match &mut types {
-            TokenTy::List { trailing_ws, .. } => trailing_ws.replace(p.pop()),
-            TokenTy::Named { trailing_ws, .. } => trailing_ws.replace(p.pop()),
-        };
-    }
-
-    types
-}
-
-fn process(ty: TokenTy, p: &mut Parser) {
-    match ty {
-        TokenTy::List {
-            nullable,
-            open_token,
-            close_token,
-            inner,
-            comma,
-            trailing_ws,
-        } => match nullable {
-            Some(nullable_token) => {
-                let _non_null_g = p.start_node(SyntaxKind::NON_NULL_TYPE);
-                process_list(p, open_token, *inner, close_token);
-                p.push_ast(S![!], nullable_token);
-                process_ignored_tokens(comma, p, trailing_ws);
-            }
-            None => {
-                process_list(p, open_token, *inner, close_token);
-                process_ignored_tokens(comma, p, trailing_ws);
-            }
-        },
-        TokenTy::Named {
-            nullable,
-            token,
-            comma,
-            trailing_ws,
-        } => match nullable {
-            Some(nullable_token) => {
-                let _non_null_g = p.start_node(SyntaxKind::NON_NULL_TYPE);
-                process_named(p, token);
-
-                p.push_ast(S![!], nullable_token);
-                process_ignored_tokens(comma, p, trailing_ws);
-            }
-            None => {
-                process_named(p, token);
-                process_ignored_tokens(comma, p, trailing_ws);
-            }
-        },
-    }
-}
-
-fn process_ignored_tokens(comma: Option<Token>, p: &mut Parser, whitespace: Option<Token>) {
-    if let Some(comma_token) = comma {
-        p.push_ast(SyntaxKind::COMMA, comma_token);
-    }
-    if let Some(ws_token) = whitespace {
-        p.push_ast(SyntaxKind::WHITESPACE, ws_token);
+        Some(TokenKind::Name) => {
+            let named_g = p.start_node(SyntaxKind::NAMED_TYPE);
+            let name_g = p.start_node(SyntaxKind::NAME);
+            name::validate_name(p.peek_data().unwrap(), p);
+            p.eat(SyntaxKind::IDENT);
+            name_g.finish_node();
+            named_g.finish_node();
+        }
+        _ => p.err("expected a Type"),
     }
-}
 
-fn process_list(p: &mut Parser, open_token: Token, inner: TokenTy, close_token: Option<Token>) {
-    let _list_g = p.start_node(SyntaxKind::LIST_TYPE);
-    p.push_ast(S!['['], open_token);
-    process(inner, p);
-    if let Some(close_token) = close_token {
-        p.push_ast(S![']'], close_token);
+    // Deal with nullable types, the ignored tokens that follow are part of the
+    // NonNullType.
+    if let Some(T![!]) = p.peek() {
+        let _non_null_g = p.start_node_at(checkpoint, SyntaxKind::NON_NULL_TYPE);
+        p.bump(S![!]);
+    } else {
+        p.bump_ignored();
     }
 }
 
-fn process_named(p: &mut Parser, token: Token) {
-    let named_g = p.start_node(SyntaxKind::NAMED_TYPE);
-    let name_g = p.start_node(SyntaxKind::NAME);
-    name::validate_name(token.data().to_string(), p);
-    p.push_ast(SyntaxKind::IDENT, token);
-    name_g.finish_node();
-    named_g.finish_node();
-}
-
 /// See: https://spec.graphql.org/October2021/#NamedType
 ///
 /// *NamedType*:
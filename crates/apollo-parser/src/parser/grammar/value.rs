@@ -78,11 +78,22 @@ pub(crate) fn list_value(p: &mut Parser) {
     p.bump(S!['[']);
 
     while let Some(node) = p.peek() {
-        if node == T![']'] {
-            p.bump(S![']']);
-            break;
-        } else {
-            value(p);
+        match node {
+            T![']'] => {
+                p.bump(S![']']);
+                break;
+            }
+            T![$]
+            | TokenKind::Int
+            | TokenKind::Float
+            | TokenKind::StringValue
+            | TokenKind::Name
+            | T!['[']
+            | T!['{'] => value(p),
+            _ => {
+                p.err("expected ]");
+                break;
+            }
         }
     }
 }
@@ -10,12 +10,11 @@ use std::{cell::RefCell, rc::Rc};
 use crate::{lexer::Lexer, Error, Token, TokenKind};
 
 pub use generated::syntax_kind::SyntaxKind;
-pub use language::{SyntaxElement, SyntaxNodeChildren, SyntaxToken};
+pub use language::{GraphQLLanguage, SyntaxElement, SyntaxNode, SyntaxNodeChildren, SyntaxToken};
 pub use syntax_tree::SyntaxTree;
+pub use token_text::TokenText;
 
-pub(crate) use language::{GraphQLLanguage, SyntaxNode};
 pub(crate) use syntax_tree::SyntaxTreeBuilder;
-pub(crate) use token_text::TokenText;
 
 /// Parse GraphQL schemas or queries into a typed AST.
 ///
@@ -77,6 +76,8 @@ pub struct Parser {
     builder: Rc<RefCell<SyntaxTreeBuilder>>,
     /// The list of syntax errors we've accumulated so far.
     errors: Vec<crate::Error>,
+    /// Lexer errors whose text is not in the AST yet, in *reverse* order.
+    lexer_errors: Vec<crate::Error>,
 }
 
 impl Parser {
@@ -97,11 +98,13 @@ impl Parser {
 
         tokens.reverse();
         errors.reverse();
+        let lexer_errors = errors.clone();
 
         Self {
             tokens,
             builder: Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
             errors,
+            lexer_errors,
         }
     }
 
@@ -161,7 +164,7 @@ impl Parser {
             .tokens
             .pop()
             .expect("Could not eat a token from the AST");
-        self.builder.borrow_mut().token(kind, token.data());
+        self.push_ast(kind, token);
     }
 
     /// Create a parser error and push it into the error vector.
@@ -175,6 +178,11 @@ impl Parser {
     /// Create a parser error and push it into the error vector.
     pub(crate) fn err_and_pop(&mut self, message: &str) {
         let current = self.pop();
+        // keep the text of the erroneous token in the AST, the EOF token has
+        // no text in the input.
+        if current.kind() != TokenKind::Eof {
+            self.push_ast(SyntaxKind::ERROR, current.clone());
+        }
         // we usually bump ignored after we pop a token, so make sure we also do
         // this when we create an error and pop.
         self.bump_ignored();
@@ -217,9 +225,24 @@ impl Parser {
 
     /// Insert a token into the AST.
     pub(crate) fn push_ast(&mut self, kind: SyntaxKind, token: Token) {
+        self.push_lexer_errors(token.index());
         self.builder.borrow_mut().token(kind, token.data())
     }
 
+    /// Insert the text of the lexer errors found before `index` into the AST.
+    pub(crate) fn push_lexer_errors(&mut self, index: usize) {
+        while self
+            .lexer_errors
+            .last()
+            .map_or(false, |err| err.index() < index)
+        {
+            let err = self.lexer_errors.pop().unwrap();
+            self.builder
+                .borrow_mut()
+                .token(SyntaxKind::ERROR, err.data());
+        }
+    }
+
     /// Start a node and make it current.
     ///
     /// This also creates a NodeGuard under the hood that will automatically
@@ -234,6 +257,25 @@ impl Parser {
         guard
     }
 
+    /// Prepare for a node that may be started later at the current position,
+    /// e.g. when its kind depends on the tokens that follow.
+    pub(crate) fn checkpoint_node(&mut self) -> rowan::Checkpoint {
+        self.builder.borrow().checkpoint()
+    }
+
+    /// Start a node at `checkpoint`, wrapping the nodes and tokens added
+    /// since, and make it current.
+    ///
+    /// Like `start_node`, the node is closed when its guard goes out of scope.
+    pub(crate) fn start_node_at(
+        &mut self,
+        checkpoint: rowan::Checkpoint,
+        kind: SyntaxKind,
+    ) -> NodeGuard {
+        self.builder.borrow_mut().start_node_at(checkpoint, kind);
+        NodeGuard::new(self.builder.clone())
+    }
+
     /// Peek the next Token and return its TokenKind.
     pub(crate) fn peek(&self) -> Option<TokenKind> {
         self.tokens.last().map(|token| token.kind())
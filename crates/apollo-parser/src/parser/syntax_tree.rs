@@ -1,6 +1,6 @@
 use std::{fmt, slice::Iter};
 
-use rowan::GreenNodeBuilder;
+use rowan::{Checkpoint, GreenNodeBuilder};
 
 use crate::{ast::Document, Error, SyntaxElement, SyntaxKind};
 
@@ -111,6 +111,18 @@ impl SyntaxTreeBuilder {
         self.builder.start_node(rowan::SyntaxKind(kind as u16));
     }
 
+    /// Prepare for a node that may be started later at the current position.
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        self.builder.checkpoint()
+    }
+
+    /// Start new node at `checkpoint`, wrapping the nodes and tokens added
+    /// since, and make it current.
+    pub(crate) fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.builder
+            .start_node_at(checkpoint, rowan::SyntaxKind(kind as u16));
+    }
+
     /// Finish current branch and restore previous branch as current.
     pub(crate) fn finish_node(&mut self) {
         self.builder.finish_node();
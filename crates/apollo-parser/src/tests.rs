@@ -10,8 +10,9 @@ use std::{
 };
 
 use expect_test::expect_file;
+use proptest::prelude::*;
 
-use crate::{Error, Lexer, Parser, Token};
+use crate::{ast::AstNode, Error, Lexer, Parser, Token};
 
 // To run these tests and update files:
 // ```bash
@@ -53,6 +54,99 @@ fn parser_tests() {
     });
 }
 
+/// Tokens of GraphQL documents, valid or not, to generate inputs the parser
+/// gets further into than arbitrary strings.
+const TOKENS: &[&str] = &[
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "on",
+    "schema",
+    "scalar",
+    "type",
+    "interface",
+    "implements",
+    "union",
+    "enum",
+    "input",
+    "directive",
+    "repeatable",
+    "extend",
+    "true",
+    "false",
+    "null",
+    "FIELD",
+    "QUERY",
+    "Pet",
+    "_name",
+    "$",
+    "!",
+    "&",
+    "(",
+    ")",
+    "...",
+    "..",
+    ":",
+    "=",
+    "@",
+    "[",
+    "]",
+    "{",
+    "}",
+    "|",
+    ",",
+    " ",
+    "\n",
+    "\r\n",
+    "\t",
+    "# comment\n",
+    "\"",
+    "\"string\"",
+    "\"\"\"",
+    "\"\"\"block\"\"\"",
+    "\\",
+    "0",
+    "-1",
+    "1.5e3",
+    "1.",
+    "0x",
+    "%",
+    "\u{feff}",
+    "é",
+];
+
+fn graphql_tokens() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(TOKENS), 0..64).prop_map(|tokens| tokens.concat())
+}
+
+/// The text of the syntax tree is the input, the errors point into the input,
+/// and the parser does not panic.
+fn assert_lossless(input: &str) {
+    let ast = Parser::new(input).parse();
+    for err in ast.errors() {
+        assert!(
+            err.index() <= input.len() && input.is_char_boundary(err.index()),
+            "error {:?} is not within the input {:?}",
+            err,
+            input
+        );
+    }
+    assert_eq!(ast.document().syntax().to_string(), input);
+}
+
+proptest! {
+    #[test]
+    fn parser_is_lossless_on_arbitrary_strings(input in any::<String>()) {
+        assert_lossless(&input);
+    }
+
+    #[test]
+    fn parser_is_lossless_on_graphql_tokens(input in graphql_tokens()) {
+        assert_lossless(&input);
+    }
+}
+
 fn assert_errors_are_present(errors: Iter<'_, Error>, path: &Path) {
     assert!(
         errors.len() != 0,
@@ -30,6 +30,20 @@ impl From<ArgumentsDef> for apollo_encoder::ArgumentsDefinition {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::ArgumentsDefinition> for ArgumentsDef {
+    type Error = crate::FromError;
+
+    fn try_from(args_def: apollo_parser::ast::ArgumentsDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            input_value_definitions: args_def
+                .input_value_definitions()
+                .map(InputValueDef::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 /// The `__Argument` type represents an argument
 ///
 /// *Argument*:
@@ -48,10 +62,25 @@ impl From<Argument> for apollo_encoder::Argument {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Argument> for Argument {
+    type Error = crate::FromError;
+
+    fn try_from(arg: apollo_parser::ast::Argument) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: arg.name().try_into()?,
+            value: arg
+                .value()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary vector of `Argument`
     pub fn arguments(&mut self) -> Result<Vec<Argument>> {
-        let num_arguments = self.u.int_in_range(0..=4)?;
+        let num_arguments = self.u.int_in_range(self.config.argument_count())?;
         let arguments = (0..num_arguments)
             .map(|_| self.argument())
             .collect::<Result<Vec<_>>>()?;
@@ -67,10 +96,37 @@ impl<'a> DocumentBuilder<'a> {
         Ok(Argument { name, value })
     }
 
+    /// Create arbitrary `Argument`s with values of the right types for `arguments_def`.
+    /// Required arguments are always given, optional ones only sometimes.
+    pub fn arguments_with_def(&mut self, arguments_def: &ArgumentsDef) -> Result<Vec<Argument>> {
+        let mut arguments = Vec::new();
+        for input_value_def in &arguments_def.input_value_definitions {
+            let is_required =
+                input_value_def.ty.is_non_null() && input_value_def.default_value.is_none();
+            if is_required || self.u.arbitrary().unwrap_or(false) {
+                arguments.push(Argument {
+                    name: input_value_def.name.clone(),
+                    value: self.input_value_for_type(&input_value_def.ty)?,
+                });
+            }
+        }
+
+        Ok(arguments)
+    }
+
     /// Create an arbitrary `ArgumentsDef`
     pub fn arguments_definition(&mut self) -> Result<ArgumentsDef> {
         Ok(ArgumentsDef {
-            input_value_definitions: self.input_values_def()?,
+            input_value_definitions: self
+                .input_values_def_in_range(self.config.arguments_definition_count())?,
+        })
+    }
+
+    /// Create an arbitrary `ArgumentsDef` of existing input types for a valid schema
+    pub(crate) fn valid_arguments_definition(&mut self) -> Result<ArgumentsDef> {
+        Ok(ArgumentsDef {
+            input_value_definitions: self
+                .valid_input_values_def(self.config.arguments_definition_count())?,
         })
     }
 }
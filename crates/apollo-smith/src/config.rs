@@ -0,0 +1,374 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+/// Number of definitions of each kind generated when no count is configured
+const DEFAULT_DEFINITION_COUNT: RangeInclusive<usize> = 1..=50;
+/// Maximum length of the generated names when none is configured
+const DEFAULT_MAX_NAME_LENGTH: usize = 30;
+/// Maximum length of the generated descriptions when none is configured
+const DEFAULT_MAX_DESCRIPTION_LENGTH: usize = 100;
+/// Maximum length of the generated string values when none is configured
+const DEFAULT_MAX_STRING_VALUE_LENGTH: usize = 40;
+/// Weight of the plain ASCII characters of descriptions and string values, which the weights of
+/// the edge cases are relative to
+pub(crate) const PLAIN_CHARACTER_WEIGHT: u32 = 100;
+
+/// A kind of definition generated by a `DocumentBuilder`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefinitionKind {
+    /// Fragment definitions
+    Fragment,
+    /// Scalar type definitions
+    Scalar,
+    /// Enum type definitions
+    Enum,
+    /// Interface type definitions
+    Interface,
+    /// Object type definitions
+    Object,
+    /// Union type definitions
+    Union,
+    /// Input object type definitions
+    InputObject,
+    /// Schema definitions
+    Schema,
+    /// Directive definitions
+    Directive,
+    /// Operation definitions
+    Operation,
+}
+
+impl DefinitionKind {
+    /// Every kind of definition
+    ///
+    /// ```rust
+    /// use apollo_smith::{Config, DefinitionKind};
+    ///
+    /// // Up to 3 definitions of every kind
+    /// let mut config = Config::default();
+    /// for kind in DefinitionKind::ALL {
+    ///     config.definition_count(kind, 0, 3);
+    /// }
+    /// ```
+    pub const ALL: [DefinitionKind; 10] = [
+        DefinitionKind::Fragment,
+        DefinitionKind::Scalar,
+        DefinitionKind::Enum,
+        DefinitionKind::Interface,
+        DefinitionKind::Object,
+        DefinitionKind::Union,
+        DefinitionKind::InputObject,
+        DefinitionKind::Schema,
+        DefinitionKind::Directive,
+        DefinitionKind::Operation,
+    ];
+}
+
+/// An edge case of the characters of descriptions and string values, which the lexer has to
+/// handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringEdgeCase {
+    /// Characters outside of ASCII, like `é` or `中`. Characters outside of the Basic
+    /// Multilingual Plane, like `😀`, are printed as escaped surrogate pairs.
+    Unicode,
+    /// Quotes, backslashes and control characters, printed as escape sequences
+    EscapedCharacter,
+    /// Triple quotes, escaped as `\"""` in block strings
+    BlockStringQuotes,
+    /// CRLF line endings. Documents with such strings are printed with CRLF line endings, both
+    /// between tokens and in block strings.
+    CrlfLineEnding,
+}
+
+impl StringEdgeCase {
+    /// Every edge case, in the order their weights are looked up in
+    pub(crate) const ALL: [StringEdgeCase; 4] = [
+        StringEdgeCase::Unicode,
+        StringEdgeCase::EscapedCharacter,
+        StringEdgeCase::BlockStringQuotes,
+        StringEdgeCase::CrlfLineEnding,
+    ];
+
+    /// The weight of the edge case when none is configured
+    fn default_weight(self) -> u32 {
+        match self {
+            StringEdgeCase::Unicode | StringEdgeCase::EscapedCharacter => 4,
+            StringEdgeCase::BlockStringQuotes | StringEdgeCase::CrlfLineEnding => 1,
+        }
+    }
+}
+
+/// Config sets the size and shape of the documents generated by `DocumentBuilder::with_config`
+///
+/// The default config generates between 1 and 50 definitions of every kind.
+///
+/// ```rust
+/// use apollo_smith::{Config, DefinitionKind};
+///
+/// // Small documents with only a few operations and fragments
+/// let mut config = Config::default();
+/// for kind in [
+///     DefinitionKind::Scalar,
+///     DefinitionKind::Enum,
+///     DefinitionKind::Interface,
+///     DefinitionKind::Object,
+///     DefinitionKind::Union,
+///     DefinitionKind::InputObject,
+///     DefinitionKind::Schema,
+///     DefinitionKind::Directive,
+/// ] {
+///     config.disable(kind);
+/// }
+/// config.definition_count(DefinitionKind::Operation, 1, 3);
+/// config.definition_count(DefinitionKind::Fragment, 0, 2);
+/// config.max_selection_depth(3);
+/// config.max_name_length(8);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    definition_counts: HashMap<DefinitionKind, RangeInclusive<usize>>,
+    disabled: HashSet<DefinitionKind>,
+    max_selection_depth: Option<usize>,
+    max_arguments: Option<usize>,
+    max_name_length: Option<usize>,
+    max_string_length: Option<usize>,
+    string_edge_case_weights: HashMap<StringEdgeCase, u32>,
+    valid_schema: bool,
+}
+
+impl Config {
+    /// Create a new instance of `Config` with the default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the minimum and maximum number of definitions of `kind` to generate
+    ///
+    /// # Panics
+    /// Panics if `min` is greater than `max`.
+    pub fn definition_count(&mut self, kind: DefinitionKind, min: usize, max: usize) {
+        assert!(
+            min <= max,
+            "the minimum definition count is greater than the maximum"
+        );
+        self.definition_counts.insert(kind, min..=max);
+    }
+
+    /// Generate definitions of `kind`, which is the default for every kind
+    pub fn enable(&mut self, kind: DefinitionKind) {
+        self.disabled.remove(&kind);
+    }
+
+    /// Do not generate any definition of `kind`
+    pub fn disable(&mut self, kind: DefinitionKind) {
+        self.disabled.insert(kind);
+    }
+
+    /// Set the maximum depth of nested selection sets, the selection set of an operation or a
+    /// fragment definition being at depth 0.
+    ///
+    /// By default, the depth of arbitrary selection sets is not limited, and the depth of the
+    /// selection sets generated for an existing schema is limited to 4.
+    pub fn max_selection_depth(&mut self, depth: usize) {
+        self.max_selection_depth = Some(depth);
+    }
+
+    /// Set the maximum number of arguments given to fields and directives, and of arguments in
+    /// argument definitions.
+    ///
+    /// By default, up to 4 arguments are given and argument definitions have between 2 and 5
+    /// arguments.
+    pub fn max_arguments(&mut self, max: usize) {
+        self.max_arguments = Some(max);
+    }
+
+    /// Set the maximum length of the generated names, 30 by default. Names have at least one
+    /// character, so a length of 0 is treated as 1.
+    pub fn max_name_length(&mut self, len: usize) {
+        self.max_name_length = Some(len.max(1));
+    }
+
+    /// Set the maximum length of descriptions and string values, in generated characters, a CRLF
+    /// line ending or triple quotes counting as one character.
+    ///
+    /// By default, descriptions have up to 100 characters and string values up to 40. Longer
+    /// strings, e.g. block strings of thousands of characters, need as much more data.
+    pub fn max_string_length(&mut self, len: usize) {
+        self.max_string_length = Some(len);
+    }
+
+    /// Set how often the characters of descriptions and string values are `edge_case`, relative
+    /// to a weight of 100 for plain ASCII characters. A weight of 0 disables the edge case.
+    ///
+    /// By default, unicode and escaped characters have a weight of 4, and triple quotes and CRLF
+    /// line endings a weight of 1.
+    ///
+    /// ```rust
+    /// use apollo_smith::{Config, StringEdgeCase};
+    ///
+    /// // Strings with mostly unicode characters, and no escape sequences
+    /// let mut config = Config::default();
+    /// config.string_edge_case_weight(StringEdgeCase::Unicode, 200);
+    /// config.string_edge_case_weight(StringEdgeCase::EscapedCharacter, 0);
+    /// config.string_edge_case_weight(StringEdgeCase::CrlfLineEnding, 0);
+    /// ```
+    pub fn string_edge_case_weight(&mut self, edge_case: StringEdgeCase, weight: u32) {
+        self.string_edge_case_weights.insert(edge_case, weight);
+    }
+
+    /// Generate a valid schema, disabled by default.
+    ///
+    /// Every type reference then resolves to an existing type of the right kind: fields have output
+    /// types, arguments and input fields have input types, union members and root operation types
+    /// are object types. Implementations of interfaces define all the fields of their interfaces,
+    /// with the same arguments and covariant types, and at most one schema definition is generated.
+    /// Type extensions and directives are not generated in type system definitions, and operations
+    /// and fragments are generated for the schema, like with `DocumentBuilder::with_document`.
+    pub fn valid_schema(&mut self, valid_schema: bool) {
+        self.valid_schema = valid_schema;
+    }
+
+    /// The number of definitions of `kind` to generate
+    pub(crate) fn definition_count_of(&self, kind: DefinitionKind) -> RangeInclusive<usize> {
+        if self.disabled.contains(&kind) {
+            return 0..=0;
+        }
+        self.definition_counts
+            .get(&kind)
+            .cloned()
+            .unwrap_or(DEFAULT_DEFINITION_COUNT)
+    }
+
+    /// The maximum depth of nested selection sets, `default` if none is configured
+    pub(crate) fn selection_depth_limit(&self, default: usize) -> usize {
+        self.max_selection_depth.unwrap_or(default)
+    }
+
+    /// The number of arguments to give to a field or a directive
+    pub(crate) fn argument_count(&self) -> RangeInclusive<usize> {
+        0..=self.max_arguments.unwrap_or(4)
+    }
+
+    /// Whether argument definitions can be generated, they need at least one argument
+    pub(crate) fn has_arguments_definitions(&self) -> bool {
+        self.max_arguments != Some(0)
+    }
+
+    /// The number of arguments of an argument definition
+    pub(crate) fn arguments_definition_count(&self) -> RangeInclusive<usize> {
+        match self.max_arguments {
+            Some(max) => max.min(2)..=max,
+            None => 2..=5,
+        }
+    }
+
+    /// Whether the type system definitions form a valid schema
+    pub(crate) fn generates_valid_schema(&self) -> bool {
+        self.valid_schema
+    }
+
+    /// The maximum length of the generated names
+    pub(crate) fn name_length_limit(&self) -> usize {
+        self.max_name_length.unwrap_or(DEFAULT_MAX_NAME_LENGTH)
+    }
+
+    /// The maximum length of the generated descriptions
+    pub(crate) fn description_length_limit(&self) -> usize {
+        self.max_string_length
+            .unwrap_or(DEFAULT_MAX_DESCRIPTION_LENGTH)
+    }
+
+    /// The maximum length of the generated string values
+    pub(crate) fn string_value_length_limit(&self) -> usize {
+        self.max_string_length
+            .unwrap_or(DEFAULT_MAX_STRING_VALUE_LENGTH)
+    }
+
+    /// The weight of `edge_case` in descriptions and string values
+    pub(crate) fn string_edge_case_weight_of(&self, edge_case: StringEdgeCase) -> u32 {
+        self.string_edge_case_weights
+            .get(&edge_case)
+            .copied()
+            .unwrap_or_else(|| edge_case.default_weight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Unstructured;
+
+    use super::*;
+    use crate::{selection_set, tests::data, DocumentBuilder};
+
+    fn selection_depth(selection_set: &selection_set::SelectionSet) -> usize {
+        selection_set
+            .selections
+            .iter()
+            .map(|selection| match selection {
+                selection_set::Selection::Field(field) => field
+                    .selection_set
+                    .as_ref()
+                    .map_or(0, |selection_set| selection_depth(selection_set) + 1),
+                selection_set::Selection::InlineFragment(inline_fragment) => {
+                    selection_depth(&inline_fragment.selection_set) + 1
+                }
+                selection_set::Selection::FragmentSpread(_) => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn it_generates_documents_with_a_config() {
+        let mut config = Config::default();
+        for kind in [
+            DefinitionKind::Fragment,
+            DefinitionKind::Scalar,
+            DefinitionKind::Interface,
+            DefinitionKind::Union,
+            DefinitionKind::InputObject,
+            DefinitionKind::Schema,
+            DefinitionKind::Directive,
+        ] {
+            config.disable(kind);
+        }
+        config.definition_count(DefinitionKind::Enum, 2, 2);
+        config.definition_count(DefinitionKind::Object, 0, 1);
+        config.definition_count(DefinitionKind::Operation, 1, 2);
+        config.max_selection_depth(1);
+        config.max_arguments(1);
+        config.max_name_length(5);
+
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let document = DocumentBuilder::with_config(&mut u, config.clone())
+                .unwrap()
+                .finish();
+
+            assert!(document.fragment_definitions.is_empty());
+            assert!(document.scalar_type_definitions.is_empty());
+            assert!(document.interface_type_definitions.is_empty());
+            assert!(document.union_type_definitions.is_empty());
+            assert!(document.input_object_type_definitions.is_empty());
+            assert!(document.schema_definitions.is_empty());
+            assert!(document.directive_definitions.is_empty());
+            assert_eq!(document.enum_type_definitions.len(), 2);
+            assert!(document.object_type_definitions.len() <= 1);
+            assert!((1..=2).contains(&document.operation_definitions.len()));
+            for operation_def in &document.operation_definitions {
+                assert!(selection_depth(&operation_def.selection_set) <= 1);
+            }
+            for enum_def in &document.enum_type_definitions {
+                // Type names get a numeric suffix when they already exist
+                let name = enum_def
+                    .name
+                    .name
+                    .trim_end_matches(|c: char| c.is_ascii_digit());
+                assert!(name.len() <= 5, "{}", enum_def.name.name);
+            }
+        }
+    }
+}
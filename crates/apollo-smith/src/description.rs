@@ -1,9 +1,37 @@
 use arbitrary::{Arbitrary, Result, Unstructured};
 
-use crate::DocumentBuilder;
+use crate::{
+    config::{StringEdgeCase, PLAIN_CHARACTER_WEIGHT},
+    DocumentBuilder,
+};
 
 const CHARSET: &[u8] =
     b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_\n\r\t/$#!.-+='";
+/// Characters outside of ASCII: accents, other scripts, invisible and special characters, and
+/// characters outside of the Basic Multilingual Plane
+const UNICODE_CHARS: &[char] = &[
+    'é',
+    'ß',
+    'ж',
+    'ع',
+    '中',
+    '文',
+    '\u{00A0}',
+    '\u{200B}',
+    '\u{2028}',
+    '\u{FEFF}',
+    '\u{FFFD}',
+    '\u{FFFF}',
+    '😀',
+    '🐱',
+    '\u{10FFFF}',
+];
+/// Characters that are escaped in strings
+const ESCAPED_CHARS: &[char] = &[
+    '"', '\\', '/', '\u{0008}', '\u{000C}', '\t', '\u{0000}', '\u{001F}', '\u{007F}', '\u{0085}',
+];
+/// Sequences of quotes that end a block string unless they are escaped
+const BLOCK_STRING_QUOTES: &[&str] = &[r#"""""#, r#""""""#, r#"\""""#];
 
 /// The `__Description` type represents a description
 ///
@@ -28,6 +56,24 @@ impl From<Description> for String {
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Descriptions).
 ///
+#[cfg(feature = "parser-impl")]
+impl From<apollo_parser::ast::Description> for Description {
+    fn from(desc: apollo_parser::ast::Description) -> Self {
+        use apollo_parser::ast::AstNode;
+
+        let string_value = desc.string_value();
+        let is_block = string_value.as_ref().map_or(false, |string| {
+            string.syntax().text().to_string().starts_with(r#"""""#)
+        });
+        let string = string_value.map(Into::into).unwrap_or_default();
+        if is_block {
+            Description(StringValue::Block(string))
+        } else {
+            Description(StringValue::Line(string))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StringValue {
     /// Represents a string value between """
@@ -39,8 +85,15 @@ pub enum StringValue {
 impl From<StringValue> for String {
     fn from(str_value: StringValue) -> Self {
         match str_value {
-            StringValue::Block(str_val) => format!(r#""""{str_val}""""#),
-            StringValue::Line(str_val) => format!(r#"{str_val}""#),
+            // apollo-encoder prints the descriptions wrapped in double quotes as block strings
+            StringValue::Block(str_val) => format!(r#""{str_val}""#),
+            // and would remove the quotes that start and end a string
+            StringValue::Line(str_val)
+                if str_val.len() > 1 && str_val.starts_with('"') && str_val.ends_with('"') =>
+            {
+                format!(r#""{str_val}""#)
+            }
+            StringValue::Line(str_val) => str_val,
         }
     }
 }
@@ -59,10 +112,79 @@ impl Arbitrary<'_> for StringValue {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
-    /// Create an arbitrary `Description`
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
+    /// Create an arbitrary `Description`, with the string edge cases of the config
     pub fn description(&mut self) -> Result<Description> {
-        self.u.arbitrary()
+        let string = self.limited_string_with_edge_cases(self.config.description_length_limit())?;
+        let string_value = if self.u.arbitrary()? {
+            StringValue::Block(string)
+        } else {
+            StringValue::Line(string)
+        };
+
+        Ok(Description(string_value))
+    }
+
+    /// Create an arbitrary string value, with the string edge cases of the config
+    pub(crate) fn string_value(&mut self) -> Result<String> {
+        self.limited_string_with_edge_cases(self.config.string_value_length_limit())
+    }
+
+    /// Create an arbitrary string of up to `max_size` plain ASCII characters and edge cases,
+    /// picked according to their weights in the config
+    fn limited_string_with_edge_cases(&mut self, max_size: usize) -> Result<String> {
+        let weights: Vec<(StringEdgeCase, u32)> = StringEdgeCase::ALL
+            .iter()
+            .map(|&edge_case| (edge_case, self.config.string_edge_case_weight_of(edge_case)))
+            .collect();
+        let total_weight = weights
+            .iter()
+            .fold(PLAIN_CHARACTER_WEIGHT, |total, (_, weight)| {
+                total.saturating_add(*weight)
+            });
+
+        let size = self.u.int_in_range(0..=max_size)?;
+        let mut gen_str = String::with_capacity(size);
+        for _ in 0..size {
+            let mut pick = self.u.int_in_range(0..=total_weight - 1)?;
+            // The plain characters come first, and are picked from the same byte of data
+            if pick < PLAIN_CHARACTER_WEIGHT {
+                gen_str.push(char::from(CHARSET[pick as usize % CHARSET.len()]));
+                continue;
+            }
+            pick -= PLAIN_CHARACTER_WEIGHT;
+            let edge_case = weights.iter().find_map(|&(edge_case, weight)| {
+                if pick < weight {
+                    Some(edge_case)
+                } else {
+                    pick -= weight;
+                    None
+                }
+            });
+            match edge_case {
+                Some(StringEdgeCase::Unicode) => {
+                    // Any character, or one of the characters most likely to be mishandled
+                    if self.u.arbitrary()? {
+                        gen_str.push(self.u.arbitrary()?)
+                    } else {
+                        gen_str.push(*self.u.choose(UNICODE_CHARS)?)
+                    }
+                }
+                Some(StringEdgeCase::EscapedCharacter) => {
+                    gen_str.push(*self.u.choose(ESCAPED_CHARS)?)
+                }
+                Some(StringEdgeCase::BlockStringQuotes) => {
+                    gen_str.push_str(self.u.choose(BLOCK_STRING_QUOTES)?)
+                }
+                Some(StringEdgeCase::CrlfLineEnding) => {
+                    gen_str.push('\n');
+                    self.crlf_line_endings = true;
+                }
+                None => unreachable!("the picked weight is less than the total weight"),
+            }
+        }
+
+        Ok(gen_str)
     }
 }
 
@@ -72,7 +194,7 @@ fn limited_string_desc(u: &mut Unstructured<'_>, max_size: usize) -> Result<Stri
     let gen_str = String::from_utf8(
         (0..size)
             .map(|_curr_idx| {
-                let idx = u.arbitrary::<usize>()?;
+                let idx = usize::from(u.arbitrary::<u8>()?);
 
                 let idx = idx % CHARSET.len();
 
@@ -84,3 +206,53 @@ fn limited_string_desc(u: &mut Unstructured<'_>, max_size: usize) -> Result<Stri
 
     Ok(gen_str)
 }
+
+#[cfg(all(test, feature = "parser-impl"))]
+mod tests {
+    use apollo_parser::{ast::AstNode, Parser, SyntaxKind};
+
+    use super::*;
+    use crate::{tests::small_config, Document};
+
+    #[test]
+    fn it_generates_strings_with_edge_cases() {
+        let mut config = small_config(2);
+        config.max_arguments(2);
+        config.max_string_length(500);
+        for edge_case in StringEdgeCase::ALL {
+            config.string_edge_case_weight(edge_case, 25);
+        }
+
+        let mut documents = String::new();
+        let mut strings = Vec::new();
+        for seed in 0..20 {
+            let document = String::from(Document::from_seed(seed, config.clone()).unwrap());
+
+            let tree = Parser::new(&document).parse();
+            let errors: Vec<_> = tree.errors().map(|err| err.message()).collect();
+            assert!(errors.is_empty(), "{}\n{:?}", document, errors);
+            let document_node = tree.document();
+            strings.extend(
+                document_node
+                    .syntax()
+                    .descendants()
+                    .filter(|node| node.kind() == SyntaxKind::STRING_VALUE)
+                    .map(|node| node.text().to_string()),
+            );
+            // The parser reads CRLF line endings as line feeds
+            let reprinted = String::from(Document::try_from(document_node).unwrap());
+            assert_eq!(reprinted, document.replace("\r\n", "\n"));
+            documents.push_str(&document);
+        }
+
+        // Surrogate pairs, other escape sequences, escaped block string quotes and CRLF line
+        // endings are all printed
+        for printed in [r"\uD83D\uDE00", r"\u0000", r#"\""""#, "\r\n", "中"] {
+            assert!(documents.contains(printed), "{}", printed);
+        }
+        // CRLF line endings are also printed within block strings, not only around their quotes
+        assert!(strings.iter().any(|string| {
+            string.starts_with(r#"""""#) && string.trim_matches('"').trim().contains("\r\n")
+        }));
+    }
+}
@@ -1,11 +1,11 @@
-use std::collections::HashSet;
-
 use arbitrary::{Arbitrary, Result};
+use indexmap::IndexSet;
 
 use crate::{
     argument::{Argument, ArgumentsDef},
     description::Description,
     name::Name,
+    ty::Ty,
     DocumentBuilder,
 };
 
@@ -21,7 +21,7 @@ pub struct DirectiveDef {
     pub(crate) name: Name,
     pub(crate) arguments_definition: Option<ArgumentsDef>,
     pub(crate) repeatable: bool,
-    pub(crate) directive_locations: HashSet<DirectiveLocation>,
+    pub(crate) directive_locations: IndexSet<DirectiveLocation>,
 }
 
 impl From<DirectiveDef> for apollo_encoder::DirectiveDefinition {
@@ -46,6 +46,33 @@ impl From<DirectiveDef> for apollo_encoder::DirectiveDefinition {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::DirectiveDefinition> for DirectiveDef {
+    type Error = crate::FromError;
+
+    fn try_from(dir_def: apollo_parser::ast::DirectiveDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: dir_def.description().map(Description::from),
+            name: dir_def.name().try_into()?,
+            arguments_definition: dir_def
+                .arguments_definition()
+                .map(ArgumentsDef::try_from)
+                .transpose()?,
+            repeatable: dir_def.repeatable_token().is_some(),
+            directive_locations: dir_def
+                .directive_locations()
+                .map(|locations| {
+                    locations
+                        .directive_locations()
+                        .map(DirectiveLocation::try_from)
+                        .collect()
+                })
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
 /// The `__Directive` type represents a Directive, it provides a way to describe alternate runtime execution and type validation behavior in a GraphQL document.
 ///
 /// *Directive*:
@@ -70,7 +97,36 @@ impl From<Directive> for apollo_encoder::Directive {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Directive> for Directive {
+    type Error = crate::FromError;
+
+    fn try_from(directive: apollo_parser::ast::Directive) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: directive.name().try_into()?,
+            arguments: directive
+                .arguments()
+                .map(|args| args.arguments().map(Argument::try_from).collect())
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl Directive {
+    /// Convert the directives of an `apollo-parser` AST node, if it has any
+    pub(crate) fn convert_directives(
+        directives: Option<apollo_parser::ast::Directives>,
+    ) -> Result<Vec<Self>, crate::FromError> {
+        directives
+            .map(|directives| directives.directives().map(Self::try_from).collect())
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary vector of `Directive`
     pub fn directives(&mut self) -> Result<Vec<Directive>> {
         // TODO choose only existing directives
@@ -90,6 +146,26 @@ impl<'a> DocumentBuilder<'a> {
         Ok(Directive { name, arguments })
     }
 
+    /// Create an arbitrary `@skip` or `@include` `Directive`, with a condition of the right type
+    pub fn skip_or_include_directive(&mut self) -> Result<Directive> {
+        let name = if self.u.arbitrary()? {
+            "skip"
+        } else {
+            "include"
+        };
+        let condition = self.input_value_for_type(&Ty::NonNull(Box::new(Ty::Named(Name::new(
+            String::from("Boolean"),
+        )))))?;
+
+        Ok(Directive {
+            name: Name::new(String::from(name)),
+            arguments: vec![Argument {
+                name: Name::new(String::from("if")),
+                value: condition,
+            }],
+        })
+    }
+
     /// Create an arbitrary `DirectiveDef`
     pub fn directive_def(&mut self) -> Result<DirectiveDef> {
         let description = self
@@ -99,12 +175,35 @@ impl<'a> DocumentBuilder<'a> {
             .then(|| self.description())
             .transpose()?;
         let name = self.type_name()?;
-        let arguments_definition = self
+        let arguments_definition = (self.config.has_arguments_definitions()
+            && self.u.arbitrary().unwrap_or(false))
+        .then(|| self.arguments_definition())
+        .transpose()?;
+        let repeatable = self.u.arbitrary().unwrap_or(false);
+        let directive_locations = self.directive_locations()?;
+
+        Ok(DirectiveDef {
+            description,
+            name,
+            arguments_definition,
+            repeatable,
+            directive_locations,
+        })
+    }
+
+    /// Create an arbitrary `DirectiveDef` named `name` for a valid schema, whose arguments have
+    /// existing input types
+    pub(crate) fn valid_directive_def(&mut self, name: Name) -> Result<DirectiveDef> {
+        let description = self
             .u
             .arbitrary()
             .unwrap_or(false)
-            .then(|| self.arguments_definition())
+            .then(|| self.description())
             .transpose()?;
+        let arguments_definition = (self.config.has_arguments_definitions()
+            && self.u.arbitrary().unwrap_or(false))
+        .then(|| self.valid_arguments_definition())
+        .transpose()?;
         let repeatable = self.u.arbitrary().unwrap_or(false);
         let directive_locations = self.directive_locations()?;
 
@@ -117,11 +216,11 @@ impl<'a> DocumentBuilder<'a> {
         })
     }
 
-    /// Create an arbitrary `HashSet` of `DirectiveLocation`
-    pub fn directive_locations(&mut self) -> Result<HashSet<DirectiveLocation>> {
+    /// Create an arbitrary `IndexSet` of `DirectiveLocation`
+    pub fn directive_locations(&mut self) -> Result<IndexSet<DirectiveLocation>> {
         (1..self.u.int_in_range(2..=5usize)?)
             .map(|_| self.u.arbitrary())
-            .collect::<Result<HashSet<_>>>()
+            .collect::<Result<IndexSet<_>>>()
     }
 }
 
@@ -149,6 +248,40 @@ pub enum DirectiveLocation {
     InputFieldDefinition,
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::DirectiveLocation> for DirectiveLocation {
+    type Error = crate::FromError;
+
+    fn try_from(dl: apollo_parser::ast::DirectiveLocation) -> Result<Self, Self::Error> {
+        use apollo_parser::ast::AstNode;
+
+        let location = match dl.syntax().text().to_string().trim() {
+            "QUERY" => DirectiveLocation::Query,
+            "MUTATION" => DirectiveLocation::Mutation,
+            "SUBSCRIPTION" => DirectiveLocation::Subscription,
+            "FIELD" => DirectiveLocation::Field,
+            "FRAGMENT_DEFINITION" => DirectiveLocation::FragmentDefinition,
+            "FRAGMENT_SPREAD" => DirectiveLocation::FragmentSpread,
+            "INLINE_FRAGMENT" => DirectiveLocation::InlineFragment,
+            "VARIABLE_DEFINITION" => DirectiveLocation::VariableDefinition,
+            "SCHEMA" => DirectiveLocation::Schema,
+            "SCALAR" => DirectiveLocation::Scalar,
+            "OBJECT" => DirectiveLocation::Object,
+            "FIELD_DEFINITION" => DirectiveLocation::FieldDefinition,
+            "ARGUMENT_DEFINITION" => DirectiveLocation::ArgumentDefinition,
+            "INTERFACE" => DirectiveLocation::Interface,
+            "UNION" => DirectiveLocation::Union,
+            "ENUM" => DirectiveLocation::Enum,
+            "ENUM_VALUE" => DirectiveLocation::EnumValue,
+            "INPUT_OBJECT" => DirectiveLocation::InputObject,
+            "INPUT_FIELD_DEFINITION" => DirectiveLocation::InputFieldDefinition,
+            _ => return Err(crate::FromError::InvalidValue),
+        };
+
+        Ok(location)
+    }
+}
+
 impl From<DirectiveLocation> for String {
     fn from(dl: DirectiveLocation) -> Self {
         match dl {
@@ -1,9 +1,17 @@
+use arbitrary::{Result, Unstructured};
+
 use crate::{
     directive::DirectiveDef, enum_::EnumTypeDef, fragment::FragmentDef,
     input_object::InputObjectTypeDef, interface::InterfaceTypeDef, object::ObjectTypeDef,
-    operation::OperationDef, scalar::ScalarTypeDef, schema::SchemaDef, union::UnionTypeDef,
+    operation::OperationDef, scalar::ScalarTypeDef, schema::SchemaDef,
+    selection_set::MAX_SELECTION_DEPTH, union::UnionTypeDef, Config, DocumentBuilder,
 };
 
+/// Size of the data [`Document::from_seed`] first tries to generate a document from
+const MIN_SEED_DATA_LEN: usize = 4 * 1024;
+/// Size of the data after which [`Document::from_seed`] gives up
+const MAX_SEED_DATA_LEN: usize = 16 * 1024 * 1024;
+
 /// The `__Document` type represents a GraphQL document.A GraphQL Document describes a complete file or request string operated on by a GraphQL service or client.
 /// A document contains multiple definitions, either executable or representative of a GraphQL type system.
 ///
@@ -33,6 +41,64 @@ pub struct Document {
     pub(crate) enum_type_definitions: Vec<EnumTypeDef>,
     pub(crate) input_object_type_definitions: Vec<InputObjectTypeDef>,
     pub(crate) directive_definitions: Vec<DirectiveDef>,
+    /// Whether the document is printed with CRLF line endings
+    pub(crate) crlf_line_endings: bool,
+}
+
+impl Document {
+    /// Generate a document with the size and shape set by `config` from a `seed`, e.g. to
+    /// generate documents outside of a fuzzer. The same seed and config always give the same
+    /// document.
+    ///
+    /// The document is generated from pseudo-random data seeded with `seed`. `Unstructured` reads
+    /// from a slice that cannot grow, so the data is generated up front, 4 KiB of it at first:
+    /// when it runs out, the document is generated again from scratch with twice as much data,
+    /// up to 16 MiB. As a generation costs about as much as the data it consumes, the passes
+    /// cost at most about twice as much as the last one, but the documents of large configs go
+    /// through up to 13 of them. Configs whose documents need more than 16 MiB of data fail with
+    /// `arbitrary::Error::NotEnoughData`. Arbitrary selection sets are at most 4 deep, unless
+    /// `config` sets another maximum depth.
+    ///
+    /// ```rust
+    /// use apollo_smith::{Config, DefinitionKind, Document};
+    ///
+    /// let mut config = Config::default();
+    /// config.definition_count(DefinitionKind::Object, 1, 5);
+    /// config.disable(DefinitionKind::Operation);
+    /// config.disable(DefinitionKind::Fragment);
+    /// let document = Document::from_seed(42, config).unwrap();
+    /// let document_str = String::from(document);
+    /// ```
+    pub fn from_seed(seed: u64, mut config: Config) -> Result<Self> {
+        // Arbitrary selection sets are only limited by the data, which would overflow the stack
+        config.max_selection_depth(config.selection_depth_limit(MAX_SELECTION_DEPTH));
+        let mut len = MIN_SEED_DATA_LEN;
+        loop {
+            let data = seeded_data(seed, len);
+            let mut u = Unstructured::new(&data);
+            match DocumentBuilder::with_config(&mut u, config.clone()) {
+                Ok(builder) => return Ok(builder.finish()),
+                Err(arbitrary::Error::NotEnoughData) if len < MAX_SEED_DATA_LEN => len *= 2,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Generate `len` pseudo-random bytes from `seed` with SplitMix64
+pub(crate) fn seeded_data(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut data = Vec::with_capacity(len + 8);
+    while data.len() < len {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        data.extend_from_slice(&(z ^ (z >> 31)).to_le_bytes());
+    }
+    data.truncate(len);
+
+    data
 }
 
 impl From<Document> for apollo_encoder::Document {
@@ -73,8 +139,157 @@ impl From<Document> for apollo_encoder::Document {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Document> for Document {
+    type Error = crate::FromError;
+
+    fn try_from(doc: apollo_parser::ast::Document) -> Result<Self, Self::Error> {
+        use apollo_parser::ast::Definition;
+
+        let mut new_doc = Self {
+            operation_definitions: Vec::new(),
+            fragment_definitions: Vec::new(),
+            schema_definitions: Vec::new(),
+            scalar_type_definitions: Vec::new(),
+            object_type_definitions: Vec::new(),
+            interface_type_definitions: Vec::new(),
+            union_type_definitions: Vec::new(),
+            enum_type_definitions: Vec::new(),
+            input_object_type_definitions: Vec::new(),
+            directive_definitions: Vec::new(),
+            crlf_line_endings: false,
+        };
+        for definition in doc.definitions() {
+            match definition {
+                Definition::OperationDefinition(def) => {
+                    new_doc.operation_definitions.push(def.try_into()?)
+                }
+                Definition::FragmentDefinition(def) => {
+                    new_doc.fragment_definitions.push(def.try_into()?)
+                }
+                Definition::SchemaDefinition(def) => {
+                    new_doc.schema_definitions.push(def.try_into()?)
+                }
+                Definition::SchemaExtension(def) => {
+                    new_doc.schema_definitions.push(def.try_into()?)
+                }
+                Definition::ScalarTypeDefinition(def) => {
+                    new_doc.scalar_type_definitions.push(def.try_into()?)
+                }
+                Definition::ScalarTypeExtension(def) => {
+                    new_doc.scalar_type_definitions.push(def.try_into()?)
+                }
+                Definition::ObjectTypeDefinition(def) => {
+                    new_doc.object_type_definitions.push(def.try_into()?)
+                }
+                Definition::ObjectTypeExtension(def) => {
+                    new_doc.object_type_definitions.push(def.try_into()?)
+                }
+                Definition::InterfaceTypeDefinition(def) => {
+                    new_doc.interface_type_definitions.push(def.try_into()?)
+                }
+                Definition::InterfaceTypeExtension(def) => {
+                    new_doc.interface_type_definitions.push(def.try_into()?)
+                }
+                Definition::UnionTypeDefinition(def) => {
+                    new_doc.union_type_definitions.push(def.try_into()?)
+                }
+                Definition::UnionTypeExtension(def) => {
+                    new_doc.union_type_definitions.push(def.try_into()?)
+                }
+                Definition::EnumTypeDefinition(def) => {
+                    new_doc.enum_type_definitions.push(def.try_into()?)
+                }
+                Definition::EnumTypeExtension(def) => {
+                    new_doc.enum_type_definitions.push(def.try_into()?)
+                }
+                Definition::InputObjectTypeDefinition(def) => {
+                    new_doc.input_object_type_definitions.push(def.try_into()?)
+                }
+                Definition::InputObjectTypeExtension(def) => {
+                    new_doc.input_object_type_definitions.push(def.try_into()?)
+                }
+                Definition::DirectiveDefinition(def) => {
+                    new_doc.directive_definitions.push(def.try_into()?)
+                }
+            }
+        }
+
+        Ok(new_doc)
+    }
+}
+
 impl From<Document> for String {
     fn from(doc: Document) -> Self {
-        apollo_encoder::Document::from(doc).to_string()
+        let crlf_line_endings = doc.crlf_line_endings;
+        let document = apollo_encoder::Document::from(doc).to_string();
+        // Line strings escape their line breaks, so this only changes the line breaks between
+        // tokens and in block strings
+        if crlf_line_endings {
+            document.replace('\n', "\r\n")
+        } else {
+            document
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_parser::Parser;
+
+    use super::*;
+    #[cfg(feature = "parser-impl")]
+    use crate::tests::data;
+    use crate::tests::small_config;
+
+    #[test]
+    fn it_generates_the_same_document_from_a_seed() {
+        let mut config = small_config(3);
+        config.max_string_length(20);
+
+        let documents: Vec<String> = (0..10)
+            .map(|seed| String::from(Document::from_seed(seed, config.clone()).unwrap()))
+            .collect();
+        for (seed, document) in (0..).zip(&documents) {
+            let again = String::from(Document::from_seed(seed, config.clone()).unwrap());
+            assert_eq!(&again, document);
+            let tree = Parser::new(document).parse();
+            let errors: Vec<_> = tree.errors().map(|err| err.message()).collect();
+            assert!(errors.is_empty(), "{}\n{:?}", document, errors);
+        }
+        assert!(documents[1..]
+            .iter()
+            .any(|document| document != &documents[0]));
+
+        // The data left is available once the document is generated
+        let data = seeded_data(0, 1024 * 1024);
+        let mut u = Unstructured::new(&data);
+        let gql_doc = DocumentBuilder::with_config(&mut u, config).unwrap();
+        let document = String::from(gql_doc.finish());
+        assert!(!document.is_empty());
+        assert!(u.len() < data.len());
+    }
+
+    #[test]
+    #[cfg(feature = "parser-impl")]
+    fn it_prints_documents_that_parse_back_to_the_same_document() {
+        for valid_schema in [false, true] {
+            let mut config = small_config(2);
+            config.max_arguments(2);
+            config.valid_schema(valid_schema);
+
+            for seed in 0..100 {
+                let data = data(seed);
+                let mut u = Unstructured::new(&data);
+                let gql_doc = DocumentBuilder::with_config(&mut u, config.clone()).unwrap();
+                let document = String::from(gql_doc.finish());
+
+                let tree = Parser::new(&document).parse();
+                let errors: Vec<_> = tree.errors().map(|err| err.message()).collect();
+                assert!(errors.is_empty(), "{}\n{:?}", document, errors);
+                let reprinted = String::from(Document::try_from(tree.document()).unwrap());
+                assert_eq!(reprinted, document.replace("\r\n", "\n"));
+            }
+        }
     }
 }
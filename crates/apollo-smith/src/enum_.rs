@@ -1,7 +1,8 @@
-use std::{collections::HashSet, hash::Hash};
+use std::hash::Hash;
 
 use apollo_encoder::{EnumDefinition, EnumValue};
 use arbitrary::Result;
+use indexmap::IndexSet;
 
 use crate::{description::Description, directive::Directive, name::Name, DocumentBuilder};
 
@@ -16,7 +17,7 @@ pub struct EnumTypeDef {
     pub(crate) description: Option<Description>,
     pub(crate) name: Name,
     pub(crate) directives: Vec<Directive>,
-    pub(crate) enum_values_def: HashSet<EnumValueDefinition>,
+    pub(crate) enum_values_def: IndexSet<EnumValueDefinition>,
     pub(crate) extend: bool,
 }
 
@@ -80,7 +81,72 @@ impl Hash for EnumValueDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::EnumTypeDefinition> for EnumTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(enum_def: apollo_parser::ast::EnumTypeDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: enum_def.description().map(Description::from),
+            name: enum_def.name().try_into()?,
+            directives: Directive::convert_directives(enum_def.directives())?,
+            enum_values_def: Self::convert_values(enum_def.enum_values_definition())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::EnumTypeExtension> for EnumTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(enum_def: apollo_parser::ast::EnumTypeExtension) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: None,
+            name: enum_def.name().try_into()?,
+            directives: Directive::convert_directives(enum_def.directives())?,
+            enum_values_def: Self::convert_values(enum_def.enum_values_definition())?,
+            extend: true,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl EnumTypeDef {
+    fn convert_values(
+        values: Option<apollo_parser::ast::EnumValuesDefinition>,
+    ) -> Result<IndexSet<EnumValueDefinition>, crate::FromError> {
+        values
+            .map(|values| {
+                values
+                    .enum_value_definitions()
+                    .map(EnumValueDefinition::try_from)
+                    .collect()
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::EnumValueDefinition> for EnumValueDefinition {
+    type Error = crate::FromError;
+
+    fn try_from(
+        enum_value_def: apollo_parser::ast::EnumValueDefinition,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: enum_value_def.description().map(Description::from),
+            value: enum_value_def
+                .enum_value()
+                .and_then(|value| value.name())
+                .try_into()?,
+            directives: Directive::convert_directives(enum_value_def.directives())?,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `EnumTypeDef`
     pub fn enum_type_definition(&mut self) -> Result<EnumTypeDef> {
         let description = self
@@ -108,7 +174,7 @@ impl<'a> DocumentBuilder<'a> {
     }
 
     /// Create an arbitrary variant `Name` given an enum
-    pub fn arbitrary_variant<'b>(&mut self, enum_: &'b EnumTypeDef) -> Result<&'b Name> {
+    pub fn arbitrary_variant<'e>(&mut self, enum_: &'e EnumTypeDef) -> Result<&'e Name> {
         let arbitrary_idx = self.u.int_in_range(0..=(enum_.enum_values_def.len() - 1))?;
         Ok(enum_
             .enum_values_def
@@ -119,8 +185,8 @@ impl<'a> DocumentBuilder<'a> {
     }
 
     /// Create an arbitrary `EnumValueDefinition`
-    pub fn enum_values_definition(&mut self) -> Result<HashSet<EnumValueDefinition>> {
-        let mut enum_values_def = HashSet::with_capacity(self.u.int_in_range(2..=10usize)?);
+    pub fn enum_values_definition(&mut self) -> Result<IndexSet<EnumValueDefinition>> {
+        let mut enum_values_def = IndexSet::with_capacity(self.u.int_in_range(2..=10usize)?);
         for i in 0..self.u.int_in_range(2..=10usize)? {
             let description = self
                 .u
@@ -140,4 +206,36 @@ impl<'a> DocumentBuilder<'a> {
 
         Ok(enum_values_def)
     }
+
+    /// Create an arbitrary `EnumTypeDef` named `name` for a valid schema, with unique values
+    pub(crate) fn valid_enum_type_definition(&mut self, name: Name) -> Result<EnumTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let enum_values_def = (0..self.u.int_in_range(1..=10usize)?)
+            .map(|i| {
+                Ok(EnumValueDefinition {
+                    description: self
+                        .u
+                        .arbitrary()
+                        .unwrap_or(false)
+                        .then(|| self.description())
+                        .transpose()?,
+                    value: self.unique_name(i)?,
+                    directives: Vec::new(),
+                })
+            })
+            .collect::<Result<IndexSet<_>>>()?;
+
+        Ok(EnumTypeDef {
+            description,
+            name,
+            enum_values_def,
+            directives: Vec::new(),
+            extend: false,
+        })
+    }
 }
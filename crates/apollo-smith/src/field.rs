@@ -1,13 +1,12 @@
-use std::collections::HashSet;
-
 use arbitrary::Result;
+use indexmap::IndexSet;
 
 use crate::{
     argument::{Argument, ArgumentsDef},
     description::Description,
     directive::Directive,
     name::Name,
-    selection_set::SelectionSet,
+    selection_set::{SelectionSet, MAX_SELECTION_DEPTH},
     ty::Ty,
     DocumentBuilder,
 };
@@ -44,13 +43,47 @@ impl From<FieldDef> for apollo_encoder::FieldDefinition {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::FieldDefinition> for FieldDef {
+    type Error = crate::FromError;
+
+    fn try_from(field_def: apollo_parser::ast::FieldDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: field_def.description().map(Description::from),
+            name: field_def.name().try_into()?,
+            arguments_definition: field_def
+                .arguments_definition()
+                .map(ArgumentsDef::try_from)
+                .transpose()?,
+            ty: field_def
+                .ty()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+            directives: Directive::convert_directives(field_def.directives())?,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl FieldDef {
+    /// Convert the fields of an `apollo-parser` AST node, if it has any
+    pub(crate) fn convert_fields(
+        fields_def: Option<apollo_parser::ast::FieldsDefinition>,
+    ) -> Result<Vec<Self>, crate::FromError> {
+        fields_def
+            .map(|fields_def| fields_def.field_definitions().map(Self::try_from).collect())
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
 /// The __Field type represents each field in an Object or Interface type.
 ///
 /// *Field*:
 ///     Alias? Name Arguments? Directives? SelectionSet?
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Language.Fields).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Field {
     pub(crate) alias: Option<Name>,
     pub(crate) name: Name,
@@ -77,11 +110,49 @@ impl From<Field> for apollo_encoder::Field {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Field> for Field {
+    type Error = crate::FromError;
+
+    fn try_from(field: apollo_parser::ast::Field) -> Result<Self, Self::Error> {
+        Ok(Self {
+            alias: field
+                .alias()
+                .map(|alias| alias.name().try_into())
+                .transpose()?,
+            name: field.name().try_into()?,
+            args: field
+                .arguments()
+                .map(|args| args.arguments().map(Argument::try_from).collect())
+                .transpose()?
+                .unwrap_or_default(),
+            directives: Directive::convert_directives(field.directives())?,
+            selection_set: field
+                .selection_set()
+                .map(SelectionSet::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+impl Field {
+    /// The `__typename` meta-field, which can be selected on any object, interface or union type
+    pub(crate) fn typename() -> Self {
+        Self {
+            alias: None,
+            name: Name::new(String::from("__typename")),
+            args: Vec::new(),
+            directives: Vec::new(),
+            selection_set: None,
+        }
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary list of `FieldDef`
     pub fn fields_definition(&mut self, exclude: &[&Name]) -> Result<Vec<FieldDef>> {
         let num_fields = self.u.int_in_range(2..=50usize)?;
-        let mut fields_names = HashSet::with_capacity(num_fields);
+        let mut fields_names = IndexSet::with_capacity(num_fields);
 
         for i in 0..num_fields {
             let name = self.name_with_index(i)?;
@@ -104,12 +175,10 @@ impl<'a> DocumentBuilder<'a> {
                         .then(|| self.description())
                         .transpose()?,
                     name: field_name,
-                    arguments_definition: self
-                        .u
-                        .arbitrary()
-                        .unwrap_or(false)
-                        .then(|| self.arguments_definition())
-                        .transpose()?,
+                    arguments_definition: (self.config.has_arguments_definitions()
+                        && self.u.arbitrary().unwrap_or(false))
+                    .then(|| self.arguments_definition())
+                    .transpose()?,
                     ty: self.choose_ty(&available_types)?,
                     directives: self.directives()?,
                 })
@@ -117,6 +186,44 @@ impl<'a> DocumentBuilder<'a> {
             .collect()
     }
 
+    /// Create an arbitrary list of `FieldDef` of `output_types` for a valid schema, in addition to
+    /// the `required` fields of implemented interfaces, whose names are not reused
+    pub(crate) fn valid_fields_definition(
+        &mut self,
+        output_types: &[Ty],
+        required: &[FieldDef],
+    ) -> Result<Vec<FieldDef>> {
+        // Object and interface types define at least one field
+        let min_fields = if required.is_empty() { 1 } else { 0 };
+        let mut fields_def = Vec::new();
+        for i in 0..self.u.int_in_range(min_fields..=10usize)? {
+            let name = self.unique_name(i)?;
+            if required.iter().any(|field| field.name == name) {
+                continue;
+            }
+            let description = self
+                .u
+                .arbitrary()
+                .unwrap_or(false)
+                .then(|| self.description())
+                .transpose()?;
+            let arguments_definition = (self.config.has_arguments_definitions()
+                && self.u.arbitrary().unwrap_or(false))
+            .then(|| self.valid_arguments_definition())
+            .transpose()?;
+
+            fields_def.push(FieldDef {
+                description,
+                name,
+                arguments_definition,
+                ty: self.choose_ty(output_types)?,
+                directives: Vec::new(),
+            });
+        }
+
+        Ok(fields_def)
+    }
+
     /// Create an arbitrary `Field` given an index to put in the name of the field (to avoid duplicated fields)
     pub fn field_with_index(&mut self, index: usize) -> Result<Field> {
         let alias = self
@@ -128,10 +235,7 @@ impl<'a> DocumentBuilder<'a> {
         let name = self.name_with_index(index)?;
         let args = self.arguments()?;
         let directives = self.directives()?;
-        let selection_set = self
-            .u
-            .arbitrary()
-            .unwrap_or(false)
+        let selection_set = (self.can_nest_selection_set() && self.u.arbitrary().unwrap_or(false))
             .then(|| self.selection_set())
             .transpose()?;
 
@@ -143,4 +247,66 @@ impl<'a> DocumentBuilder<'a> {
             selection_set,
         })
     }
+
+    /// Create an arbitrary `Field` selecting an existing field of `parent`, returns `None` if no
+    /// field can be selected, e.g. on a union type
+    pub(crate) fn typed_field(
+        &mut self,
+        parent: &Name,
+        scope: Option<&Name>,
+        depth: usize,
+    ) -> Result<Option<Field>> {
+        let fields: Vec<FieldDef> = self
+            .fields_of(parent)
+            .into_iter()
+            // Only leaf fields are selected once the maximum depth is reached, and fields without
+            // a possible value never are
+            .filter(|field| {
+                (depth < self.config.selection_depth_limit(MAX_SELECTION_DEPTH)
+                    || !self.is_composite_type(field.ty.name()))
+                    && self.has_value(&field.ty)
+            })
+            .collect();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        let field_def = self.u.choose(&fields)?.clone();
+
+        let args = match &field_def.arguments_definition {
+            Some(arguments_def) => self.arguments_with_def(arguments_def)?,
+            None => Vec::new(),
+        };
+        let alias = if !args.is_empty() {
+            self.alias_count += 1;
+            Some(Name::new(format!(
+                "{}_{}_{}",
+                field_def.name.name, parent.name, self.alias_count
+            )))
+        } else if scope != Some(parent) {
+            Some(Name::new(format!(
+                "{}_{}",
+                field_def.name.name, parent.name
+            )))
+        } else {
+            None
+        };
+        let directives = if self.u.int_in_range(0..=7usize)? == 0 {
+            vec![self.skip_or_include_directive()?]
+        } else {
+            Vec::new()
+        };
+        let ty = field_def.ty.name().clone();
+        let selection_set = self
+            .is_composite_type(&ty)
+            .then(|| self.typed_selection_set(&ty, Some(&ty), depth + 1))
+            .transpose()?;
+
+        Ok(Some(Field {
+            alias,
+            name: field_def.name,
+            args,
+            directives,
+            selection_set,
+        }))
+    }
 }
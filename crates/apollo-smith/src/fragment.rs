@@ -10,7 +10,7 @@ use crate::{
 ///     fragment FragmentName TypeCondition Directives? SelectionSet
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#FragmentDefinition).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FragmentDef {
     pub(crate) name: Name,
     pub(crate) type_condition: TypeCondition,
@@ -34,13 +34,36 @@ impl From<FragmentDef> for apollo_encoder::FragmentDefinition {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::FragmentDefinition> for FragmentDef {
+    type Error = crate::FromError;
+
+    fn try_from(frag_def: apollo_parser::ast::FragmentDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: frag_def
+                .fragment_name()
+                .and_then(|fragment_name| fragment_name.name())
+                .try_into()?,
+            type_condition: frag_def
+                .type_condition()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+            directives: Directive::convert_directives(frag_def.directives())?,
+            selection_set: frag_def
+                .selection_set()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+        })
+    }
+}
+
 /// The __fragmentSpread type represents a named fragment used in a selection set.
 ///
 /// *FragmentSpread*:
 ///     ... FragmentName Directives?
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#FragmentSpread).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FragmentSpread {
     pub(crate) name: Name,
     pub(crate) directives: Vec<Directive>,
@@ -58,13 +81,28 @@ impl From<FragmentSpread> for apollo_encoder::FragmentSpread {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::FragmentSpread> for FragmentSpread {
+    type Error = crate::FromError;
+
+    fn try_from(fragment_spread: apollo_parser::ast::FragmentSpread) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: fragment_spread
+                .fragment_name()
+                .and_then(|fragment_name| fragment_name.name())
+                .try_into()?,
+            directives: Directive::convert_directives(fragment_spread.directives())?,
+        })
+    }
+}
+
 /// The __inlineFragment type represents an inline fragment in a selection set that could be used as a field
 ///
 /// *InlineFragment*:
 ///     ... TypeCondition? Directives? SelectionSet
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Inline-Fragments).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InlineFragment {
     pub(crate) type_condition: Option<TypeCondition>,
     pub(crate) directives: Vec<Directive>,
@@ -84,15 +122,34 @@ impl From<InlineFragment> for apollo_encoder::InlineFragment {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InlineFragment> for InlineFragment {
+    type Error = crate::FromError;
+
+    fn try_from(inline_fragment: apollo_parser::ast::InlineFragment) -> Result<Self, Self::Error> {
+        Ok(Self {
+            type_condition: inline_fragment
+                .type_condition()
+                .map(TypeCondition::try_from)
+                .transpose()?,
+            directives: Directive::convert_directives(inline_fragment.directives())?,
+            selection_set: inline_fragment
+                .selection_set()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+        })
+    }
+}
+
 /// The __typeCondition type represents where a fragment could be applied
 ///
 /// *TypeCondition*:
 ///     on NamedType
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#TypeCondition).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypeCondition {
-    name: Name,
+    pub(crate) name: Name,
 }
 
 impl From<TypeCondition> for apollo_encoder::TypeCondition {
@@ -101,7 +158,21 @@ impl From<TypeCondition> for apollo_encoder::TypeCondition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::TypeCondition> for TypeCondition {
+    type Error = crate::FromError;
+
+    fn try_from(ty_cond: apollo_parser::ast::TypeCondition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: ty_cond
+                .named_type()
+                .and_then(|named_type| named_type.name())
+                .try_into()?,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `FragmentDef`
     pub fn fragment_definition(&mut self) -> Result<FragmentDef> {
         let name = self.type_name()?;
@@ -122,10 +193,10 @@ impl<'a> DocumentBuilder<'a> {
         let available_fragment: Vec<&FragmentDef> = self
             .fragment_defs
             .iter()
-            .filter(|f| excludes.contains(&f.name))
+            .filter(|f| !excludes.contains(&f.name))
             .collect();
 
-        let name = if !available_fragment.is_empty() {
+        let name = if available_fragment.is_empty() {
             return Ok(None);
         } else {
             self.u.choose(&available_fragment)?.name.clone()
@@ -166,4 +237,92 @@ impl<'a> DocumentBuilder<'a> {
             name: self.choose_named_ty(&named_types)?.name().clone(),
         })
     }
+
+    /// Create an arbitrary `FragmentDef` on an existing object, interface or union type, selecting
+    /// its fields. Returns `None` if there is no such type.
+    ///
+    /// Fragment definitions use neither variables nor other fragments, so they are valid in any operation.
+    pub fn fragment_definition_for_schema(&mut self) -> Result<Option<FragmentDef>> {
+        let composite_types: Vec<Name> = self.composite_type_names().cloned().collect();
+        if composite_types.is_empty() {
+            return Ok(None);
+        }
+        let ty = self.u.choose(&composite_types)?.clone();
+        let name = Name::new(format!(
+            "{}_{}",
+            self.limited_string(self.config.name_length_limit())?,
+            self.fragment_defs.len()
+        ));
+        let operation_variables = self.operation_variables.take();
+        let selection_set = self.typed_selection_set(&ty, None, 1);
+        self.operation_variables = operation_variables;
+
+        Ok(Some(FragmentDef {
+            name,
+            type_condition: TypeCondition { name: ty },
+            directives: Vec::new(),
+            selection_set: selection_set?,
+        }))
+    }
+
+    /// Create an arbitrary `InlineFragment` on a type whose possible types overlap with the ones of `parent`
+    pub(crate) fn typed_inline_fragment(
+        &mut self,
+        parent: &Name,
+        scope: Option<&Name>,
+        depth: usize,
+    ) -> Result<Option<InlineFragment>> {
+        let possible_types = self.possible_types(parent);
+        let type_conditions: Vec<Name> = self
+            .composite_type_names()
+            .filter(|ty| !self.possible_types(ty).is_disjoint(&possible_types))
+            .cloned()
+            .collect();
+        let ty = match self.u.choose(&type_conditions) {
+            Ok(ty) if self.u.arbitrary().unwrap_or(false) => ty.clone(),
+            _ => parent.clone(),
+        };
+        let selection_set = self.typed_selection_set(&ty, scope, depth + 1)?;
+        // A type without possible types, e.g. an interface without implementations, cannot be
+        // the type condition of a fragment in its own selection set
+        let type_condition = (&ty != parent
+            || (!possible_types.is_empty() && self.u.arbitrary().unwrap_or(false)))
+        .then(|| TypeCondition { name: ty });
+
+        Ok(Some(InlineFragment {
+            type_condition,
+            directives: Vec::new(),
+            selection_set,
+        }))
+    }
+
+    /// Create an arbitrary `FragmentSpread` of an existing fragment whose possible types overlap
+    /// with the ones of `parent`. Returns `None` in a fragment definition, or if there is no such fragment.
+    pub(crate) fn typed_fragment_spread(
+        &mut self,
+        parent: &Name,
+    ) -> Result<Option<FragmentSpread>> {
+        if self.operation_variables.is_none() {
+            return Ok(None);
+        }
+        let possible_types = self.possible_types(parent);
+        let fragments: Vec<Name> = self
+            .fragment_defs
+            .iter()
+            .filter(|fragment| {
+                !self
+                    .possible_types(&fragment.type_condition.name)
+                    .is_disjoint(&possible_types)
+            })
+            .map(|fragment| fragment.name.clone())
+            .collect();
+        if fragments.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(FragmentSpread {
+            name: self.u.choose(&fragments)?.clone(),
+            directives: Vec::new(),
+        }))
+    }
 }
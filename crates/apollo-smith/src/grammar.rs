@@ -0,0 +1,540 @@
+use std::{collections::HashMap, fmt};
+
+use arbitrary::{Result, Unstructured};
+use ungrammar::{Grammar, Rule};
+
+/// Maximum depth of nested nodes when none is configured
+const DEFAULT_MAX_DEPTH: usize = 16;
+/// Maximum number of items of a repetition, like `Selection*`
+const MAX_REPETITIONS: usize = 3;
+/// Characters of the generated string tokens
+const STRING_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz ABCDEFGHIJKLMNOPQRSTUVWXYZ 0123456789_";
+
+/// GrammarGenerator generates arbitrary text for any node of an ungrammar grammar, like the
+/// `graphql.ungram` grammar the `apollo-parser` syntax tree is generated from.
+///
+/// Unlike `DocumentBuilder`, which generates valid documents, it follows the grammar as written:
+/// the generated text has the shape of the syntax tree, but the grammar is less strict than
+/// GraphQL, e.g. union members are not separated by `|`, so it is not always valid. It counts the
+/// productions of the grammar it generates, to find the ones a fuzz corpus never reaches.
+///
+/// ```rust
+/// use apollo_smith::GrammarGenerator;
+/// use arbitrary::Unstructured;
+///
+/// let grammar = r#"
+/// Variable = '$' Name
+/// Name = 'ident'
+/// Type = Name | '[' Type ']'
+/// VariableDefinition = Variable ':' Type
+/// "#;
+/// let mut generator = GrammarGenerator::new(grammar).unwrap();
+///
+/// let mut u = Unstructured::new(&[1, 2, 3, 4, 5, 6, 7, 8]);
+/// let variable_definition = generator.generate(&mut u, "VariableDefinition").unwrap();
+/// assert!(variable_definition.starts_with("$ "));
+///
+/// let coverage = generator.coverage();
+/// assert!(coverage.percentage() > 0.0);
+/// ```
+#[derive(Debug)]
+pub struct GrammarGenerator {
+    nodes: Vec<GrammarNode>,
+    productions: Vec<String>,
+    counts: Vec<u64>,
+    max_depth: usize,
+}
+
+/// A node of the grammar, with its rule lowered to a `Production`
+#[derive(Debug)]
+struct GrammarNode {
+    name: String,
+    /// Index of the production counting how many times the node is generated
+    production: usize,
+    rule: Production,
+    /// The smallest depth of nested nodes needed to generate the node
+    min_depth: usize,
+}
+
+/// A rule of the grammar, with the indexes of the productions counting the choices it makes
+#[derive(Debug)]
+enum Production {
+    Node(usize),
+    Token(String),
+    Seq(Vec<Production>),
+    /// An alternative between productions, each counted on its own
+    Alt(Vec<(usize, Production)>),
+    /// An optional production, counted when it is present and when it is absent
+    Opt(usize, usize, Box<Production>),
+    /// A repeated production, counted when it has items and when it is empty
+    Rep(usize, usize, Box<Production>),
+}
+
+impl GrammarGenerator {
+    /// Create a new instance of `GrammarGenerator` from the source of an ungrammar grammar
+    pub fn new(grammar: &str) -> std::result::Result<Self, ungrammar::Error> {
+        let grammar: Grammar = grammar.parse()?;
+        let node_indexes: HashMap<ungrammar::Node, usize> = grammar
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node, index))
+            .collect();
+
+        let mut productions = Vec::new();
+        let mut nodes: Vec<GrammarNode> = grammar
+            .iter()
+            .map(|node| {
+                let name = grammar[node].name.clone();
+                let production = productions.len();
+                productions.push(name.clone());
+                let rule = lower(
+                    &grammar,
+                    &node_indexes,
+                    &name,
+                    &grammar[node].rule,
+                    &mut productions,
+                );
+                GrammarNode {
+                    name,
+                    production,
+                    rule,
+                    min_depth: usize::MAX,
+                }
+            })
+            .collect();
+
+        // The depths are lowered until they are all known, the nodes that cannot be generated
+        // without themselves keep an infinite depth
+        loop {
+            let mut changed = false;
+            for index in 0..nodes.len() {
+                let min_depth = min_depth(&nodes, &nodes[index].rule);
+                if min_depth < nodes[index].min_depth {
+                    nodes[index].min_depth = min_depth;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(Self {
+            nodes,
+            counts: vec![0; productions.len()],
+            productions,
+            max_depth: DEFAULT_MAX_DEPTH,
+        })
+    }
+
+    /// Set the maximum depth of nested nodes, 16 by default. Past this depth, optional and repeated
+    /// productions are left out, and the alternatives needing the fewest nested nodes are picked.
+    pub fn max_depth(&mut self, depth: usize) {
+        self.max_depth = depth;
+    }
+
+    /// Create arbitrary text for the node named `node`, with its tokens separated by spaces
+    ///
+    /// Fails if the grammar has no node named `node`, if the node cannot be generated without
+    /// itself, or if `u` runs out of data.
+    pub fn generate(
+        &mut self,
+        u: &mut Unstructured,
+        node: &str,
+    ) -> std::result::Result<String, GrammarError> {
+        let index = self
+            .nodes
+            .iter()
+            .position(|grammar_node| grammar_node.name == node)
+            .ok_or_else(|| GrammarError::UnknownNode(node.to_string()))?;
+        if self.nodes[index].min_depth == usize::MAX {
+            return Err(GrammarError::InfiniteNode(node.to_string()));
+        }
+
+        let mut generation = Generation {
+            nodes: &self.nodes,
+            counts: &mut self.counts,
+            max_depth: self.max_depth,
+            u,
+            tokens: Vec::new(),
+        };
+        generation.node(index, 0)?;
+
+        Ok(generation.tokens.join(" "))
+    }
+
+    /// The productions of the grammar generated so far
+    pub fn coverage(&self) -> GrammarCoverage {
+        GrammarCoverage {
+            productions: self
+                .productions
+                .iter()
+                .cloned()
+                .zip(self.counts.iter().copied())
+                .collect(),
+        }
+    }
+}
+
+/// An error generating text for a node with a `GrammarGenerator`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError {
+    /// The grammar has no node with this name
+    UnknownNode(String),
+    /// The node cannot be generated without itself, e.g. `List = '[' List ']'`
+    InfiniteNode(String),
+    /// The data could not be used to generate the node, e.g. it ran out
+    Arbitrary(arbitrary::Error),
+}
+
+impl From<arbitrary::Error> for GrammarError {
+    fn from(err: arbitrary::Error) -> Self {
+        Self::Arbitrary(err)
+    }
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownNode(node) => write!(f, "the grammar has no node named `{}`", node),
+            Self::InfiniteNode(node) => write!(f, "`{}` cannot be generated without itself", node),
+            Self::Arbitrary(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+/// The productions of a grammar generated by a `GrammarGenerator`: its nodes, the alternatives
+/// of its `|` rules, and its optional and repeated rules, with and without them.
+///
+/// Displaying it prints the coverage percentage and the productions never generated.
+#[derive(Debug, Clone)]
+pub struct GrammarCoverage {
+    productions: Vec<(String, u64)>,
+}
+
+impl GrammarCoverage {
+    /// The percentage of the productions of the grammar that were generated
+    pub fn percentage(&self) -> f64 {
+        if self.productions.is_empty() {
+            return 100.0;
+        }
+        let generated = self
+            .productions
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .count();
+
+        generated as f64 * 100.0 / self.productions.len() as f64
+    }
+
+    /// The productions of the grammar with the number of times they were generated, in the order
+    /// of the grammar
+    pub fn productions(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.productions
+            .iter()
+            .map(|(production, count)| (production.as_str(), *count))
+    }
+
+    /// The productions of the grammar that were never generated
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.productions()
+            .filter(|(_, count)| *count == 0)
+            .map(|(production, _)| production)
+    }
+}
+
+impl fmt::Display for GrammarCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let missing: Vec<&str> = self.missing().collect();
+        writeln!(
+            f,
+            "grammar coverage: {:.1}% ({} of {} productions)",
+            self.percentage(),
+            self.productions.len() - missing.len(),
+            self.productions.len()
+        )?;
+        if !missing.is_empty() {
+            writeln!(f, "never generated:")?;
+            for production in missing {
+                writeln!(f, "  {}", production)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The generation of the text of a node
+struct Generation<'g, 'a, 'b> {
+    nodes: &'g [GrammarNode],
+    counts: &'g mut [u64],
+    max_depth: usize,
+    u: &'a mut Unstructured<'b>,
+    tokens: Vec<String>,
+}
+
+impl<'g, 'a, 'b> Generation<'g, 'a, 'b> {
+    fn node(&mut self, index: usize, depth: usize) -> Result<()> {
+        let nodes = self.nodes;
+        self.counts[nodes[index].production] += 1;
+        self.production(&nodes[index].rule, depth + 1)
+    }
+
+    fn production(&mut self, production: &Production, depth: usize) -> Result<()> {
+        let nodes = self.nodes;
+        let is_too_deep = depth >= self.max_depth;
+        // Productions of nodes that cannot be generated without themselves are never picked
+        let is_finite = |production: &Production| min_depth(nodes, production) != usize::MAX;
+        match production {
+            Production::Node(index) => self.node(*index, depth)?,
+            Production::Token(token) => {
+                let token = self.token(token)?;
+                self.tokens.push(token);
+            }
+            Production::Seq(productions) => {
+                for production in productions {
+                    self.production(production, depth)?;
+                }
+            }
+            Production::Alt(alternatives) => {
+                let alternatives: Vec<&(usize, Production)> = alternatives
+                    .iter()
+                    .filter(|(_, alternative)| is_finite(alternative))
+                    .collect();
+                let (counted, alternative) = if is_too_deep {
+                    alternatives
+                        .iter()
+                        .min_by_key(|(_, alternative)| min_depth(nodes, alternative))
+                        .expect("nodes that can be generated have a finite alternative")
+                } else {
+                    self.u.choose(&alternatives)?
+                };
+                self.counts[*counted] += 1;
+                self.production(alternative, depth)?;
+            }
+            Production::Opt(present, absent, production) => {
+                if !is_too_deep && is_finite(production) && self.u.arbitrary()? {
+                    self.counts[*present] += 1;
+                    self.production(production, depth)?;
+                } else {
+                    self.counts[*absent] += 1;
+                }
+            }
+            Production::Rep(with_items, empty, production) => {
+                let count = if is_too_deep || !is_finite(production) {
+                    0
+                } else {
+                    self.u.int_in_range(0..=MAX_REPETITIONS)?
+                };
+                self.counts[if count > 0 { *with_items } else { *empty }] += 1;
+                for _ in 0..count {
+                    self.production(production, depth)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The text of a token: a name, a string or a number for the tokens standing for them, and
+    /// the token itself for punctuators and keywords
+    fn token(&mut self, token: &str) -> Result<String> {
+        let text = match token {
+            "ident" => {
+                let len = self.u.int_in_range(1..=8)?;
+                let mut name = String::with_capacity(len);
+                for index in 0..len {
+                    let ch = if index == 0 {
+                        *self
+                            .u
+                            .choose(b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_")?
+                    } else {
+                        *self.u.choose(
+                            b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_0123456789",
+                        )?
+                    };
+                    name.push(char::from(ch));
+                }
+                name
+            }
+            "string" => {
+                let len = self.u.int_in_range(0..=20)?;
+                let mut string = String::with_capacity(len + 2);
+                string.push('"');
+                for _ in 0..len {
+                    string.push(char::from(*self.u.choose(STRING_CHARSET)?));
+                }
+                string.push('"');
+                string
+            }
+            "int" => self.u.arbitrary::<i32>()?.to_string(),
+            "float" => format!(
+                "{}.{}",
+                self.u.arbitrary::<i32>()?,
+                self.u.int_in_range(0..=999_999u32)?
+            ),
+            token => token.to_string(),
+        };
+
+        Ok(text)
+    }
+}
+
+/// Lower the rule of the node `node` to a `Production`, adding the productions it counts to
+/// `productions`
+fn lower(
+    grammar: &Grammar,
+    node_indexes: &HashMap<ungrammar::Node, usize>,
+    node: &str,
+    rule: &Rule,
+    productions: &mut Vec<String>,
+) -> Production {
+    match rule {
+        Rule::Labeled { rule, .. } => lower(grammar, node_indexes, node, rule, productions),
+        Rule::Node(it) => Production::Node(node_indexes[it]),
+        Rule::Token(it) => Production::Token(grammar[*it].name.clone()),
+        Rule::Seq(rules) => Production::Seq(
+            rules
+                .iter()
+                .map(|rule| lower(grammar, node_indexes, node, rule, productions))
+                .collect(),
+        ),
+        Rule::Alt(rules) => Production::Alt(
+            rules
+                .iter()
+                .map(|rule| {
+                    let counted = productions.len();
+                    productions.push(format!("{} = {}", node, render(grammar, rule)));
+                    (
+                        counted,
+                        lower(grammar, node_indexes, node, rule, productions),
+                    )
+                })
+                .collect(),
+        ),
+        Rule::Opt(rule) => {
+            let rendered = render_nested(grammar, rule);
+            let present = productions.len();
+            productions.push(format!("{}: with {}?", node, rendered));
+            productions.push(format!("{}: without {}?", node, rendered));
+            let production = lower(grammar, node_indexes, node, rule, productions);
+            Production::Opt(present, present + 1, Box::new(production))
+        }
+        Rule::Rep(rule) => {
+            let rendered = render_nested(grammar, rule);
+            let with_items = productions.len();
+            productions.push(format!("{}: {}* with items", node, rendered));
+            productions.push(format!("{}: {}* empty", node, rendered));
+            let production = lower(grammar, node_indexes, node, rule, productions);
+            Production::Rep(with_items, with_items + 1, Box::new(production))
+        }
+    }
+}
+
+/// The smallest depth of nested nodes needed to generate `production`, with the depths of the
+/// nodes known so far
+fn min_depth(nodes: &[GrammarNode], production: &Production) -> usize {
+    match production {
+        Production::Node(index) => nodes[*index].min_depth.saturating_add(1),
+        Production::Token(_) | Production::Opt(..) | Production::Rep(..) => 0,
+        Production::Seq(productions) => productions
+            .iter()
+            .map(|production| min_depth(nodes, production))
+            .max()
+            .unwrap_or(0),
+        Production::Alt(alternatives) => alternatives
+            .iter()
+            .map(|(_, alternative)| min_depth(nodes, alternative))
+            .min()
+            .unwrap_or(0),
+    }
+}
+
+/// The rule as written in the grammar
+fn render(grammar: &Grammar, rule: &Rule) -> String {
+    match rule {
+        Rule::Labeled { label, rule } => format!("{}:{}", label, render(grammar, rule)),
+        Rule::Node(it) => grammar[*it].name.clone(),
+        Rule::Token(it) => format!("'{}'", grammar[*it].name),
+        Rule::Seq(rules) => rules
+            .iter()
+            .map(|rule| render_nested(grammar, rule))
+            .collect::<Vec<_>>()
+            .join(" "),
+        Rule::Alt(rules) => rules
+            .iter()
+            .map(|rule| render_nested(grammar, rule))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        Rule::Opt(rule) => format!("{}?", render_nested(grammar, rule)),
+        Rule::Rep(rule) => format!("{}*", render_nested(grammar, rule)),
+    }
+}
+
+/// The rule as written in the grammar, in parentheses if it is made of several rules
+fn render_nested(grammar: &Grammar, rule: &Rule) -> String {
+    match rule {
+        Rule::Seq(_) | Rule::Alt(_) => format!("({})", render(grammar, rule)),
+        _ => render(grammar, rule),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_parser::{ast::AstNode, Parser};
+
+    use super::*;
+    use crate::tests::data;
+
+    #[test]
+    fn it_generates_every_node_of_the_graphql_grammar() {
+        let mut generator = GrammarGenerator::new(include_str!("../../../graphql.ungram")).unwrap();
+        for seed in 0..1000 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let document = generator.generate(&mut u, "Document").unwrap();
+
+            // The grammar is less strict than GraphQL, but the parser reads any generated text
+            let tree = Parser::new(&document).parse();
+            assert_eq!(tree.document().syntax().to_string(), document);
+        }
+
+        let data = data(0);
+        let variable_definition = generator
+            .generate(&mut Unstructured::new(&data), "VariableDefinition")
+            .unwrap();
+        assert!(
+            variable_definition.starts_with("$ "),
+            "{}",
+            variable_definition
+        );
+
+        let coverage = generator.coverage();
+        assert_eq!(coverage.missing().count(), 0, "{}", coverage);
+        assert_eq!(coverage.percentage(), 100.0);
+    }
+
+    #[test]
+    fn it_fails_on_nodes_it_cannot_generate() {
+        let mut generator =
+            GrammarGenerator::new("Name = 'ident'\nList = '[' List ']'\nType = Name | List")
+                .unwrap();
+        let data = data(0);
+
+        assert_eq!(
+            generator.generate(&mut Unstructured::new(&data), "Value"),
+            Err(GrammarError::UnknownNode("Value".to_string()))
+        );
+        assert_eq!(
+            generator.generate(&mut Unstructured::new(&data), "List"),
+            Err(GrammarError::InfiniteNode("List".to_string()))
+        );
+        // Nodes with an alternative that cannot be generated pick the other ones
+        assert!(generator
+            .generate(&mut Unstructured::new(&data), "Type")
+            .is_ok());
+        assert_eq!(generator.coverage().productions().next(), Some(("Name", 1)));
+    }
+}
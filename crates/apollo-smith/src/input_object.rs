@@ -47,7 +47,58 @@ impl From<InputObjectTypeDef> for apollo_encoder::InputObjectDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InputObjectTypeDefinition> for InputObjectTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(
+        input_object_def: apollo_parser::ast::InputObjectTypeDefinition,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: input_object_def.name().try_into()?,
+            description: input_object_def.description().map(Description::from),
+            fields: Self::convert_fields(input_object_def.input_fields_definition())?,
+            directives: Directive::convert_directives(input_object_def.directives())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InputObjectTypeExtension> for InputObjectTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(
+        input_object_def: apollo_parser::ast::InputObjectTypeExtension,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: input_object_def.name().try_into()?,
+            description: None,
+            fields: Self::convert_fields(input_object_def.input_fields_definition())?,
+            directives: Directive::convert_directives(input_object_def.directives())?,
+            extend: true,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl InputObjectTypeDef {
+    fn convert_fields(
+        fields: Option<apollo_parser::ast::InputFieldsDefinition>,
+    ) -> Result<Vec<InputValueDef>, crate::FromError> {
+        fields
+            .map(|fields| {
+                fields
+                    .input_value_definitions()
+                    .map(InputValueDef::try_from)
+                    .collect()
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `InputObjectTypeDef`
     pub fn input_object_type_definition(&mut self) -> Result<InputObjectTypeDef> {
         let description = self
@@ -67,4 +118,29 @@ impl<'a> DocumentBuilder<'a> {
             fields,
         })
     }
+
+    /// Create an arbitrary `InputObjectTypeDef` named `name` for a valid schema.
+    ///
+    /// Its fields only reference the existing input object types, so input objects never reference
+    /// themselves.
+    pub(crate) fn valid_input_object_type_definition(
+        &mut self,
+        name: Name,
+    ) -> Result<InputObjectTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let fields = self.valid_input_values_def(1..=5)?;
+
+        Ok(InputObjectTypeDef {
+            description,
+            directives: Vec::new(),
+            name,
+            extend: false,
+            fields,
+        })
+    }
 }
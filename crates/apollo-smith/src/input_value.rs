@@ -1,5 +1,9 @@
 use crate::{description::Description, directive::Directive, name::Name, ty::Ty, DocumentBuilder};
 use arbitrary::Result;
+use std::ops::RangeInclusive;
+
+/// Depth after which lists are empty and optional fields of input objects are left out, since input objects can be recursive
+const MAX_INPUT_VALUE_DEPTH: usize = 3;
 
 #[derive(Debug, Clone, PartialEq)]
 
@@ -33,31 +37,81 @@ impl From<InputValue> for apollo_encoder::Value {
     }
 }
 
-impl From<InputValue> for String {
-    fn from(input_val: InputValue) -> Self {
-        match input_val {
-            InputValue::Variable(v) => format!("${}", String::from(v)),
-            InputValue::Int(i) => format!("{i}"),
-            InputValue::Float(f) => format!("{f}"),
-            InputValue::String(s) => s,
-            InputValue::Boolean(b) => format!("{b}"),
-            InputValue::Null => String::from("null"),
-            InputValue::Enum(val) => val.into(),
-            InputValue::List(list) => format!(
-                "[{}]",
-                list.into_iter()
-                    .map(String::from)
-                    .collect::<Vec<String>>()
-                    .join(", ")
+/// The JSON value of an input value, e.g. to provide it as a variable. Variables have no JSON
+/// value of their own and become `null`.
+impl From<InputValue> for serde_json::Value {
+    fn from(input_value: InputValue) -> Self {
+        match input_value {
+            InputValue::Variable(_) | InputValue::Null => Self::Null,
+            InputValue::Int(i) => Self::from(i),
+            InputValue::Float(f) => Self::from(f),
+            InputValue::String(s) => Self::String(s),
+            InputValue::Boolean(b) => Self::Bool(b),
+            InputValue::Enum(enm) => Self::String(enm.into()),
+            InputValue::List(l) => Self::Array(l.into_iter().map(Into::into).collect()),
+            InputValue::Object(o) => {
+                Self::Object(o.into_iter().map(|(n, i)| (n.into(), i.into())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Value> for InputValue {
+    type Error = crate::FromError;
+
+    fn try_from(value: apollo_parser::ast::Value) -> Result<Self, Self::Error> {
+        use apollo_parser::ast;
+
+        let value = match value {
+            ast::Value::Variable(variable) => Self::Variable(variable.name().try_into()?),
+            ast::Value::StringValue(string) => Self::String(string.into()),
+            ast::Value::FloatValue(float) => Self::Float(
+                float
+                    .float_token()
+                    .ok_or(crate::FromError::MissingField)?
+                    .text()
+                    .parse()
+                    .map_err(|_| crate::FromError::InvalidValue)?,
             ),
-            InputValue::Object(obj) => format!(
-                "{{ {} }}",
-                obj.into_iter()
-                    .map(|(k, v)| format!("{}: {}", String::from(k), String::from(v)))
-                    .collect::<Vec<String>>()
-                    .join(", ")
+            ast::Value::IntValue(int) => Self::Int(
+                int.int_token()
+                    .ok_or(crate::FromError::MissingField)?
+                    .text()
+                    .parse()
+                    .map_err(|_| crate::FromError::InvalidValue)?,
             ),
-        }
+            ast::Value::BooleanValue(boolean) => Self::Boolean(boolean.true_token().is_some()),
+            ast::Value::NullValue(_) => Self::Null,
+            ast::Value::EnumValue(enum_value) => Self::Enum(enum_value.name().try_into()?),
+            ast::Value::ListValue(list) => Self::List(
+                list.values()
+                    .map(Self::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            ast::Value::ObjectValue(object) => Self::Object(
+                object
+                    .object_fields()
+                    .map(|field| {
+                        Ok((
+                            field.name().try_into()?,
+                            field
+                                .value()
+                                .ok_or(crate::FromError::MissingField)?
+                                .try_into()?,
+                        ))
+                    })
+                    .collect::<Result<_, crate::FromError>>()?,
+            ),
+        };
+
+        Ok(value)
+    }
+}
+
+impl From<InputValue> for String {
+    fn from(input_val: InputValue) -> Self {
+        apollo_encoder::Value::from(input_val).to_string()
     }
 }
 
@@ -104,16 +158,41 @@ impl From<InputValueDef> for apollo_encoder::InputField {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InputValueDefinition> for InputValueDef {
+    type Error = crate::FromError;
+
+    fn try_from(input_val: apollo_parser::ast::InputValueDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: input_val.description().map(Description::from),
+            name: input_val.name().try_into()?,
+            ty: input_val
+                .ty()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+            default_value: input_val
+                .default_value()
+                .and_then(|default| default.value())
+                .map(InputValue::try_from)
+                .transpose()?,
+            directives: Directive::convert_directives(input_val.directives())?,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `InputValue`
     pub fn input_value(&mut self) -> Result<InputValue> {
         let val = match self.u.int_in_range(0..=8usize)? {
             // Int
             0 => InputValue::Int(self.u.arbitrary()?),
             // Float
-            1 => InputValue::Float(self.u.arbitrary()?),
+            1 => {
+                let float: f64 = self.u.arbitrary()?;
+                InputValue::Float(if float.is_finite() { float } else { 0.0 })
+            }
             // String
-            2 => InputValue::String(self.limited_string(40)?),
+            2 => InputValue::String(self.string_value()?),
             // Boolean
             3 => InputValue::Boolean(self.u.arbitrary()?),
             // Null
@@ -151,10 +230,107 @@ impl<'a> DocumentBuilder<'a> {
         Ok(val)
     }
 
+    /// Create an arbitrary `InputValue` of the type `ty`, using the existing enum and input object types.
+    ///
+    /// When generating an operation for a schema, a variable of type `ty` is sometimes declared and used instead of a literal.
+    pub fn input_value_for_type(&mut self, ty: &Ty) -> Result<InputValue> {
+        if self.operation_variables.is_some() && self.u.int_in_range(0..=3usize)? == 0 {
+            if let Some(name) = self.declare_variable(ty)? {
+                return Ok(InputValue::Variable(name));
+            }
+        }
+
+        self.literal_for_type(ty, 0)
+    }
+
+    pub(crate) fn literal_for_type(&mut self, ty: &Ty, depth: usize) -> Result<InputValue> {
+        let (ty, is_nullable) = match ty {
+            Ty::NonNull(inner) => (inner.as_ref(), false),
+            _ => (ty, true),
+        };
+        if is_nullable && self.u.int_in_range(0..=5usize)? == 0 {
+            return Ok(InputValue::Null);
+        }
+
+        let name = match ty {
+            // Input objects can reference themselves through lists, which may be empty
+            Ty::List(item_ty) => {
+                let max_items = if depth < MAX_INPUT_VALUE_DEPTH { 3 } else { 0 };
+                return Ok(InputValue::List(
+                    (0..self.u.int_in_range(0..=max_items)?)
+                        .map(|_| self.literal_for_type(item_ty, depth + 1))
+                        .collect::<Result<Vec<_>>>()?,
+                ));
+            }
+            Ty::NonNull(inner) => return self.literal_for_type(inner, depth),
+            Ty::Named(name) => name,
+        };
+
+        let val = match name.name.as_str() {
+            "Int" => InputValue::Int(self.u.arbitrary::<i32>()?.into()),
+            "Float" => {
+                let float: f64 = self.u.arbitrary()?;
+                InputValue::Float(if float.is_finite() { float } else { 0.0 })
+            }
+            "String" => InputValue::String(self.string_value()?),
+            "Boolean" => InputValue::Boolean(self.u.arbitrary()?),
+            "ID" => {
+                if self.u.arbitrary()? {
+                    InputValue::Int(self.u.arbitrary::<i32>()?.into())
+                } else {
+                    InputValue::String(self.string_value()?)
+                }
+            }
+            _ => {
+                let enum_values: Vec<Name> = self
+                    .enum_type_defs
+                    .iter()
+                    .filter(|enum_| &enum_.name == name)
+                    .flat_map(|enum_| enum_.enum_values_def.iter().map(|v| v.value.clone()))
+                    .collect();
+                let input_fields: Vec<InputValueDef> = self
+                    .input_object_type_defs
+                    .iter()
+                    .filter(|input_object| &input_object.name == name)
+                    .flat_map(|input_object| input_object.fields.iter().cloned())
+                    .collect();
+
+                if !enum_values.is_empty() {
+                    InputValue::Enum(self.u.choose(&enum_values)?.clone())
+                } else if !input_fields.is_empty() {
+                    let mut fields = Vec::with_capacity(input_fields.len());
+                    for field in input_fields {
+                        let is_required = field.ty.is_non_null() && field.default_value.is_none();
+                        if is_required
+                            || (depth < MAX_INPUT_VALUE_DEPTH
+                                && self.u.arbitrary().unwrap_or(false))
+                        {
+                            fields.push((field.name, self.literal_for_type(&field.ty, depth + 1)?));
+                        }
+                    }
+                    InputValue::Object(fields)
+                } else {
+                    // Custom scalars accept any value, strings are the most common
+                    InputValue::String(self.string_value()?)
+                }
+            }
+        };
+
+        Ok(val)
+    }
+
     /// Create an arbitrary list of `InputValueDef`
     pub fn input_values_def(&mut self) -> Result<Vec<InputValueDef>> {
-        let arbitrary_iv_num = self.u.int_in_range(2..=5usize)?;
-        let mut input_values = Vec::with_capacity(arbitrary_iv_num - 1);
+        self.input_values_def_in_range(2..=5)
+    }
+
+    /// Create an arbitrary list of `InputValueDef` whose length is in `range`
+    pub(crate) fn input_values_def_in_range(
+        &mut self,
+        range: RangeInclusive<usize>,
+    ) -> Result<Vec<InputValueDef>> {
+        let arbitrary_iv_num = self.u.int_in_range(range)?;
+        let mut input_values = Vec::with_capacity(arbitrary_iv_num);
 
         for i in 0..arbitrary_iv_num {
             let description = self
@@ -185,6 +361,38 @@ impl<'a> DocumentBuilder<'a> {
 
         Ok(input_values)
     }
+    /// Create an arbitrary list of `InputValueDef` of existing input types for a valid schema,
+    /// whose length is in `range`
+    pub(crate) fn valid_input_values_def(
+        &mut self,
+        range: RangeInclusive<usize>,
+    ) -> Result<Vec<InputValueDef>> {
+        let input_types = self.list_existing_input_types();
+        (0..self.u.int_in_range(range)?)
+            .map(|i| {
+                let description = self
+                    .u
+                    .arbitrary()
+                    .unwrap_or(false)
+                    .then(|| self.description())
+                    .transpose()?;
+                let name = self.unique_name(i)?;
+                let ty = self.choose_ty(&input_types)?;
+                let default_value = (self.u.int_in_range(0..=3usize)? == 0)
+                    .then(|| self.input_value_for_type(&ty))
+                    .transpose()?;
+
+                Ok(InputValueDef {
+                    description,
+                    name,
+                    ty,
+                    default_value,
+                    directives: Vec::new(),
+                })
+            })
+            .collect()
+    }
+
     /// Create an arbitrary `InputValueDef`
     pub fn input_value_def(&mut self) -> Result<InputValueDef> {
         let description = self
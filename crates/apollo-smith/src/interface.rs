@@ -1,10 +1,10 @@
-use std::collections::HashSet;
-
 use apollo_encoder::InterfaceDefinition;
 use arbitrary::Result;
+use indexmap::IndexSet;
 
 use crate::{
-    description::Description, directive::Directive, field::FieldDef, name::Name, DocumentBuilder,
+    description::Description, directive::Directive, field::FieldDef, name::Name, ty::Ty,
+    DocumentBuilder,
 };
 
 /// InterfaceTypeDef is an abstract type where there are common fields declared.
@@ -21,7 +21,7 @@ use crate::{
 pub struct InterfaceTypeDef {
     pub(crate) description: Option<Description>,
     pub(crate) name: Name,
-    pub(crate) interfaces: HashSet<Name>,
+    pub(crate) interfaces: IndexSet<Name>,
     pub(crate) directives: Vec<Directive>,
     pub(crate) fields_def: Vec<FieldDef>,
     pub(crate) extend: bool,
@@ -48,7 +48,57 @@ impl From<InterfaceTypeDef> for InterfaceDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InterfaceTypeDefinition> for InterfaceTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(itf_def: apollo_parser::ast::InterfaceTypeDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: itf_def.description().map(Description::from),
+            name: itf_def.name().try_into()?,
+            interfaces: Self::convert_interfaces(itf_def.implements_interfaces())?,
+            directives: Directive::convert_directives(itf_def.directives())?,
+            fields_def: FieldDef::convert_fields(itf_def.fields_definition())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::InterfaceTypeExtension> for InterfaceTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(itf_def: apollo_parser::ast::InterfaceTypeExtension) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: None,
+            name: itf_def.name().try_into()?,
+            interfaces: Self::convert_interfaces(itf_def.implements_interfaces())?,
+            directives: Directive::convert_directives(itf_def.directives())?,
+            fields_def: FieldDef::convert_fields(itf_def.fields_definition())?,
+            extend: true,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl InterfaceTypeDef {
+    /// Convert the interfaces implemented by an `apollo-parser` AST node, if it implements any
+    pub(crate) fn convert_interfaces(
+        interfaces: Option<apollo_parser::ast::ImplementsInterfaces>,
+    ) -> Result<IndexSet<Name>, crate::FromError> {
+        interfaces
+            .map(|interfaces| {
+                interfaces
+                    .named_types()
+                    .map(|named| named.name().try_into())
+                    .collect()
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `InterfaceTypeDef`
     pub fn interface_type_definition(&mut self) -> Result<InterfaceTypeDef> {
         let description = self
@@ -72,16 +122,16 @@ impl<'a> DocumentBuilder<'a> {
         })
     }
 
-    /// Create an arbitrary `HashSet` of implemented interfaces
-    pub fn implements_interfaces(&mut self) -> Result<HashSet<Name>> {
+    /// Create an arbitrary `IndexSet` of implemented interfaces
+    pub fn implements_interfaces(&mut self) -> Result<IndexSet<Name>> {
         if self.interface_type_defs.is_empty() {
-            return Ok(HashSet::new());
+            return Ok(IndexSet::new());
         }
 
         let num_itf = self
             .u
             .int_in_range(0..=(self.interface_type_defs.len() - 1))?;
-        let mut interface_impls = HashSet::with_capacity(num_itf);
+        let mut interface_impls = IndexSet::with_capacity(num_itf);
 
         for _ in 0..num_itf {
             interface_impls.insert(self.u.choose(&self.interface_type_defs)?.name.clone());
@@ -89,4 +139,68 @@ impl<'a> DocumentBuilder<'a> {
 
         Ok(interface_impls)
     }
+
+    /// Create an arbitrary `InterfaceTypeDef` named `name` for a valid schema, with fields of
+    /// `output_types`. It only implements existing interfaces, so interfaces never implement
+    /// themselves.
+    pub(crate) fn valid_interface_type_definition(
+        &mut self,
+        name: Name,
+        output_types: &[Ty],
+    ) -> Result<InterfaceTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let (interfaces, mut fields_def) = self.valid_implements_interfaces()?;
+        let new_fields_def = self.valid_fields_definition(output_types, &fields_def)?;
+        fields_def.extend(new_fields_def);
+
+        Ok(InterfaceTypeDef {
+            description,
+            name,
+            interfaces,
+            directives: Vec::new(),
+            fields_def,
+            extend: false,
+        })
+    }
+
+    /// Choose arbitrary existing interfaces to implement in a valid schema, along with the
+    /// interfaces they implement, and create the fields they require: the same fields with the
+    /// same arguments, and types that are subtypes of the interface field types.
+    ///
+    /// Interfaces defining fields with the same name are never implemented together, since the
+    /// types of these fields may not have a common subtype.
+    pub(crate) fn valid_implements_interfaces(
+        &mut self,
+    ) -> Result<(IndexSet<Name>, Vec<FieldDef>)> {
+        let mut interfaces = IndexSet::new();
+        let mut fields_def: Vec<FieldDef> = Vec::new();
+        if self.interface_type_defs.is_empty() {
+            return Ok((interfaces, fields_def));
+        }
+
+        for _ in 0..self.u.int_in_range(0..=2usize)? {
+            // The fields of an interface include the fields of the interfaces it implements
+            let itf = self.u.choose(&self.interface_type_defs)?.clone();
+            if itf.fields_def.iter().any(|field| {
+                fields_def
+                    .iter()
+                    .any(|required| required.name == field.name)
+            }) {
+                continue;
+            }
+            interfaces.insert(itf.name);
+            interfaces.extend(itf.interfaces);
+            for mut field_def in itf.fields_def {
+                field_def.ty = self.subtype(&field_def.ty)?;
+                fields_def.push(field_def);
+            }
+        }
+
+        Ok((interfaces, fields_def))
+    }
 }
@@ -1,18 +1,25 @@
 #![doc = include_str!("../README.md")]
 
 pub(crate) mod argument;
+pub(crate) mod config;
 pub(crate) mod description;
 pub(crate) mod directive;
 pub(crate) mod document;
 pub(crate) mod enum_;
 pub(crate) mod field;
 pub(crate) mod fragment;
+#[cfg(feature = "grammar")]
+pub(crate) mod grammar;
 pub(crate) mod input_object;
 pub(crate) mod input_value;
 pub(crate) mod interface;
+pub(crate) mod malformation;
 pub(crate) mod name;
 pub(crate) mod object;
 pub(crate) mod operation;
+#[cfg(feature = "parser-impl")]
+pub(crate) mod reduce;
+pub(crate) mod response;
 pub(crate) mod scalar;
 pub(crate) mod schema;
 pub(crate) mod selection_set;
@@ -22,15 +29,25 @@ pub(crate) mod variable;
 
 use arbitrary::Unstructured;
 
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use crate::{name::Name, ty::Ty};
+
 pub use arbitrary::Result;
+pub use config::{Config, DefinitionKind, StringEdgeCase};
 pub use directive::DirectiveDef;
 pub use document::Document;
 pub use enum_::EnumTypeDef;
 pub use fragment::FragmentDef;
+#[cfg(feature = "grammar")]
+pub use grammar::{GrammarCoverage, GrammarError, GrammarGenerator};
 pub use input_object::InputObjectTypeDef;
 pub use interface::InterfaceTypeDef;
+pub use malformation::Malformation;
 pub use object::ObjectTypeDef;
 pub use operation::OperationDef;
+#[cfg(feature = "parser-impl")]
+pub use reduce::reduce;
 pub use scalar::ScalarTypeDef;
 pub use schema::SchemaDef;
 pub use union::UnionTypeDef;
@@ -54,8 +71,8 @@ pub use union::UnionTypeDef;
 ///     // Your code here...
 /// });
 /// ```
-pub struct DocumentBuilder<'a> {
-    pub(crate) u: &'a mut Unstructured<'a>,
+pub struct DocumentBuilder<'a, 'b> {
+    pub(crate) u: &'a mut Unstructured<'b>,
     pub(crate) input_object_type_defs: Vec<InputObjectTypeDef>,
     pub(crate) object_type_defs: Vec<ObjectTypeDef>,
     pub(crate) interface_type_defs: Vec<InterfaceTypeDef>,
@@ -66,71 +83,131 @@ pub struct DocumentBuilder<'a> {
     pub(crate) directive_defs: Vec<DirectiveDef>,
     pub(crate) operation_defs: Vec<OperationDef>,
     pub(crate) fragment_defs: Vec<FragmentDef>,
+    /// Variables declared by the operation being generated for a schema, `None` outside of such an operation
+    pub(crate) operation_variables: Option<Vec<variable::VariableDef>>,
+    /// Number of aliases generated for fields with arguments, to keep them unique
+    pub(crate) alias_count: usize,
+    /// Number of arbitrary selection sets being generated, nested in each other
+    pub(crate) selection_depth: usize,
+    /// Whether a string with a CRLF line ending was generated, so that the document is printed
+    /// with CRLF line endings
+    pub(crate) crlf_line_endings: bool,
+    pub(crate) config: Config,
 }
 
-impl<'a> DocumentBuilder<'a> {
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an instance of `DocumentBuilder`
-    pub fn new(u: &'a mut Unstructured<'a>) -> Result<Self> {
-        let mut builder = Self {
-            u,
-            object_type_defs: Vec::new(),
-            interface_type_defs: Vec::new(),
-            enum_type_defs: Vec::new(),
-            schema_defs: Vec::new(),
-            directive_defs: Vec::new(),
-            operation_defs: Vec::new(),
-            fragment_defs: Vec::new(),
-            scalar_type_defs: Vec::new(),
-            union_type_defs: Vec::new(),
-            input_object_type_defs: Vec::new(),
-        };
+    pub fn new(u: &'a mut Unstructured<'b>) -> Result<Self> {
+        Self::with_config(u, Config::default())
+    }
+
+    /// Create an instance of `DocumentBuilder` whose document has the size and shape set by `config`
+    ///
+    /// ```rust
+    /// use apollo_smith::{Config, DefinitionKind, DocumentBuilder};
+    /// use arbitrary::Unstructured;
+    ///
+    /// let data = [42; 1024];
+    /// let mut u = Unstructured::new(&data);
+    /// let mut config = Config::default();
+    /// config.definition_count(DefinitionKind::Object, 1, 5);
+    /// config.disable(DefinitionKind::Operation);
+    /// config.disable(DefinitionKind::Fragment);
+    /// let gql_doc = DocumentBuilder::with_config(&mut u, config);
+    /// ```
+    pub fn with_config(u: &'a mut Unstructured<'b>, config: Config) -> Result<Self> {
+        let mut builder = Self::empty(u, config);
+
+        if builder.config.generates_valid_schema() {
+            builder.add_valid_type_system_definitions()?;
+            let fragment_count = builder.config.definition_count_of(DefinitionKind::Fragment);
+            let operation_count = builder
+                .config
+                .definition_count_of(DefinitionKind::Operation);
+            builder.add_executable_definitions_for_schema(fragment_count, operation_count)?;
+            return Ok(builder);
+        }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Fragment))?
+        {
             let fragment_def = builder.fragment_definition()?;
             builder.fragment_defs.push(fragment_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Scalar))?
+        {
             let scalar_type_def = builder.scalar_type_definition()?;
             builder.scalar_type_defs.push(scalar_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Enum))?
+        {
             let enum_type_def = builder.enum_type_definition()?;
             builder.enum_type_defs.push(enum_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder.u.int_in_range(
+            builder
+                .config
+                .definition_count_of(DefinitionKind::Interface),
+        )? {
             let interface_type_def = builder.interface_type_definition()?;
             builder.interface_type_defs.push(interface_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Object))?
+        {
             let object_type_def = builder.object_type_definition()?;
             builder.object_type_defs.push(object_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Union))?
+        {
             let union_type_def = builder.union_type_definition()?;
             builder.union_type_defs.push(union_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder.u.int_in_range(
+            builder
+                .config
+                .definition_count_of(DefinitionKind::InputObject),
+        )? {
             let input_object_type_def = builder.input_object_type_definition()?;
             builder.input_object_type_defs.push(input_object_type_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder
+            .u
+            .int_in_range(builder.config.definition_count_of(DefinitionKind::Schema))?
+        {
             let schema_def = builder.schema_definition()?;
             builder.schema_defs.push(schema_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder.u.int_in_range(
+            builder
+                .config
+                .definition_count_of(DefinitionKind::Directive),
+        )? {
             let directive_def = builder.directive_def()?;
             builder.directive_defs.push(directive_def);
         }
 
-        for _ in 0..builder.u.int_in_range(1..=50)? {
+        for _ in 0..builder.u.int_in_range(
+            builder
+                .config
+                .definition_count_of(DefinitionKind::Operation),
+        )? {
             let operation_def = builder.operation_definition()?;
             builder.operation_defs.push(operation_def);
         }
@@ -138,19 +215,514 @@ impl<'a> DocumentBuilder<'a> {
         Ok(builder)
     }
 
+    /// Create an instance of `DocumentBuilder` generating operations for an existing schema
+    ///
+    /// The type system definitions and extensions of `document` are used as the schema, its
+    /// executable definitions are ignored. Instead of arbitrary definitions, the builder generates
+    /// operations and fragments that are valid against the schema: they select existing fields
+    /// with arguments of the right types, use fragments on possible types only, and declare the
+    /// variables they use.
+    ///
+    /// ```compile_fail
+    /// // fuzz/fuzz_targets/my_apollo_smith_fuzz_target.rs
+    /// #![no_main]
+    ///
+    /// use libfuzzer_sys::fuzz_target;
+    /// use arbitrary::Unstructured;
+    /// use apollo_parser::Parser;
+    /// use apollo_smith::DocumentBuilder;
+    ///
+    /// fuzz_target!(|input: &[u8]| {
+    ///     let schema = Parser::new(include_str!("schema.graphql")).parse().document();
+    ///     let mut u = Unstructured::new(input);
+    ///     let gql_doc = DocumentBuilder::with_document(&mut u, &schema)?;
+    ///     let operations = String::from(gql_doc.finish_executable());
+    ///
+    ///     // Execute the operations against the schema...
+    /// });
+    /// ```
+    #[cfg(feature = "parser-impl")]
+    pub fn with_document(
+        u: &'a mut Unstructured<'b>,
+        document: &apollo_parser::ast::Document,
+    ) -> Result<Self> {
+        let mut builder = Self::empty(u, Config::default());
+        builder
+            .add_type_system_definitions(document)
+            .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+        builder.add_executable_definitions_for_schema(0..=5, 1..=5)?;
+
+        Ok(builder)
+    }
+
+    /// Generate operations and fragments that are valid against the type system definitions
+    fn add_executable_definitions_for_schema(
+        &mut self,
+        fragment_count: RangeInclusive<usize>,
+        operation_count: RangeInclusive<usize>,
+    ) -> Result<()> {
+        for _ in 0..self.u.int_in_range(fragment_count)? {
+            if let Some(fragment_def) = self.fragment_definition_for_schema()? {
+                self.fragment_defs.push(fragment_def);
+            }
+        }
+
+        for _ in 0..self.u.int_in_range(operation_count)? {
+            if let Some(operation_def) = self.operation_definition_for_schema()? {
+                self.operation_defs.push(operation_def);
+            }
+        }
+
+        // Unused fragments are invalid
+        let mut used_fragments = HashSet::new();
+        for operation_def in &self.operation_defs {
+            operation_def
+                .selection_set
+                .fragment_spread_names(&mut used_fragments);
+        }
+        self.fragment_defs
+            .retain(|fragment_def| used_fragments.contains(&fragment_def.name));
+
+        Ok(())
+    }
+
+    /// Generate the type system definitions of a valid schema, see [`Config::valid_schema`]
+    fn add_valid_type_system_definitions(&mut self) -> Result<()> {
+        // Types can reference each other, so all the type names are chosen first
+        let mut type_count = 0;
+        let scalars = self.valid_type_names(DefinitionKind::Scalar, &mut type_count)?;
+        let enums = self.valid_type_names(DefinitionKind::Enum, &mut type_count)?;
+        let input_objects = self.valid_type_names(DefinitionKind::InputObject, &mut type_count)?;
+        let interfaces = self.valid_type_names(DefinitionKind::Interface, &mut type_count)?;
+        let mut objects = self.valid_type_names(DefinitionKind::Object, &mut type_count)?;
+        let unions = self.valid_type_names(DefinitionKind::Union, &mut type_count)?;
+        // A schema has a query root operation type, which is an object type
+        if objects.is_empty() {
+            objects.push(self.unique_name(type_count + 1)?);
+        }
+        let has_schema_def = self
+            .u
+            .int_in_range(self.config.definition_count_of(DefinitionKind::Schema))?
+            > 0;
+        if !has_schema_def {
+            objects[0] = Name::new(String::from("Query"));
+        }
+        let output_types: Vec<Ty> = scalars
+            .iter()
+            .chain(&enums)
+            .chain(&interfaces)
+            .chain(&objects)
+            .chain(&unions)
+            .map(|name| Ty::Named(name.clone()))
+            .collect();
+
+        for name in scalars {
+            let scalar_type_def = self.valid_scalar_type_definition(name)?;
+            self.scalar_type_defs.push(scalar_type_def);
+        }
+        for name in enums {
+            let enum_type_def = self.valid_enum_type_definition(name)?;
+            self.enum_type_defs.push(enum_type_def);
+        }
+        // Input objects only reference the ones created before them
+        for name in input_objects {
+            let input_object_type_def = self.valid_input_object_type_definition(name)?;
+            self.input_object_type_defs.push(input_object_type_def);
+        }
+        for name in unions {
+            let union_type_def = self.valid_union_type_definition(name, &objects)?;
+            self.union_type_defs.push(union_type_def);
+        }
+        // Interfaces only implement the ones created before them
+        for name in interfaces {
+            let interface_type_def = self.valid_interface_type_definition(name, &output_types)?;
+            self.interface_type_defs.push(interface_type_def);
+        }
+        for name in objects {
+            let object_type_def = self.valid_object_type_definition(name, &output_types)?;
+            self.object_type_defs.push(object_type_def);
+        }
+        for directive_name in self.valid_type_names(DefinitionKind::Directive, &mut 0)? {
+            let directive_def = self.valid_directive_def(directive_name)?;
+            self.directive_defs.push(directive_def);
+        }
+        if has_schema_def {
+            if let Some(schema_def) = self.valid_schema_definition()? {
+                self.schema_defs.push(schema_def);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create unique names for the definitions of `kind` of a valid schema, `count` being the
+    /// number of names already created
+    fn valid_type_names(&mut self, kind: DefinitionKind, count: &mut usize) -> Result<Vec<Name>> {
+        (0..self.u.int_in_range(self.config.definition_count_of(kind))?)
+            .map(|_| {
+                *count += 1;
+                self.unique_name(*count)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "parser-impl")]
+    fn add_type_system_definitions(
+        &mut self,
+        document: &apollo_parser::ast::Document,
+    ) -> std::result::Result<(), FromError> {
+        let document = Document::try_from(document.clone())?;
+        self.schema_defs = document.schema_definitions;
+        self.scalar_type_defs = document.scalar_type_definitions;
+        self.object_type_defs = document.object_type_definitions;
+        self.interface_type_defs = document.interface_type_definitions;
+        self.union_type_defs = document.union_type_definitions;
+        self.enum_type_defs = document.enum_type_definitions;
+        self.input_object_type_defs = document.input_object_type_definitions;
+        self.directive_defs = document.directive_definitions;
+
+        Ok(())
+    }
+
+    fn empty(u: &'a mut Unstructured<'b>, config: Config) -> Self {
+        Self {
+            u,
+            object_type_defs: Vec::new(),
+            interface_type_defs: Vec::new(),
+            enum_type_defs: Vec::new(),
+            schema_defs: Vec::new(),
+            directive_defs: Vec::new(),
+            operation_defs: Vec::new(),
+            fragment_defs: Vec::new(),
+            scalar_type_defs: Vec::new(),
+            union_type_defs: Vec::new(),
+            input_object_type_defs: Vec::new(),
+            operation_variables: None,
+            alias_count: 0,
+            selection_depth: 0,
+            crlf_line_endings: false,
+            config,
+        }
+    }
+
+    /// The operations generated so far, e.g. to generate their variables with
+    /// [`DocumentBuilder::variables_for_operation`] and their responses with
+    /// [`DocumentBuilder::response_for_operation`]
+    pub fn operation_definitions(&self) -> &[OperationDef] {
+        &self.operation_defs
+    }
+
     /// Convert a `DocumentBuilder` into a GraphQL `Document`
-    pub fn finish(self) -> Document {
+    pub fn finish(mut self) -> Document {
+        self.take_document()
+    }
+
+    /// Move the generated definitions into a GraphQL `Document`, leaving the builder empty
+    pub(crate) fn take_document(&mut self) -> Document {
+        Document {
+            schema_definitions: std::mem::take(&mut self.schema_defs),
+            object_type_definitions: std::mem::take(&mut self.object_type_defs),
+            interface_type_definitions: std::mem::take(&mut self.interface_type_defs),
+            enum_type_definitions: std::mem::take(&mut self.enum_type_defs),
+            directive_definitions: std::mem::take(&mut self.directive_defs),
+            operation_definitions: std::mem::take(&mut self.operation_defs),
+            fragment_definitions: std::mem::take(&mut self.fragment_defs),
+            scalar_type_definitions: std::mem::take(&mut self.scalar_type_defs),
+            union_type_definitions: std::mem::take(&mut self.union_type_defs),
+            input_object_type_definitions: std::mem::take(&mut self.input_object_type_defs),
+            crlf_line_endings: self.crlf_line_endings,
+        }
+    }
+
+    /// Convert a `DocumentBuilder` into a GraphQL `Document` containing only its operation and
+    /// fragment definitions, e.g. the operations generated by [`DocumentBuilder::with_document`]
+    pub fn finish_executable(self) -> Document {
         Document {
-            schema_definitions: self.schema_defs,
-            object_type_definitions: self.object_type_defs,
-            interface_type_definitions: self.interface_type_defs,
-            enum_type_definitions: self.enum_type_defs,
-            directive_definitions: self.directive_defs,
             operation_definitions: self.operation_defs,
             fragment_definitions: self.fragment_defs,
-            scalar_type_definitions: self.scalar_type_defs,
-            union_type_definitions: self.union_type_defs,
-            input_object_type_definitions: self.input_object_type_defs,
+            schema_definitions: Vec::new(),
+            object_type_definitions: Vec::new(),
+            interface_type_definitions: Vec::new(),
+            enum_type_definitions: Vec::new(),
+            directive_definitions: Vec::new(),
+            scalar_type_definitions: Vec::new(),
+            union_type_definitions: Vec::new(),
+            input_object_type_definitions: Vec::new(),
+            crlf_line_endings: self.crlf_line_endings,
+        }
+    }
+}
+
+/// An error converting an `apollo-parser` AST node into an `apollo-smith` definition
+#[cfg(feature = "parser-impl")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromError {
+    /// A part of the node is missing, e.g. the name of a type definition in an invalid document
+    MissingField,
+    /// A value cannot be represented, e.g. an integer that does not fit in an `i64`
+    InvalidValue,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use apollo_compiler::{Type, TypeDefinition};
+    use apollo_parser::{ast, Parser};
+
+    use super::*;
+
+    /// A schema with every kind of type, arguments, lists and non-null types, and an interface
+    /// without implementations
+    #[cfg(feature = "parser-impl")]
+    pub(crate) const SCHEMA: &str = r#"
+schema { query: Query mutation: Mutation subscription: Subscription }
+type Query {
+  node(id: ID!): Node
+  search(text: String, filter: Filter, first: Int = 10): [SearchResult!]!
+  user(id: ID!): User
+  users(ids: [ID!]!, role: Role): [User]
+  me: User!
+  draft: Draft
+  requiredDraft: Draft!
+  drafts: [Draft!]!
+}
+type Mutation {
+  rename(id: ID!, name: String!): User
+  setRole(input: RoleInput!): Boolean
+}
+type Subscription { userChanged(id: ID): User tick: Int! }
+interface Node { id: ID! }
+interface Named { name: String }
+interface Draft { title: String }
+type User implements Node & Named {
+  id: ID!
+  name: String
+  friends(first: Int): [User!]
+  role: Role
+  best: User
+}
+type Post implements Node { id: ID! title: String! author: User! tags: [String] }
+union SearchResult = User | Post
+enum Role { ADMIN MEMBER GUEST }
+input Filter { role: Role and: [Filter!] not: Filter text: String! }
+input RoleInput { id: ID! role: Role = MEMBER }
+scalar Date
+extend type Post { date: Date score: Float }
+"#;
+
+    /// Pseudo-random data to generate documents from, the same for the same seed
+    pub(crate) fn data(seed: u64) -> Vec<u8> {
+        document::seeded_data(seed, 64 * 1024)
+    }
+
+    /// The syntax errors, schema errors and validation diagnostics of `document`
+    pub(crate) fn errors(document: &str) -> Vec<String> {
+        let tree = Parser::new(document).parse();
+        let mut errors: Vec<String> = tree.errors().map(|err| err.message().to_string()).collect();
+        let document = tree.document();
+        let schema = apollo_compiler::Schema::new(&document);
+        errors.extend(
+            schema
+                .errors()
+                .iter()
+                .chain(&apollo_compiler::validate(&schema, &document))
+                .map(|diagnostic| diagnostic.message().to_string()),
+        );
+        errors
+    }
+
+    /// A config generating up to `max_definitions` definitions of every kind, with shallow
+    /// selection sets and short names, so that most data is enough to generate a document
+    pub(crate) fn small_config(max_definitions: usize) -> Config {
+        let mut config = Config::default();
+        for kind in DefinitionKind::ALL {
+            config.definition_count(kind, 0, max_definitions);
+        }
+        config.max_selection_depth(2);
+        config.max_name_length(4);
+        config
+    }
+
+    /// The type system rules broken by the schema of `document` that apollo-compiler does not check
+    fn type_system_errors(document: &str) -> Vec<String> {
+        let document = Parser::new(document).parse().document();
+        let schema = apollo_compiler::Schema::new(&document);
+        let mut errors = Vec::new();
+        let has_kind = |ty: &Type, is_kind: fn(&TypeDefinition) -> bool| {
+            schema.type_definition(ty.name()).map_or(false, is_kind)
+        };
+        let check_arguments =
+            |errors: &mut Vec<String>,
+             arguments: &[apollo_compiler::schema::InputValueDefinition]| {
+                for argument in arguments {
+                    if !has_kind(argument.ty(), TypeDefinition::is_input) {
+                        errors.push(format!("`{}` is not an input type", argument.ty()));
+                    }
+                }
+            };
+
+        for ty in schema.types().filter(|ty| !ty.is_built_in()) {
+            for field in ty.fields() {
+                if !has_kind(field.ty(), TypeDefinition::is_output) {
+                    errors.push(format!("`{}` is not an output type", field.ty()));
+                }
+                check_arguments(&mut errors, field.arguments());
+            }
+            let interfaces = match ty {
+                TypeDefinition::Object(object) => object.implements_interfaces(),
+                TypeDefinition::Interface(interface) => interface.implements_interfaces(),
+                TypeDefinition::Union(union_) => {
+                    for member in union_.members() {
+                        if !matches!(
+                            schema.type_definition(member),
+                            Some(TypeDefinition::Object(_))
+                        ) {
+                            errors.push(format!("union member `{}` is not an object", member));
+                        }
+                    }
+                    &[]
+                }
+                TypeDefinition::InputObject(input_object) => {
+                    check_arguments(&mut errors, input_object.fields());
+                    &[]
+                }
+                _ => &[],
+            };
+            for name in interfaces {
+                let interface = match schema.type_definition(name) {
+                    Some(TypeDefinition::Interface(interface)) => interface,
+                    _ => {
+                        errors.push(format!("`{}` is not an interface", name));
+                        continue;
+                    }
+                };
+                for transitive in interface.implements_interfaces() {
+                    if !interfaces.contains(transitive) {
+                        errors.push(format!(
+                            "`{}` does not implement `{}`",
+                            ty.name(),
+                            transitive
+                        ));
+                    }
+                }
+                for interface_field in interface.fields() {
+                    let field = match ty.field(interface_field.name()) {
+                        Some(field) => field,
+                        None => {
+                            errors.push(format!("missing field `{}`", interface_field.name()));
+                            continue;
+                        }
+                    };
+                    let signature =
+                        |arguments: &[apollo_compiler::schema::InputValueDefinition]| {
+                            arguments
+                                .iter()
+                                .map(|argument| {
+                                    (argument.name().to_string(), argument.ty().to_string())
+                                })
+                                .collect::<Vec<_>>()
+                        };
+                    if signature(field.arguments()) != signature(interface_field.arguments()) {
+                        errors.push(format!("`{}` has other arguments", field.name()));
+                    }
+                    if !is_subtype(&schema, field.ty(), interface_field.ty()) {
+                        errors.push(format!("`{}` is not a subtype", field.ty()));
+                    }
+                }
+            }
+        }
+        for directive in schema
+            .directives()
+            .filter(|directive| !directive.is_built_in())
+        {
+            check_arguments(&mut errors, directive.arguments());
+        }
+
+        let roots = [
+            schema.query_type(),
+            schema.mutation_type(),
+            schema.subscription_type(),
+        ];
+        if roots[0].is_none() {
+            errors.push(String::from("missing query root operation type"));
+        }
+        for (i, root) in roots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, root)| Some((i, (*root)?)))
+        {
+            if !matches!(
+                schema.type_definition(root),
+                Some(TypeDefinition::Object(_))
+            ) {
+                errors.push(format!("root operation type `{}` is not an object", root));
+            }
+            if roots[..i].contains(&Some(root)) {
+                errors.push(format!("`{}` is used for several root operations", root));
+            }
+        }
+        let schema_definitions = document
+            .definitions()
+            .filter(|definition| matches!(definition, ast::Definition::SchemaDefinition(_)))
+            .count();
+        if schema_definitions > 1 {
+            errors.push(String::from("several schema definitions"));
+        }
+
+        errors
+    }
+
+    fn is_subtype(schema: &apollo_compiler::Schema, ty: &Type, of: &Type) -> bool {
+        match (ty, of) {
+            (Type::NonNull(ty), Type::NonNull(of)) => is_subtype(schema, ty, of),
+            (Type::NonNull(ty), of) => is_subtype(schema, ty, of),
+            (_, Type::NonNull(_)) => false,
+            (Type::List(ty), Type::List(of)) => is_subtype(schema, ty, of),
+            (Type::Named(ty), Type::Named(of)) => {
+                ty == of
+                    || schema.is_possible_type(of, ty)
+                    || matches!(
+                        schema.type_definition(ty),
+                        Some(TypeDefinition::Interface(interface))
+                            if interface.implements_interfaces().contains(of)
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn it_generates_valid_schemas() {
+        let mut config = small_config(5);
+        config.definition_count(DefinitionKind::Operation, 1, 2);
+        config.valid_schema(true);
+
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let gql_doc = DocumentBuilder::with_config(&mut u, config.clone()).unwrap();
+            let document = String::from(gql_doc.finish());
+
+            let errors = errors(&document);
+            assert!(errors.is_empty(), "{}\n{:?}", document, errors);
+            let errors = type_system_errors(&document);
+            assert!(errors.is_empty(), "{}\n{:?}", document, errors);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parser-impl")]
+    fn it_generates_valid_operations_for_a_schema() {
+        let schema = Parser::new(SCHEMA).parse().document();
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let gql_doc = DocumentBuilder::with_document(&mut u, &schema).unwrap();
+            let operations = String::from(gql_doc.finish_executable());
+            assert!(operations.contains('{'));
+
+            let errors = errors(&format!("{}\n{}", SCHEMA, operations));
+            assert!(errors.is_empty(), "{}\n{:?}", operations, errors);
         }
     }
 }
@@ -0,0 +1,251 @@
+use std::ops::Range;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::DocumentBuilder;
+
+/// Punctuators of the GraphQL grammar, and characters that are not punctuators at all
+const PUNCTUATORS: &[&str] = &[
+    "!", "$", "&", "(", ")", "...", ":", "=", "@", "[", "]", "{", "|", "}", ",", "..", "?", "#",
+    "\"", "-",
+];
+/// Keywords of the GraphQL grammar, swapped with each other
+const KEYWORDS: &[&str] = &[
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "on",
+    "schema",
+    "scalar",
+    "type",
+    "interface",
+    "implements",
+    "union",
+    "enum",
+    "input",
+    "directive",
+    "repeatable",
+    "extend",
+    "true",
+    "false",
+    "null",
+];
+
+/// A structured change making a valid document malformed, applied by
+/// [`DocumentBuilder::finish_malformed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Arbitrary)]
+pub enum Malformation {
+    /// Remove a brace, a bracket or a parenthesis
+    DropDelimiter,
+    /// Cut a string value before its closing quotes, leaving it unterminated
+    TruncateString,
+    /// Insert a punctuator, or a character which is not a punctuator, anywhere in the document
+    InsertPunctuator,
+    /// Replace a keyword with another keyword
+    SwapKeyword,
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
+    /// Convert a `DocumentBuilder` into the text of a malformed GraphQL document
+    ///
+    /// The valid document is printed, then between 1 and 5 arbitrary [`Malformation`]s are applied to
+    /// its text, e.g. to exercise the error recovery of a parser.
+    ///
+    /// ```compile_fail
+    /// // fuzz/fuzz_targets/my_apollo_smith_fuzz_target.rs
+    /// #![no_main]
+    ///
+    /// use libfuzzer_sys::fuzz_target;
+    /// use arbitrary::Unstructured;
+    /// use apollo_smith::DocumentBuilder;
+    ///
+    /// fuzz_target!(|input: &[u8]| {
+    ///     let mut u = Unstructured::new(input);
+    ///     let gql_doc = DocumentBuilder::new(&mut u)?;
+    ///     let malformed_document = gql_doc.finish_malformed()?;
+    ///
+    ///     // Your code here...
+    /// });
+    /// ```
+    pub fn finish_malformed(mut self) -> Result<String> {
+        let mut document = String::from(self.take_document());
+        for _ in 0..self.u.int_in_range(1..=5usize)? {
+            let malformation = self.u.arbitrary()?;
+            malform(self.u, &mut document, malformation)?;
+        }
+
+        Ok(document)
+    }
+}
+
+/// Apply `malformation` to `document`, or insert a punctuator if nothing in `document` can be
+/// malformed that way
+fn malform(
+    u: &mut Unstructured<'_>,
+    document: &mut String,
+    malformation: Malformation,
+) -> Result<()> {
+    match malformation {
+        Malformation::DropDelimiter => {
+            let delimiters: Vec<usize> = document
+                .match_indices(['{', '}', '(', ')', '[', ']'])
+                .map(|(index, _)| index)
+                .collect();
+            if !delimiters.is_empty() {
+                let index = *u.choose(&delimiters)?;
+                document.remove(index);
+                return Ok(());
+            }
+        }
+        Malformation::TruncateString => {
+            let strings = string_values(document);
+            if !strings.is_empty() {
+                let (content, end) = u.choose(&strings)?.clone();
+                let cut = *u.choose(&char_boundaries(document, content))?;
+                document.replace_range(cut..end, "");
+                return Ok(());
+            }
+        }
+        Malformation::SwapKeyword => {
+            let keywords = keywords(document);
+            if !keywords.is_empty() {
+                let range = u.choose(&keywords)?.clone();
+                let others: Vec<&str> = KEYWORDS
+                    .iter()
+                    .copied()
+                    .filter(|keyword| *keyword != &document[range.clone()])
+                    .collect();
+                let keyword = *u.choose(&others)?;
+                document.replace_range(range, keyword);
+                return Ok(());
+            }
+        }
+        Malformation::InsertPunctuator => {}
+    }
+    let index = *u.choose(&char_boundaries(document, 0..document.len()))?;
+    document.insert_str(index, u.choose(PUNCTUATORS)?);
+
+    Ok(())
+}
+
+/// The char boundaries of `document` in `range`, including its end
+fn char_boundaries(document: &str, range: Range<usize>) -> Vec<usize> {
+    (range.start..=range.end)
+        .filter(|index| document.is_char_boundary(*index))
+        .collect()
+}
+
+/// The content and the end of the string values of `document`, quotes excluded from the content
+/// and included in the end
+fn string_values(document: &str) -> Vec<(Range<usize>, usize)> {
+    let mut strings = Vec::new();
+    let mut rest = 0;
+    while let Some(start) = document[rest..].find('"').map(|index| rest + index) {
+        let quotes = if document[start..].starts_with(r#"""""#) {
+            r#"""""#
+        } else {
+            "\""
+        };
+        let content_start = start + quotes.len();
+        match closing_quotes(&document[content_start..], quotes) {
+            Some(index) => {
+                let content_end = content_start + index;
+                let end = content_end + quotes.len();
+                strings.push((content_start..content_end, end));
+                rest = end;
+            }
+            None => break,
+        }
+    }
+    strings
+}
+
+/// The index of the `quotes` that close a string with `content`, skipping escaped quotes: `\"` in
+/// line strings and `\"""` in block strings
+fn closing_quotes(content: &str, quotes: &str) -> Option<usize> {
+    let is_block = quotes != "\"";
+    let mut chars = content.char_indices();
+    while let Some((index, c)) = chars.next() {
+        let rest = &content[index..];
+        if !is_block && c == '\\' {
+            // A backslash escapes the next character of a line string
+            chars.next();
+        } else if is_block && rest.starts_with(r#"\""""#) {
+            chars.nth(2);
+        } else if rest.starts_with(quotes) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// The ranges of the keywords of `document`, outside of string values
+fn keywords(document: &str) -> Vec<Range<usize>> {
+    let strings = string_values(document);
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut keywords = Vec::new();
+    let mut start = None;
+    for (index, c) in document.char_indices().chain(Some((document.len(), ' '))) {
+        match (start, is_name_char(c)) {
+            (None, true) => start = Some(index),
+            (Some(word_start), false) => {
+                start = None;
+                let in_string = strings
+                    .iter()
+                    .any(|(content, _)| content.contains(&word_start));
+                if !in_string && KEYWORDS.contains(&&document[word_start..index]) {
+                    keywords.push(word_start..index);
+                }
+            }
+            _ => {}
+        }
+    }
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_parser::{ast::AstNode, Parser};
+
+    use super::*;
+    use crate::{
+        tests::{data, small_config},
+        DefinitionKind,
+    };
+
+    #[test]
+    fn it_generates_malformed_documents() {
+        let mut config = small_config(5);
+        config.definition_count(DefinitionKind::Operation, 1, 2);
+        config.valid_schema(true);
+
+        let mut malformed_count = 0;
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let gql_doc = DocumentBuilder::with_config(&mut u, config.clone()).unwrap();
+            let document = gql_doc.finish_malformed().unwrap();
+
+            let tree = Parser::new(&document).parse();
+            if tree.errors().len() > 0 {
+                malformed_count += 1;
+            }
+            assert_eq!(tree.document().syntax().to_string(), document);
+        }
+        assert!(malformed_count > 50, "{}", malformed_count);
+    }
+
+    #[test]
+    fn it_skips_escaped_quotes_in_string_values() {
+        let document = r#"{ a(b: "a\"b", c: """x\"""y""") }"#;
+        let strings: Vec<_> = string_values(document)
+            .into_iter()
+            .map(|(content, end)| (&document[content], &document[end..]))
+            .collect();
+        assert_eq!(
+            strings,
+            [(r#"a\"b"#, r#", c: """x\"""y""") }"#), (r#"x\"""y"#, ") }")]
+        );
+    }
+}
@@ -49,15 +49,33 @@ impl Name {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl From<apollo_parser::ast::Name> for Name {
+    fn from(name: apollo_parser::ast::Name) -> Self {
+        Self::new(name.text().to_string())
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<Option<apollo_parser::ast::Name>> for Name {
+    type Error = crate::FromError;
+
+    fn try_from(name: Option<apollo_parser::ast::Name>) -> Result<Self, Self::Error> {
+        name.map(Self::from).ok_or(crate::FromError::MissingField)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `Name`
     pub fn name(&mut self) -> Result<Name> {
-        Ok(Name::new(self.limited_string(30)?))
+        Ok(Name::new(
+            self.limited_string(self.config.name_length_limit())?,
+        ))
     }
 
     /// Create an arbitrary type `Name`
     pub fn type_name(&mut self) -> Result<Name> {
-        let mut new_name = self.limited_string(30)?;
+        let mut new_name = self.limited_string(self.config.name_length_limit())?;
         if self.list_existing_type_names().any(|n| n.name == new_name) {
             new_name.push_str(&format!(
                 "{}",
@@ -69,12 +87,22 @@ impl<'a> DocumentBuilder<'a> {
 
     /// Create an arbitrary `Name` with an index included in the name (to avoid name conflict)
     pub fn name_with_index(&mut self, index: usize) -> Result<Name> {
-        let mut name = self.limited_string(30)?;
+        let mut name = self.limited_string(self.config.name_length_limit())?;
         name.push_str(&format!("{}", index));
 
         Ok(Name::new(name))
     }
 
+    /// Create an arbitrary `Name` ending with `_` and `index`. Generated names never end with `_`,
+    /// so names created with different indexes are always different.
+    pub(crate) fn unique_name(&mut self, index: usize) -> Result<Name> {
+        Ok(Name::new(format!(
+            "{}_{}",
+            self.limited_string(self.config.name_length_limit())?,
+            index
+        )))
+    }
+
     // Mirror what happens in `Arbitrary for String`, but do so with a clamped size.
     pub(crate) fn limited_string(&mut self, max_size: usize) -> Result<String> {
         loop {
@@ -83,7 +111,7 @@ impl<'a> DocumentBuilder<'a> {
             let gen_str = String::from_utf8(
                 (0..size)
                     .map(|curr_idx| {
-                        let idx = self.u.arbitrary::<usize>()?;
+                        let idx = usize::from(self.u.arbitrary::<u8>()?);
 
                         // Cannot start with a number
                         let ch = if curr_idx == 0 {
@@ -107,6 +135,10 @@ impl<'a> DocumentBuilder<'a> {
             if !new_gen.is_empty() && !RESERVED_KEYWORDS.contains(&new_gen) {
                 break Ok(new_gen.to_string());
             }
+            // Once the data is exhausted every attempt generates the same empty string
+            if self.u.is_empty() {
+                break Err(arbitrary::Error::NotEnoughData);
+            }
         }
     }
 
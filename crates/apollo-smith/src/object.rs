@@ -1,10 +1,12 @@
-use std::collections::HashSet;
-
 use apollo_encoder::ObjectDefinition;
 use arbitrary::Result;
+use indexmap::IndexSet;
 
+#[cfg(feature = "parser-impl")]
+use crate::interface::InterfaceTypeDef;
 use crate::{
-    description::Description, directive::Directive, field::FieldDef, name::Name, DocumentBuilder,
+    description::Description, directive::Directive, field::FieldDef, name::Name, ty::Ty,
+    DocumentBuilder,
 };
 
 /// Object types represent concrete instantiations of sets of fields.
@@ -20,7 +22,7 @@ use crate::{
 pub struct ObjectTypeDef {
     pub(crate) description: Option<Description>,
     pub(crate) name: Name,
-    pub(crate) interface_impls: HashSet<Name>,
+    pub(crate) interface_impls: IndexSet<Name>,
     pub(crate) directives: Vec<Directive>,
     pub(crate) fields_def: Vec<FieldDef>,
     pub(crate) extend: bool,
@@ -47,7 +49,43 @@ impl From<ObjectTypeDef> for ObjectDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::ObjectTypeDefinition> for ObjectTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(object_def: apollo_parser::ast::ObjectTypeDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: object_def.description().map(Description::from),
+            name: object_def.name().try_into()?,
+            interface_impls: InterfaceTypeDef::convert_interfaces(
+                object_def.implements_interfaces(),
+            )?,
+            directives: Directive::convert_directives(object_def.directives())?,
+            fields_def: FieldDef::convert_fields(object_def.fields_definition())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::ObjectTypeExtension> for ObjectTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(object_def: apollo_parser::ast::ObjectTypeExtension) -> Result<Self, Self::Error> {
+        Ok(Self {
+            description: None,
+            name: object_def.name().try_into()?,
+            interface_impls: InterfaceTypeDef::convert_interfaces(
+                object_def.implements_interfaces(),
+            )?,
+            directives: Directive::convert_directives(object_def.directives())?,
+            fields_def: FieldDef::convert_fields(object_def.fields_definition())?,
+            extend: true,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `ObjectTypeDef`
     pub fn object_type_definition(&mut self) -> Result<ObjectTypeDef> {
         let description = self
@@ -90,4 +128,31 @@ impl<'a> DocumentBuilder<'a> {
             extend: self.u.arbitrary().unwrap_or(false),
         })
     }
+
+    /// Create an arbitrary `ObjectTypeDef` named `name` for a valid schema, with fields of
+    /// `output_types`, implementing existing interfaces
+    pub(crate) fn valid_object_type_definition(
+        &mut self,
+        name: Name,
+        output_types: &[Ty],
+    ) -> Result<ObjectTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let (interface_impls, mut fields_def) = self.valid_implements_interfaces()?;
+        let new_fields_def = self.valid_fields_definition(output_types, &fields_def)?;
+        fields_def.extend(new_fields_def);
+
+        Ok(ObjectTypeDef {
+            description,
+            directives: Vec::new(),
+            interface_impls,
+            name,
+            fields_def,
+            extend: false,
+        })
+    }
 }
@@ -1,7 +1,10 @@
 use arbitrary::{Arbitrary, Result};
 
 use crate::{
-    directive::Directive, name::Name, selection_set::SelectionSet, variable::VariableDef,
+    directive::Directive,
+    name::Name,
+    selection_set::{Selection, SelectionSet},
+    variable::VariableDef,
     DocumentBuilder,
 };
 
@@ -11,7 +14,7 @@ use crate::{
 ///     OperationType Name? VariableDefinitions? Directives? SelectionSet
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Language.Operations).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OperationDef {
     pub(crate) operation_type: OperationType,
     pub(crate) name: Option<Name>,
@@ -39,13 +42,44 @@ impl From<OperationDef> for apollo_encoder::OperationDefinition {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::OperationDefinition> for OperationDef {
+    type Error = crate::FromError;
+
+    fn try_from(op_def: apollo_parser::ast::OperationDefinition) -> Result<Self, Self::Error> {
+        let operation_type = op_def.operation_type();
+        Ok(Self {
+            shorthand: operation_type.is_none(),
+            operation_type: operation_type
+                .map(OperationType::from)
+                .unwrap_or(OperationType::Query),
+            name: op_def.name().map(Name::from),
+            variable_definitions: op_def
+                .variable_definitions()
+                .map(|var_defs| {
+                    var_defs
+                        .variable_definitions()
+                        .map(VariableDef::try_from)
+                        .collect()
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            directives: Directive::convert_directives(op_def.directives())?,
+            selection_set: op_def
+                .selection_set()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+        })
+    }
+}
+
 /// The __operationType type represents the kind of operation
 ///
 /// *OperationType*:
 ///     query | mutation | subscription
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#OperationType).
-#[derive(Debug, Arbitrary)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Arbitrary)]
 pub enum OperationType {
     Query,
     Mutation,
@@ -62,7 +96,20 @@ impl From<OperationType> for apollo_encoder::OperationType {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl From<apollo_parser::ast::OperationType> for OperationType {
+    fn from(op_type: apollo_parser::ast::OperationType) -> Self {
+        if op_type.mutation_token().is_some() {
+            Self::Mutation
+        } else if op_type.subscription_token().is_some() {
+            Self::Subscription
+        } else {
+            Self::Query
+        }
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `OperationDef`
     pub fn operation_definition(&mut self) -> Result<OperationDef> {
         let name = self
@@ -86,4 +133,47 @@ impl<'a> DocumentBuilder<'a> {
             shorthand,
         })
     }
+
+    /// Create an arbitrary `OperationDef` that is valid against the existing types, returns `None`
+    /// if there is no root operation type.
+    ///
+    /// The operation selects existing fields with arguments of the right types, spreads existing
+    /// fragments on possible types, and declares all the variables it uses.
+    pub fn operation_definition_for_schema(&mut self) -> Result<Option<OperationDef>> {
+        let root_operation_types = self.root_operation_types();
+        if root_operation_types.is_empty() {
+            return Ok(None);
+        }
+        let (operation_type, root) = self.u.choose(&root_operation_types)?.clone();
+        let name = Name::new(format!(
+            "{}_{}",
+            self.limited_string(self.config.name_length_limit())?,
+            self.operation_defs.len()
+        ));
+
+        self.operation_variables = Some(Vec::new());
+        let selection_set = match operation_type {
+            // Subscriptions must select a single root field, which is not `__typename`
+            OperationType::Subscription => self.typed_field(&root, Some(&root), 0).map(|field| {
+                field.map(|field| SelectionSet {
+                    selections: vec![Selection::Field(field)],
+                })
+            }),
+            _ => self.selection_set_for_type(&root).map(Some),
+        };
+        let variable_definitions = self.operation_variables.take().unwrap_or_default();
+        let selection_set = match selection_set? {
+            Some(selection_set) => selection_set,
+            None => return Ok(None),
+        };
+
+        Ok(Some(OperationDef {
+            operation_type,
+            name: Some(name),
+            variable_definitions,
+            directives: Vec::new(),
+            selection_set,
+            shorthand: false,
+        }))
+    }
 }
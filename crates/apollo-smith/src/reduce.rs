@@ -0,0 +1,172 @@
+use std::{ops::Range, panic};
+
+use apollo_parser::{ast::AstNode, Parser, SyntaxKind};
+
+/// Reduce `document` to a smaller document for which `predicate` still holds, e.g. a minimal
+/// reproducer of a bug found by a fuzzer.
+///
+/// This is a delta debugging reducer working on the syntax tree of `apollo-parser`: it removes
+/// definitions, selections, arguments, directives, descriptions and the definitions of fields,
+/// input values, enum values and variables, first in large chunks and then one by one, as long as
+/// `predicate` holds for the document without them. The removals are repeated on the reduced
+/// document until none of them keeps `predicate` true. Documents that the parser panics on have no
+/// syntax tree, and are reduced by removing lines instead.
+///
+/// If `predicate` does not hold for `document`, it is returned as is.
+///
+/// The panics of the parser are caught with `std::panic::catch_unwind`, after the panic hook has
+/// run: a hook that aborts the process, like the one of libfuzzer's `fuzz_target!`, has to be
+/// replaced while reducing.
+///
+/// ```rust
+/// use apollo_parser::Parser;
+///
+/// let document = r#"
+/// type Query {
+///   cat(name: String): Cat
+///   dog: Dog
+/// }
+///
+/// type Cat { name: String, meows: Boolean }
+///
+/// type Dog { name: String
+/// "#;
+///
+/// // The smallest part of the document that still has syntax errors
+/// let reduced = apollo_smith::reduce(document, |document| {
+///     Parser::new(document).parse().errors().len() > 0
+/// });
+/// assert_eq!(reduced.trim(), "type Dog {");
+/// ```
+pub fn reduce(document: &str, mut predicate: impl FnMut(&str) -> bool) -> String {
+    let mut document = document.to_string();
+    if !predicate(&document) {
+        return document;
+    }
+
+    loop {
+        let ranges = removable_ranges(&document);
+        let mut chunk_size = (ranges.len() + 1) / 2;
+        let mut reduced = false;
+        while chunk_size > 0 && !reduced {
+            for chunk in ranges.chunks(chunk_size) {
+                let candidate = remove_ranges(&document, chunk);
+                if candidate.len() < document.len() && predicate(&candidate) {
+                    document = candidate;
+                    reduced = true;
+                    break;
+                }
+            }
+            chunk_size /= 2;
+        }
+        // The ranges of the reduced document are different, they are collected again
+        if !reduced {
+            break document;
+        }
+    }
+}
+
+/// The byte ranges of the parts of `document` that can be removed, in the order they start in.
+/// Ranges can be nested in each other.
+fn removable_ranges(document: &str) -> Vec<Range<usize>> {
+    match panic::catch_unwind(|| syntax_tree_ranges(document)) {
+        Ok(ranges) => ranges,
+        Err(_) => line_ranges(document),
+    }
+}
+
+/// The ranges of the nodes of the syntax tree of `document` that can be removed
+fn syntax_tree_ranges(document: &str) -> Vec<Range<usize>> {
+    let tree = Parser::new(document).parse();
+    tree.document()
+        .syntax()
+        .descendants()
+        .filter(|node| {
+            let is_definition = node
+                .parent()
+                .map_or(false, |parent| parent.kind() == SyntaxKind::DOCUMENT);
+            let is_selection = node
+                .parent()
+                .map_or(false, |parent| parent.kind() == SyntaxKind::SELECTION_SET);
+            is_definition
+                || is_selection
+                || matches!(
+                    node.kind(),
+                    SyntaxKind::ARGUMENTS
+                        | SyntaxKind::ARGUMENT
+                        | SyntaxKind::DIRECTIVE
+                        | SyntaxKind::DESCRIPTION
+                        | SyntaxKind::ARGUMENTS_DEFINITION
+                        | SyntaxKind::FIELD_DEFINITION
+                        | SyntaxKind::INPUT_VALUE_DEFINITION
+                        | SyntaxKind::ENUM_VALUE_DEFINITION
+                        | SyntaxKind::VARIABLE_DEFINITIONS
+                        | SyntaxKind::VARIABLE_DEFINITION
+                )
+        })
+        .map(|node| {
+            let range = node.text_range();
+            usize::from(range.start())..usize::from(range.end())
+        })
+        .filter(|range| !range.is_empty())
+        .collect()
+}
+
+/// The ranges of the lines of `document`, with their line terminator
+fn line_ranges(document: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    document
+        .split_inclusive('\n')
+        .map(|line| {
+            let range = start..start + line.len();
+            start = range.end;
+            range
+        })
+        .collect()
+}
+
+/// `document` without the text of `ranges`, which are sorted by their start
+fn remove_ranges(document: &str, ranges: &[Range<usize>]) -> String {
+    let mut remaining = String::with_capacity(document.len());
+    let mut end = 0;
+    for range in ranges {
+        // Ranges nested in a removed range are already removed
+        if range.start < end {
+            continue;
+        }
+        remaining.push_str(&document[end..range.start]);
+        end = range.end;
+    }
+    remaining.push_str(&document[end..]);
+
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, Document};
+
+    #[test]
+    fn it_reduces_documents_to_a_minimal_reproducer() {
+        let mut config = Config::default();
+        config.max_selection_depth(3);
+        let generated = String::from(Document::from_seed(0, config).unwrap());
+        let document = format!("{}\ntype Broken {{ field(: Int): Int }}\n", generated);
+
+        let has_error = |document: &str| {
+            Parser::new(document)
+                .parse()
+                .errors()
+                .any(|err| err.message() == "expected an Input Value Definition")
+        };
+        let reduced = reduce(&document, has_error);
+        assert!(has_error(&reduced));
+        assert!(reduced.len() < 100, "{}", reduced);
+        assert!(reduced.contains("(: Int)"), "{}", reduced);
+        assert_eq!(reduce(&reduced, has_error), reduced);
+
+        // Documents for which the predicate does not hold are not reduced
+        assert_eq!(reduce(&generated, has_error), generated);
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::HashSet;
+
+use arbitrary::Result;
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+use crate::{
+    directive::Directive,
+    field::Field,
+    fragment::FragmentDef,
+    input_value::InputValue,
+    name::Name,
+    operation::OperationDef,
+    selection_set::{Selection, SelectionSet},
+    ty::Ty,
+    DocumentBuilder,
+};
+
+/// Maximum number of items of the lists in a response
+const MAX_LIST_LENGTH: usize = 3;
+
+/// The operation a response is generated for, and what its selections depend on
+struct ResponseContext<'f> {
+    operation: &'f OperationDef,
+    variables: &'f Map<String, Value>,
+    fragment_defs: &'f [FragmentDef],
+}
+
+impl<'f> ResponseContext<'f> {
+    /// Check whether the `@skip` and `@include` directives of a selection keep it in the response
+    fn is_included(&self, directives: &[Directive]) -> bool {
+        directives.iter().all(|directive| {
+            let condition = directive
+                .arguments
+                .iter()
+                .find(|argument| argument.name.name == "if")
+                .and_then(|argument| self.boolean(&argument.value));
+            !matches!(
+                (directive.name.name.as_str(), condition),
+                ("skip", Some(true)) | ("include", Some(false))
+            )
+        })
+    }
+
+    /// The value of a boolean literal or variable, using the variable's default value if it is
+    /// not provided
+    fn boolean(&self, value: &InputValue) -> Option<bool> {
+        match value {
+            InputValue::Boolean(b) => Some(*b),
+            InputValue::Variable(name) => self
+                .variables
+                .get(&name.name)
+                .and_then(Value::as_bool)
+                .or_else(|| {
+                    self.operation
+                        .variable_definitions
+                        .iter()
+                        .find(|variable| &variable.name == name)
+                        .and_then(|variable| match variable.default_value {
+                            Some(InputValue::Boolean(b)) => Some(b),
+                            _ => None,
+                        })
+                }),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
+    /// Create arbitrary JSON values for the variables of `operation`, of the types they are
+    /// declared with and using the existing enum and input object types.
+    ///
+    /// Variables that are nullable or have a default value are sometimes left out.
+    pub fn variables_for_operation(
+        &mut self,
+        operation: &OperationDef,
+    ) -> Result<Map<String, Value>> {
+        let mut variables = Map::new();
+        for variable in &operation.variable_definitions {
+            let is_optional = !variable.ty.is_non_null() || variable.default_value.is_some();
+            if is_optional && self.u.arbitrary().unwrap_or(false) {
+                continue;
+            }
+            let value = self.literal_for_type(&variable.ty, 0)?;
+            variables.insert(variable.name.name.clone(), value.into());
+        }
+
+        Ok(variables)
+    }
+
+    /// Create an arbitrary JSON response to `operation` executed with `variables`, the value of
+    /// its `data`, or `null` if the schema has no root operation type for it.
+    ///
+    /// Every selected field has a value of its type under its response key, its alias or its
+    /// name: lists have a few items, nullable fields are sometimes `null`, and enums have one of
+    /// their values. Fields are collected through fragments and the `@skip` and `@include`
+    /// directives like in an execution, so the fields of an object are the ones selected on its
+    /// type. The fields are in the order of the selections if serde_json's `preserve_order`
+    /// feature is enabled, and sorted by response key otherwise, like any `serde_json::Map`.
+    ///
+    /// The value of a field of an interface or union type is an object of one of its possible
+    /// types, named by its `__typename`, which is in the response even when it is not selected
+    /// so that the type of the object can be resolved, e.g. by an executor reading the response.
+    /// When the type has no possible types, such nullable fields are `null` and lists of such
+    /// non-null items are empty. A non-null field of such a type has no value, so operations
+    /// selecting one, which `DocumentBuilder::with_document` never generates, get an error.
+    pub fn response_for_operation(
+        &mut self,
+        operation: &OperationDef,
+        variables: &Map<String, Value>,
+    ) -> Result<Value> {
+        let root = match self
+            .root_operation_types()
+            .into_iter()
+            .find(|(operation_type, _)| *operation_type == operation.operation_type)
+        {
+            Some((_, root)) => root,
+            None => return Ok(Value::Null),
+        };
+
+        let fragment_defs = std::mem::take(&mut self.fragment_defs);
+        let context = ResponseContext {
+            operation,
+            variables,
+            fragment_defs: &fragment_defs,
+        };
+        let data = self.object_response(&context, &root, &[&operation.selection_set], false);
+        self.fragment_defs = fragment_defs;
+
+        data
+    }
+
+    /// Create the JSON object of type `ty` selected by `selection_sets`, with its `__typename`
+    /// if `with_typename` is set
+    fn object_response<'f>(
+        &mut self,
+        context: &ResponseContext<'f>,
+        ty: &Name,
+        selection_sets: &[&'f SelectionSet],
+        with_typename: bool,
+    ) -> Result<Value> {
+        let mut fields = IndexMap::new();
+        for selection_set in selection_sets {
+            self.collect_fields(context, ty, selection_set, &mut fields, &mut HashSet::new());
+        }
+
+        let mut object = Map::new();
+        if with_typename {
+            object.insert(String::from("__typename"), Value::String(ty.name.clone()));
+        }
+        for (response_key, fields) in fields {
+            let value = if fields[0].name.name == "__typename" {
+                Value::String(ty.name.clone())
+            } else {
+                match self.field_type(ty, &fields[0].name) {
+                    Some(field_ty) => {
+                        let selection_sets: Vec<&SelectionSet> = fields
+                            .iter()
+                            .filter_map(|field| field.selection_set.as_ref())
+                            .collect();
+                        self.value_response(context, &field_ty, &selection_sets)?
+                    }
+                    None => Value::Null,
+                }
+            };
+            object.insert(response_key.to_string(), value);
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    /// Create an arbitrary JSON value of the output type `ty`, with the fields of
+    /// `selection_sets` if it is an object
+    fn value_response<'f>(
+        &mut self,
+        context: &ResponseContext<'f>,
+        ty: &Ty,
+        selection_sets: &[&'f SelectionSet],
+    ) -> Result<Value> {
+        let (ty, is_nullable) = match ty {
+            Ty::NonNull(inner) => (inner.as_ref(), false),
+            _ => (ty, true),
+        };
+        if is_nullable && self.u.int_in_range(0..=5usize)? == 0 {
+            return Ok(Value::Null);
+        }
+
+        let name = match ty {
+            Ty::List(item_ty) => {
+                let len = if self.has_value(item_ty) {
+                    self.u.int_in_range(0..=MAX_LIST_LENGTH)?
+                } else {
+                    0
+                };
+                return (0..len)
+                    .map(|_| self.value_response(context, item_ty, selection_sets))
+                    .collect::<Result<Vec<_>>>()
+                    .map(Value::Array);
+            }
+            Ty::NonNull(inner) => return self.value_response(context, inner, selection_sets),
+            Ty::Named(name) => name,
+        };
+
+        if self.object_type_defs.iter().any(|o| &o.name == name) {
+            return self.object_response(context, name, selection_sets, false);
+        }
+        if self.is_composite_type(name) {
+            let possible_types: Vec<Name> = self.possible_types(name).into_iter().collect();
+            if possible_types.is_empty() {
+                return if is_nullable {
+                    Ok(Value::Null)
+                } else {
+                    Err(arbitrary::Error::IncorrectFormat)
+                };
+            }
+            let object_ty = self.u.choose(&possible_types)?.clone();
+            return self.object_response(context, &object_ty, selection_sets, true);
+        }
+
+        let value = match name.name.as_str() {
+            "Int" => Value::from(self.u.arbitrary::<i32>()?),
+            "Float" => {
+                let float: f64 = self.u.arbitrary()?;
+                Value::from(if float.is_finite() { float } else { 0.0 })
+            }
+            "String" | "ID" => Value::String(self.limited_string(40)?),
+            "Boolean" => Value::Bool(self.u.arbitrary()?),
+            _ => {
+                let enum_values: Vec<&Name> = self
+                    .enum_type_defs
+                    .iter()
+                    .filter(|enum_| &enum_.name == name)
+                    .flat_map(|enum_| enum_.enum_values_def.iter().map(|v| &v.value))
+                    .collect();
+                if enum_values.is_empty() {
+                    // Custom scalars can have any value, strings are the most common
+                    Value::String(self.limited_string(40)?)
+                } else {
+                    Value::String(self.u.choose(&enum_values)?.name.clone())
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Collect the fields of `selection_set` selected on the object type `ty` by their response
+    /// key, following fragments whose type condition applies to `ty`
+    ///
+    /// See: https://spec.graphql.org/October2021/#CollectFields()
+    fn collect_fields<'f>(
+        &self,
+        context: &ResponseContext<'f>,
+        ty: &Name,
+        selection_set: &'f SelectionSet,
+        fields: &mut IndexMap<&'f str, Vec<&'f Field>>,
+        visited_fragments: &mut HashSet<&'f Name>,
+    ) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    if context.is_included(&field.directives) {
+                        let response_key = field.alias.as_ref().unwrap_or(&field.name);
+                        fields
+                            .entry(response_key.name.as_str())
+                            .or_default()
+                            .push(field);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if !context.is_included(&fragment_spread.directives)
+                        || !visited_fragments.insert(&fragment_spread.name)
+                    {
+                        continue;
+                    }
+                    let fragment_def = context
+                        .fragment_defs
+                        .iter()
+                        .find(|fragment_def| fragment_def.name == fragment_spread.name);
+                    if let Some(fragment_def) = fragment_def {
+                        if self
+                            .possible_types(&fragment_def.type_condition.name)
+                            .contains(ty)
+                        {
+                            self.collect_fields(
+                                context,
+                                ty,
+                                &fragment_def.selection_set,
+                                fields,
+                                visited_fragments,
+                            );
+                        }
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let applies = inline_fragment
+                        .type_condition
+                        .as_ref()
+                        .map_or(true, |type_condition| {
+                            self.possible_types(&type_condition.name).contains(ty)
+                        });
+                    if applies && context.is_included(&inline_fragment.directives) {
+                        self.collect_fields(
+                            context,
+                            ty,
+                            &inline_fragment.selection_set,
+                            fields,
+                            visited_fragments,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// The type of the field `name` of the object type `ty`, including the fields added by its extensions
+    fn field_type(&self, ty: &Name, name: &Name) -> Option<Ty> {
+        self.object_type_defs
+            .iter()
+            .filter(|o| &o.name == ty)
+            .flat_map(|o| o.fields_def.iter())
+            .find(|field_def| &field_def.name == name)
+            .map(|field_def| field_def.ty.clone())
+    }
+}
+
+#[cfg(all(test, feature = "parser-impl"))]
+mod tests {
+    use apollo_parser::Parser;
+    use arbitrary::Unstructured;
+
+    use super::*;
+    use crate::{
+        operation::OperationType,
+        tests::{data, SCHEMA},
+        Document,
+    };
+
+    /// Check that `data` is `response`, without the `__typename` of the objects that do not select it
+    fn is_data_of(data: &Value, response: &Value) -> bool {
+        match (data, response) {
+            (Value::Object(data), Value::Object(response)) => {
+                data.keys().all(|key| response.contains_key(key))
+                    && response.iter().all(|(key, value)| match data.get(key) {
+                        Some(data) => is_data_of(data, value),
+                        None => key == "__typename",
+                    })
+            }
+            (Value::Array(data), Value::Array(response)) => {
+                data.len() == response.len()
+                    && data
+                        .iter()
+                        .zip(response)
+                        .all(|(data, value)| is_data_of(data, value))
+            }
+            _ => data == response,
+        }
+    }
+
+    #[test]
+    fn it_generates_variables_and_responses_for_operations() {
+        let schema_doc = Parser::new(SCHEMA).parse().document();
+        let schema = apollo_compiler::Schema::new(&schema_doc);
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let mut gql_doc = DocumentBuilder::with_document(&mut u, &schema_doc).unwrap();
+            let mut responses = Vec::new();
+            for operation in gql_doc.operation_definitions().to_vec() {
+                // Subscriptions cannot be executed as a single request
+                if operation.operation_type == OperationType::Subscription {
+                    continue;
+                }
+                let variables = gql_doc.variables_for_operation(&operation).unwrap();
+                let response = gql_doc
+                    .response_for_operation(&operation, &variables)
+                    .unwrap();
+                responses.push((operation.name.unwrap(), variables, response));
+            }
+            let operations = String::from(gql_doc.finish_executable());
+            let document = Parser::new(&operations).parse().document();
+
+            // Reading the response as raw data gives it back if it has the types of the fields
+            for (name, variables, response) in responses {
+                let shaped = apollo_compiler::execution::shape(
+                    &schema,
+                    &document,
+                    Some(&name.name),
+                    &variables,
+                    &response,
+                );
+                assert_eq!(shaped.errors(), [], "{}\n{}", operations, response);
+                let data = Value::from(shaped.data().unwrap().clone());
+                assert!(
+                    is_data_of(&data, &response),
+                    "{}\n{}\n{}",
+                    operations,
+                    data,
+                    response
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_respects_non_null_fields_without_possible_types() {
+        let schema_doc = Parser::new(SCHEMA).parse().document();
+
+        // Non-null fields of an interface without implementations are never selected
+        for seed in 0..100 {
+            let data = data(seed);
+            let mut u = Unstructured::new(&data);
+            let gql_doc = DocumentBuilder::with_document(&mut u, &schema_doc).unwrap();
+            let operations = String::from(gql_doc.finish_executable());
+            assert!(!operations.contains("requiredDraft"), "{}", operations);
+        }
+
+        let operations = "{ draft { title } drafts { title } } { requiredDraft { title } }";
+        let operations = Document::try_from(Parser::new(operations).parse().document()).unwrap();
+        let data = data(0);
+        let mut u = Unstructured::new(&data);
+        let mut gql_doc = DocumentBuilder::with_document(&mut u, &schema_doc).unwrap();
+
+        // The nullable field is null and the list is empty, there is no object of their type
+        let response = gql_doc
+            .response_for_operation(&operations.operation_definitions[0], &Map::new())
+            .unwrap();
+        assert_eq!(response.to_string(), r#"{"draft":null,"drafts":[]}"#);
+        // The non-null field has no value
+        assert!(gql_doc
+            .response_for_operation(&operations.operation_definitions[1], &Map::new())
+            .is_err());
+    }
+}
@@ -33,7 +33,35 @@ impl From<ScalarTypeDef> for apollo_encoder::ScalarDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::ScalarTypeDefinition> for ScalarTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(scalar_def: apollo_parser::ast::ScalarTypeDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: scalar_def.name().try_into()?,
+            description: scalar_def.description().map(Description::from),
+            directives: Directive::convert_directives(scalar_def.directives())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::ScalarTypeExtension> for ScalarTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(scalar_def: apollo_parser::ast::ScalarTypeExtension) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: scalar_def.name().try_into()?,
+            description: None,
+            directives: Directive::convert_directives(scalar_def.directives())?,
+            extend: true,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `ScalarTypeDef`
     pub fn scalar_type_definition(&mut self) -> Result<ScalarTypeDef> {
         let name = self.type_name()?;
@@ -54,4 +82,21 @@ impl<'a> DocumentBuilder<'a> {
             extend,
         })
     }
+
+    /// Create an arbitrary `ScalarTypeDef` named `name` for a valid schema
+    pub(crate) fn valid_scalar_type_definition(&mut self, name: Name) -> Result<ScalarTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+
+        Ok(ScalarTypeDef {
+            name,
+            description,
+            directives: Vec::new(),
+            extend: false,
+        })
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{description::Description, directive::Directive, ty::Ty, DocumentBuilder};
+use crate::{
+    description::Description, directive::Directive, name::Name, operation::OperationType, ty::Ty,
+    DocumentBuilder,
+};
 use arbitrary::Result;
 
 /// A GraphQL service’s collective type system capabilities are referred to as that service’s “schema”.
@@ -42,7 +45,107 @@ impl From<SchemaDef> for apollo_encoder::SchemaDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::SchemaDefinition> for SchemaDef {
+    type Error = crate::FromError;
+
+    fn try_from(schema_def: apollo_parser::ast::SchemaDefinition) -> Result<Self, Self::Error> {
+        let mut new_schema_def = Self {
+            description: schema_def.description().map(Description::from),
+            directives: Directive::convert_directives(schema_def.directives())?,
+            query: None,
+            mutation: None,
+            subscription: None,
+            extend: false,
+        };
+        new_schema_def.convert_root_operations(schema_def.root_operation_type_definitions())?;
+
+        Ok(new_schema_def)
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::SchemaExtension> for SchemaDef {
+    type Error = crate::FromError;
+
+    fn try_from(schema_def: apollo_parser::ast::SchemaExtension) -> Result<Self, Self::Error> {
+        let mut new_schema_def = Self {
+            description: None,
+            directives: Directive::convert_directives(schema_def.directives())?,
+            query: None,
+            mutation: None,
+            subscription: None,
+            extend: true,
+        };
+        new_schema_def.convert_root_operations(schema_def.root_operation_type_definitions())?;
+
+        Ok(new_schema_def)
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl SchemaDef {
+    fn convert_root_operations(
+        &mut self,
+        root_operations: impl Iterator<Item = apollo_parser::ast::RootOperationTypeDefinition>,
+    ) -> Result<(), crate::FromError> {
+        for root_operation in root_operations {
+            let operation_type = root_operation
+                .operation_type()
+                .ok_or(crate::FromError::MissingField)?;
+            let ty = Some(Ty::Named(
+                root_operation
+                    .named_type()
+                    .and_then(|named| named.name())
+                    .try_into()?,
+            ));
+            if operation_type.query_token().is_some() {
+                self.query = ty;
+            } else if operation_type.mutation_token().is_some() {
+                self.mutation = ty;
+            } else if operation_type.subscription_token().is_some() {
+                self.subscription = ty;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
+    /// List the existing root operation types, given by the schema definitions or, without
+    /// schema definition, by the object types named `Query`, `Mutation` and `Subscription`
+    pub(crate) fn root_operation_types(&self) -> Vec<(OperationType, Name)> {
+        let mut root_operation_types = Vec::new();
+        for schema_def in &self.schema_defs {
+            let roots = [
+                (OperationType::Query, &schema_def.query),
+                (OperationType::Mutation, &schema_def.mutation),
+                (OperationType::Subscription, &schema_def.subscription),
+            ];
+            for (operation_type, root) in roots {
+                if let Some(root) = root {
+                    root_operation_types.push((operation_type, root.name().clone()));
+                }
+            }
+        }
+        if self.schema_defs.is_empty() {
+            let roots = [
+                (OperationType::Query, "Query"),
+                (OperationType::Mutation, "Mutation"),
+                (OperationType::Subscription, "Subscription"),
+            ];
+            for (operation_type, root) in roots {
+                let root = Name::new(String::from(root));
+                if self.object_type_defs.iter().any(|o| o.name == root) {
+                    root_operation_types.push((operation_type, root));
+                }
+            }
+        }
+
+        root_operation_types
+    }
+
     /// Create an arbitrary `SchemaDef`
     pub fn schema_definition(&mut self) -> Result<SchemaDef> {
         let description = self
@@ -88,4 +191,47 @@ impl<'a> DocumentBuilder<'a> {
             extend: self.u.arbitrary().unwrap_or(false),
         })
     }
+
+    /// Create an arbitrary `SchemaDef` for a valid schema, whose root operation types are
+    /// different existing object types. Returns `None` if there is no object type.
+    pub(crate) fn valid_schema_definition(&mut self) -> Result<Option<SchemaDef>> {
+        let mut object_types = self.list_existing_object_types();
+        if object_types.is_empty() {
+            return Ok(None);
+        }
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let query = self.take_root_operation_type(&mut object_types)?;
+        let mutation = match self.u.arbitrary().unwrap_or(false) {
+            true => self.take_root_operation_type(&mut object_types)?,
+            false => None,
+        };
+        let subscription = match self.u.arbitrary().unwrap_or(false) {
+            true => self.take_root_operation_type(&mut object_types)?,
+            false => None,
+        };
+
+        Ok(Some(SchemaDef {
+            description,
+            directives: Vec::new(),
+            query,
+            mutation,
+            subscription,
+            extend: false,
+        }))
+    }
+
+    /// Remove an arbitrary type from `object_types` to use it as a root operation type
+    fn take_root_operation_type(&mut self, object_types: &mut Vec<Ty>) -> Result<Option<Ty>> {
+        if object_types.is_empty() {
+            return Ok(None);
+        }
+        let idx = self.u.int_in_range(0..=(object_types.len() - 1))?;
+
+        Ok(Some(object_types.swap_remove(idx)))
+    }
 }
@@ -1,5 +1,10 @@
+use std::collections::HashSet;
+
 use arbitrary::Result;
 
+/// Depth of nested selection sets after which only leaf fields are selected, since types can be recursive
+pub(crate) const MAX_SELECTION_DEPTH: usize = 4;
+
 use crate::{
     field::Field,
     fragment::{FragmentSpread, InlineFragment},
@@ -13,9 +18,30 @@ use crate::{
 ///     Selection*
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Selection-Sets).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SelectionSet {
-    selections: Vec<Selection>,
+    pub(crate) selections: Vec<Selection>,
+}
+
+impl SelectionSet {
+    /// Collect the names of the fragments spread in this selection set, at any depth
+    pub(crate) fn fragment_spread_names(&self, names: &mut HashSet<Name>) {
+        for selection in &self.selections {
+            match selection {
+                Selection::Field(field) => {
+                    if let Some(selection_set) = &field.selection_set {
+                        selection_set.fragment_spread_names(names);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    names.insert(fragment_spread.name.clone());
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    inline_fragment.selection_set.fragment_spread_names(names);
+                }
+            }
+        }
+    }
 }
 
 impl From<SelectionSet> for apollo_encoder::SelectionSet {
@@ -30,12 +56,26 @@ impl From<SelectionSet> for apollo_encoder::SelectionSet {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::SelectionSet> for SelectionSet {
+    type Error = crate::FromError;
+
+    fn try_from(sel_set: apollo_parser::ast::SelectionSet) -> Result<Self, Self::Error> {
+        Ok(Self {
+            selections: sel_set
+                .selections()
+                .map(Selection::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 /// The __selection type represents a selection in a selection set
 /// *Selection*:
 ///     Field | FragmentSpread | InlineFragment
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#Selection).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Selection {
     /// Represents a field
     Field(Field),
@@ -59,14 +99,40 @@ impl From<Selection> for apollo_encoder::Selection {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Selection> for Selection {
+    type Error = crate::FromError;
+
+    fn try_from(selection: apollo_parser::ast::Selection) -> Result<Self, Self::Error> {
+        use apollo_parser::ast;
+
+        let selection = match selection {
+            ast::Selection::Field(field) => Self::Field(field.try_into()?),
+            ast::Selection::FragmentSpread(fragment_spread) => {
+                Self::FragmentSpread(fragment_spread.try_into()?)
+            }
+            ast::Selection::InlineFragment(inline_fragment) => {
+                Self::InlineFragment(inline_fragment.try_into()?)
+            }
+        };
+
+        Ok(selection)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `SelectionSet`
     pub fn selection_set(&mut self) -> Result<SelectionSet> {
         let mut exclude_names = Vec::new();
-        let selections = (0..self.u.int_in_range(2..=7usize)?)
+        let num_selections = self.u.int_in_range(2..=7usize)?;
+        self.selection_depth += 1;
+        let selections = (0..num_selections)
             .map(|i| self.selection(i, &mut exclude_names)) // TODO do not generate duplication variable name
-            .collect::<Result<Vec<_>>>()?;
-        Ok(SelectionSet { selections })
+            .collect::<Result<Vec<_>>>();
+        self.selection_depth -= 1;
+        Ok(SelectionSet {
+            selections: selections?,
+        })
     }
     /// Create an arbitrary `Selection`
     pub fn selection(&mut self, index: usize, excludes: &mut Vec<Name>) -> Result<Selection> {
@@ -76,10 +142,59 @@ impl<'a> DocumentBuilder<'a> {
                 Some(frag_spread) => Selection::FragmentSpread(frag_spread),
                 None => Selection::Field(self.field_with_index(index)?),
             },
-            2 => Selection::InlineFragment(self.inline_fragment()?),
+            2 if self.can_nest_selection_set() => {
+                Selection::InlineFragment(self.inline_fragment()?)
+            }
+            2 => Selection::Field(self.field_with_index(index)?),
             _ => unreachable!(),
         };
 
         Ok(selection)
     }
+
+    /// Create an arbitrary `SelectionSet` on the existing type named `ty`, selecting its fields
+    /// with arguments of the right types, and fragments on its possible types
+    pub fn selection_set_for_type(&mut self, ty: &Name) -> Result<SelectionSet> {
+        self.typed_selection_set(ty, Some(ty), 0)
+    }
+
+    /// Create an arbitrary `SelectionSet` on `parent`.
+    ///
+    /// `scope` is the type of the field the selection set belongs to, or `None` in a fragment definition.
+    /// Fields selected on another type than `scope`, or with arguments, are aliased so that all the
+    /// fields with the same response key can be merged.
+    pub(crate) fn typed_selection_set(
+        &mut self,
+        parent: &Name,
+        scope: Option<&Name>,
+        depth: usize,
+    ) -> Result<SelectionSet> {
+        let mut selections = Vec::new();
+        for _ in 0..self.u.int_in_range(1..=5usize)? {
+            let selection = match self.u.int_in_range(0..=5usize)? {
+                0 if depth < self.config.selection_depth_limit(MAX_SELECTION_DEPTH) => self
+                    .typed_inline_fragment(parent, scope, depth)?
+                    .map(Selection::InlineFragment),
+                1 => self
+                    .typed_fragment_spread(parent)?
+                    .map(Selection::FragmentSpread),
+                _ => self
+                    .typed_field(parent, scope, depth)?
+                    .map(Selection::Field),
+            };
+            selections.extend(selection);
+        }
+        if selections.is_empty() {
+            selections.push(Selection::Field(Field::typename()));
+        }
+
+        Ok(SelectionSet { selections })
+    }
+
+    /// Whether a selection in the arbitrary selection set being generated can have a selection
+    /// set of its own without exceeding the maximum selection depth
+    pub(crate) fn can_nest_selection_set(&self) -> bool {
+        // `selection_depth` counts the selection sets being generated, the outermost being at depth 0
+        self.selection_depth <= self.config.selection_depth_limit(usize::MAX)
+    }
 }
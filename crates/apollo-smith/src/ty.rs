@@ -1,8 +1,9 @@
 use apollo_encoder::Type_;
 use arbitrary::Result;
+use indexmap::IndexSet;
 use once_cell::sync::Lazy;
 
-use crate::{input_value::InputValue, name::Name, DocumentBuilder};
+use crate::{field::FieldDef, name::Name, DocumentBuilder};
 
 static BUILTIN_SCALAR_NAMES: Lazy<[Ty; 5]> = Lazy::new(|| {
     [
@@ -42,6 +43,38 @@ impl From<Ty> for Type_ {
     }
 }
 
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::Type> for Ty {
+    type Error = crate::FromError;
+
+    fn try_from(ty: apollo_parser::ast::Type) -> Result<Self, Self::Error> {
+        use apollo_parser::ast;
+
+        let ty = match ty {
+            ast::Type::NamedType(named) => Ty::Named(named.name().try_into()?),
+            ast::Type::ListType(list) => Ty::List(Box::new(
+                list.ty()
+                    .ok_or(crate::FromError::MissingField)?
+                    .try_into()?,
+            )),
+            ast::Type::NonNullType(non_null) => {
+                let inner = match (non_null.named_type(), non_null.list_type()) {
+                    (Some(named), _) => Ty::Named(named.name().try_into()?),
+                    (None, Some(list)) => Ty::List(Box::new(
+                        list.ty()
+                            .ok_or(crate::FromError::MissingField)?
+                            .try_into()?,
+                    )),
+                    (None, None) => return Err(crate::FromError::MissingField),
+                };
+                Ty::NonNull(Box::new(inner))
+            }
+        };
+
+        Ok(ty)
+    }
+}
+
 impl Ty {
     pub(crate) fn name(&self) -> &Name {
         match self {
@@ -57,9 +90,16 @@ impl Ty {
     pub fn is_named(&self) -> bool {
         matches!(self, Self::Named(..))
     }
+
+    /// Returns `true` if the ty is [`NonNull`].
+    ///
+    /// [`NonNull`]: Ty::NonNull
+    pub fn is_non_null(&self) -> bool {
+        matches!(self, Self::NonNull(..))
+    }
 }
 
-impl<'a> DocumentBuilder<'a> {
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `Ty`
     pub fn ty(&mut self) -> Result<Ty> {
         self.generate_ty(true)
@@ -144,6 +184,63 @@ impl<'a> DocumentBuilder<'a> {
             .collect()
     }
 
+    /// List all existing (already created) scalar, enum and input object `Ty`
+    pub(crate) fn list_existing_input_types(&self) -> Vec<Ty> {
+        self.scalar_type_defs
+            .iter()
+            .map(|s| &s.name)
+            .chain(self.enum_type_defs.iter().map(|e| &e.name))
+            .chain(self.input_object_type_defs.iter().map(|i| &i.name))
+            .map(|name| Ty::Named(name.clone()))
+            .collect()
+    }
+
+    /// Choose an arbitrary subtype of the output type `ty`: any of its existing possible types
+    /// may replace a named type, and nullable types may become non-null
+    pub(crate) fn subtype(&mut self, ty: &Ty) -> Result<Ty> {
+        let nullable = match ty {
+            Ty::NonNull(inner) => return Ok(Ty::NonNull(Box::new(self.nullable_subtype(inner)?))),
+            _ => self.nullable_subtype(ty)?,
+        };
+
+        Ok(if self.u.arbitrary().unwrap_or(false) {
+            Ty::NonNull(Box::new(nullable))
+        } else {
+            nullable
+        })
+    }
+
+    fn nullable_subtype(&mut self, ty: &Ty) -> Result<Ty> {
+        let ty = match ty {
+            Ty::Named(name) => {
+                let mut subtypes = vec![name];
+                subtypes.extend(
+                    self.interface_type_defs
+                        .iter()
+                        .filter(|itf| itf.interfaces.contains(name))
+                        .map(|itf| &itf.name),
+                );
+                subtypes.extend(
+                    self.object_type_defs
+                        .iter()
+                        .filter(|o| o.interface_impls.contains(name))
+                        .map(|o| &o.name),
+                );
+                subtypes.extend(
+                    self.union_type_defs
+                        .iter()
+                        .filter(|u| &u.name == name)
+                        .flat_map(|u| u.members.iter().map(Ty::name)),
+                );
+                Ty::Named(self.u.choose(&subtypes)?.to_owned().clone())
+            }
+            Ty::List(item) => Ty::List(Box::new(self.subtype(item)?)),
+            Ty::NonNull(inner) => self.nullable_subtype(inner)?,
+        };
+
+        Ok(ty)
+    }
+
     /// List all existing object (already created) `Ty`
     pub(crate) fn list_existing_object_types(&self) -> Vec<Ty> {
         self.object_type_defs
@@ -152,8 +249,66 @@ impl<'a> DocumentBuilder<'a> {
             .collect()
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn generate_value_for_type(&mut self, _ty: &Ty) -> InputValue {
-        todo!()
+    /// Check whether the type named `name` is an existing object, interface or union type
+    pub(crate) fn is_composite_type(&self, name: &Name) -> bool {
+        self.composite_type_names()
+            .any(|composite| composite == name)
+    }
+
+    /// List the names of all existing object, interface and union types
+    pub(crate) fn composite_type_names(&self) -> impl Iterator<Item = &Name> {
+        self.object_type_defs
+            .iter()
+            .map(|o| &o.name)
+            .chain(self.interface_type_defs.iter().map(|itf| &itf.name))
+            .chain(self.union_type_defs.iter().map(|u| &u.name))
+    }
+
+    /// List the names of the object types a value of the type named `name` can have
+    pub(crate) fn possible_types(&self, name: &Name) -> IndexSet<Name> {
+        let mut possible_types: IndexSet<Name> = self
+            .object_type_defs
+            .iter()
+            .filter(|o| &o.name == name || o.interface_impls.contains(name))
+            .map(|o| o.name.clone())
+            .collect();
+        possible_types.extend(
+            self.union_type_defs
+                .iter()
+                .filter(|u| &u.name == name)
+                .flat_map(|u| u.members.iter().map(|member| member.name().clone())),
+        );
+
+        possible_types
+    }
+
+    /// Check whether a value of the output type `ty` can be generated: a non-null interface or
+    /// union type without possible types has none
+    pub(crate) fn has_value(&self, ty: &Ty) -> bool {
+        match ty {
+            Ty::NonNull(inner) => match inner.as_ref() {
+                Ty::Named(name) => {
+                    !self.is_composite_type(name) || !self.possible_types(name).is_empty()
+                }
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+
+    /// List the fields of the object or interface type named `name`, including the fields added by its extensions
+    pub(crate) fn fields_of(&self, name: &Name) -> Vec<FieldDef> {
+        self.object_type_defs
+            .iter()
+            .filter(|o| &o.name == name)
+            .flat_map(|o| o.fields_def.iter())
+            .chain(
+                self.interface_type_defs
+                    .iter()
+                    .filter(|itf| &itf.name == name)
+                    .flat_map(|itf| itf.fields_def.iter()),
+            )
+            .cloned()
+            .collect()
     }
 }
@@ -1,7 +1,6 @@
-use std::collections::HashSet;
-
 use apollo_encoder::UnionDefinition;
 use arbitrary::Result;
+use indexmap::IndexSet;
 
 use crate::{description::Description, directive::Directive, name::Name, ty::Ty, DocumentBuilder};
 
@@ -15,7 +14,7 @@ use crate::{description::Description, directive::Directive, name::Name, ty::Ty,
 pub struct UnionTypeDef {
     pub(crate) name: Name,
     pub(crate) description: Option<Description>,
-    pub(crate) members: HashSet<Ty>,
+    pub(crate) members: IndexSet<Ty>,
     pub(crate) directives: Vec<Directive>,
     pub(crate) extend: bool,
 }
@@ -41,7 +40,54 @@ impl From<UnionTypeDef> for UnionDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::UnionTypeDefinition> for UnionTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(union_def: apollo_parser::ast::UnionTypeDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: union_def.name().try_into()?,
+            description: union_def.description().map(Description::from),
+            members: Self::convert_members(union_def.union_member_types())?,
+            directives: Directive::convert_directives(union_def.directives())?,
+            extend: false,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::UnionTypeExtension> for UnionTypeDef {
+    type Error = crate::FromError;
+
+    fn try_from(union_def: apollo_parser::ast::UnionTypeExtension) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: union_def.name().try_into()?,
+            description: None,
+            members: Self::convert_members(union_def.union_member_types())?,
+            directives: Directive::convert_directives(union_def.directives())?,
+            extend: true,
+        })
+    }
+}
+
+#[cfg(feature = "parser-impl")]
+impl UnionTypeDef {
+    fn convert_members(
+        members: Option<apollo_parser::ast::UnionMemberTypes>,
+    ) -> Result<IndexSet<Ty>, crate::FromError> {
+        members
+            .map(|members| {
+                members
+                    .named_types()
+                    .map(|named| Ok(Ty::Named(named.name().try_into()?)))
+                    .collect()
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary `UnionTypeDef`
     pub fn union_type_definition(&mut self) -> Result<UnionTypeDef> {
         let name = self.type_name()?;
@@ -62,7 +108,7 @@ impl<'a> DocumentBuilder<'a> {
 
         let members = (0..self.u.int_in_range(2..=10)?)
             .map(|_| self.choose_named_ty(&existing_types))
-            .collect::<Result<HashSet<_>>>()?;
+            .collect::<Result<IndexSet<_>>>()?;
 
         Ok(UnionTypeDef {
             name,
@@ -72,4 +118,30 @@ impl<'a> DocumentBuilder<'a> {
             extend,
         })
     }
+
+    /// Create an arbitrary `UnionTypeDef` named `name` for a valid schema, whose members are some
+    /// of the `object_types`
+    pub(crate) fn valid_union_type_definition(
+        &mut self,
+        name: Name,
+        object_types: &[Name],
+    ) -> Result<UnionTypeDef> {
+        let description = self
+            .u
+            .arbitrary()
+            .unwrap_or(false)
+            .then(|| self.description())
+            .transpose()?;
+        let members = (0..self.u.int_in_range(1..=5usize)?)
+            .map(|_| Ok(Ty::Named(self.u.choose(object_types)?.clone())))
+            .collect::<Result<IndexSet<_>>>()?;
+
+        Ok(UnionTypeDef {
+            name,
+            description,
+            members,
+            directives: Vec::new(),
+            extend: false,
+        })
+    }
 }
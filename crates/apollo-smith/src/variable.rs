@@ -8,12 +8,12 @@ use crate::{directive::Directive, input_value::InputValue, name::Name, ty::Ty, D
 ///     VariableName : Type DefaultValue? Directives?
 ///
 /// Detailed documentation can be found in [GraphQL spec](https://spec.graphql.org/October2021/#sec-Language.Variables).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VariableDef {
-    name: Name,
-    ty: Ty,
-    default_value: Option<InputValue>,
-    directives: Vec<Directive>,
+    pub(crate) name: Name,
+    pub(crate) ty: Ty,
+    pub(crate) default_value: Option<InputValue>,
+    pub(crate) directives: Vec<Directive>,
 }
 
 impl From<VariableDef> for apollo_encoder::VariableDefinition {
@@ -29,7 +29,31 @@ impl From<VariableDef> for apollo_encoder::VariableDefinition {
     }
 }
 
-impl<'a> DocumentBuilder<'a> {
+#[cfg(feature = "parser-impl")]
+impl TryFrom<apollo_parser::ast::VariableDefinition> for VariableDef {
+    type Error = crate::FromError;
+
+    fn try_from(var_def: apollo_parser::ast::VariableDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: var_def
+                .variable()
+                .and_then(|variable| variable.name())
+                .try_into()?,
+            ty: var_def
+                .ty()
+                .ok_or(crate::FromError::MissingField)?
+                .try_into()?,
+            default_value: var_def
+                .default_value()
+                .and_then(|default| default.value())
+                .map(InputValue::try_from)
+                .transpose()?,
+            directives: Directive::convert_directives(var_def.directives())?,
+        })
+    }
+}
+
+impl<'a, 'b> DocumentBuilder<'a, 'b> {
     /// Create an arbitrary list of `VariableDef`
     pub fn variable_definitions(&mut self) -> Result<Vec<VariableDef>> {
         (0..self.u.int_in_range(0..=7usize)?)
@@ -56,4 +80,27 @@ impl<'a> DocumentBuilder<'a> {
             directives,
         })
     }
+
+    /// Declare a new variable of type `ty` in the operation being generated, and return its name
+    pub(crate) fn declare_variable(&mut self, ty: &Ty) -> Result<Option<Name>> {
+        let index = match &self.operation_variables {
+            Some(variables) => variables.len(),
+            None => return Ok(None),
+        };
+        let name = Name::new(format!(
+            "{}_{}",
+            self.limited_string(self.config.name_length_limit().min(10))?,
+            index
+        ));
+        if let Some(variables) = &mut self.operation_variables {
+            variables.push(VariableDef {
+                name: name.clone(),
+                ty: ty.clone(),
+                default_value: None,
+                directives: Vec::new(),
+            });
+        }
+
+        Ok(Some(name))
+    }
 }
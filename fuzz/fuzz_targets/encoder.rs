@@ -0,0 +1,49 @@
+#![no_main]
+use apollo_parser::Parser;
+use apollo_rs_fuzz::{generate_small_document, log_gql_doc};
+use apollo_smith::Document;
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+use std::convert::TryFrom;
+
+fuzz_target!(|data: &[u8]| {
+    let doc_generated = match generate_small_document(data) {
+        Ok(d) => d,
+        Err(_) => {
+            return;
+        }
+    };
+    debug!("======= DOCUMENT =======");
+    debug!("{}", doc_generated);
+    debug!("========================");
+
+    // The document printed by apollo-encoder parses
+    let tree = Parser::new(&doc_generated).parse();
+    if tree.errors().len() > 0 {
+        let errors = tree
+            .errors()
+            .map(|err| err.message())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        debug!("Parser errors ========== \n{:?}", errors);
+        debug!("========================");
+        log_gql_doc(&doc_generated, &errors);
+        panic!("apollo-encoder printed a document that does not parse");
+    }
+
+    // Printing the parsed document again gives the same document
+    let reprinted = match Document::try_from(tree.document()) {
+        Ok(document) => String::from(document),
+        Err(err) => {
+            log_gql_doc(&doc_generated, &format!("{:?}", err));
+            panic!("the parsed document cannot be converted: {:?}", err);
+        }
+    };
+    if reprinted != doc_generated {
+        debug!("======= REPRINTED DOCUMENT =======");
+        debug!("{}", reprinted);
+        debug!("==================================");
+        log_gql_doc(&doc_generated, &reprinted);
+        panic!("the parsed document is printed differently");
+    }
+});
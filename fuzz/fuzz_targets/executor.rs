@@ -0,0 +1,127 @@
+#![no_main]
+use apollo_compiler::{
+    execution,
+    execution::{Executor, Resolver, ResponseValue},
+    validate, Schema,
+};
+use apollo_parser::{ast, Parser};
+use apollo_rs_fuzz::{generate_valid_operations_with_responses, log_gql_doc};
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+const SCHEMA: &str = r#"
+schema { query: Query mutation: Mutation subscription: Subscription }
+type Query {
+  node(id: ID!): Node
+  search(text: String, filter: Filter, first: Int = 10): [SearchResult!]!
+  user(id: ID!): User
+  users(ids: [ID!]!, role: Role): [User]
+  me: User!
+}
+type Mutation {
+  rename(id: ID!, name: String!): User
+  setRole(input: RoleInput!): Boolean
+}
+type Subscription { userChanged(id: ID): User tick: Int! }
+interface Node { id: ID! }
+interface Named { name: String }
+type User implements Node & Named {
+  id: ID!
+  name: String
+  friends(first: Int): [User!]
+  role: Role
+  best: User
+}
+type Post implements Node { id: ID! title: String! author: User! tags: [String] }
+union SearchResult = User | Post
+enum Role { ADMIN MEMBER GUEST }
+input Filter { role: Role and: [Filter!] not: Filter text: String! }
+input RoleInput { id: ID! role: Role = MEMBER }
+scalar Date
+extend type Post { date: Date score: Float }
+"#;
+
+/// Resolves fields with the default resolver, which reads them from their parent's JSON. The
+/// parent of the root fields is `null`, so every field is `null`
+struct NullResolver;
+
+impl Resolver for NullResolver {}
+
+fuzz_target!(|data: &[u8]| {
+    let schema_doc = Parser::new(SCHEMA).parse().document();
+    let (operations, responses) = match generate_valid_operations_with_responses(data, &schema_doc)
+    {
+        Ok(d) => d,
+        Err(_) => {
+            return;
+        }
+    };
+    debug!("======= OPERATIONS =======");
+    debug!("{}", operations);
+    debug!("==========================");
+
+    // Validate the operations along with the schema they were generated for
+    let doc_generated = format!("{}\n{}", SCHEMA, operations);
+    let tree = Parser::new(&doc_generated).parse();
+    let mut errors: Vec<String> = tree.errors().map(|err| err.message().to_string()).collect();
+    let document = tree.document();
+    errors.extend(
+        validate(&Schema::new(&document), &document)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string()),
+    );
+    if !errors.is_empty() {
+        let errors = errors.join("\n");
+        debug!("Validation errors ====== \n{:?}", errors);
+        debug!("========================");
+        log_gql_doc(&doc_generated, &errors);
+        panic!("error detected");
+    }
+
+    let schema = Schema::new(&schema_doc);
+    let executor = Executor::new(&schema, &NullResolver);
+    let document = Parser::new(&operations).parse().document();
+    let operation_defs = document
+        .definitions()
+        .filter_map(|definition| match definition {
+            ast::Definition::OperationDefinition(operation) => Some(operation),
+            _ => None,
+        });
+    for (operation, (variables, generated)) in operation_defs.zip(responses) {
+        let name = operation.name().map(|name| name.text().to_string());
+        let response =
+            futures::executor::block_on(executor.execute(&document, name.as_deref(), &variables));
+        debug!("{:?}", response);
+
+        // The root fields are `null`, except `__typename`: the data is an object of `null`s
+        // without errors, unless a root field is non-null, which is an error and leaves no data
+        let is_valid = match response.data() {
+            Some(ResponseValue::Object(fields)) => {
+                response.errors().is_empty()
+                    && fields
+                        .values()
+                        .all(|value| value.is_null() || value.as_str().is_some())
+            }
+            _ => !response.errors().is_empty(),
+        };
+        if !is_valid {
+            log_gql_doc(&operations, &format!("{:?}", response));
+            panic!("unexpected response to an operation resolved to `null`");
+        }
+
+        // Subscriptions cannot be executed as a single request
+        let is_subscription = operation
+            .operation_type()
+            .map_or(false, |ty| ty.subscription_token().is_some());
+        if is_subscription {
+            continue;
+        }
+        // The generated response has the types of the fields it selects, so it is read back
+        // as raw data without errors
+        let shaped = execution::shape(&schema, &document, name.as_deref(), &variables, &generated);
+        if !shaped.errors().is_empty() {
+            log_gql_doc(&operations, &format!("{}\n{:?}", generated, shaped));
+            panic!("the generated variables or response do not match the operation");
+        }
+    }
+});
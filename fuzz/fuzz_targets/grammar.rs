@@ -0,0 +1,33 @@
+#![no_main]
+use apollo_parser::{ast::AstNode, Parser};
+use apollo_rs_fuzz::{generate_grammar_document, log_gql_doc};
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+fuzz_target!(|data: &[u8]| {
+    let doc_generated = match generate_grammar_document(data) {
+        Ok(d) => d,
+        Err(_) => {
+            return;
+        }
+    };
+    debug!("======= DOCUMENT =======");
+    debug!("{}", doc_generated);
+    debug!("========================");
+
+    // The grammar is less strict than GraphQL, so the document can have errors
+    let tree = Parser::new(&doc_generated).parse();
+    let errors = tree
+        .errors()
+        .map(|err| format!("{:?}", err))
+        .collect::<Vec<String>>()
+        .join("\n");
+    debug!("Parser errors ========== \n{}", errors);
+    debug!("========================");
+
+    // The syntax tree is lossless
+    if tree.document().syntax().to_string() != doc_generated {
+        log_gql_doc(&doc_generated, &errors);
+        panic!("the syntax tree does not round-trip to the document");
+    }
+});
@@ -0,0 +1,41 @@
+#![no_main]
+use apollo_parser::{ast::AstNode, Parser};
+use apollo_rs_fuzz::log_gql_doc;
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+fuzz_target!(|data: &[u8]| {
+    drop(env_logger::try_init());
+    let input = String::from_utf8_lossy(data);
+    debug!("======= INPUT =======");
+    debug!("{}", input);
+    debug!("=====================");
+
+    let tree = Parser::new(&input).parse();
+    let errors = tree
+        .errors()
+        .map(|err| format!("{:?}", err))
+        .collect::<Vec<String>>()
+        .join("\n");
+    debug!("Parser errors ========== \n{}", errors);
+    debug!("========================");
+
+    // Errors point into the input
+    if let Some(err) = tree
+        .errors()
+        .find(|err| err.index() > input.len() || !input.is_char_boundary(err.index()))
+    {
+        log_gql_doc(&input, &errors);
+        panic!("the error {:?} is not within the input", err);
+    }
+
+    // The syntax tree is lossless
+    let tree_text = tree.document().syntax().to_string();
+    if tree_text != input {
+        debug!("======= TREE TEXT =======");
+        debug!("{}", tree_text);
+        debug!("=========================");
+        log_gql_doc(&input, &errors);
+        panic!("the syntax tree does not round-trip to the input");
+    }
+});
@@ -1,9 +1,8 @@
 #![no_main]
 use apollo_parser::Parser;
-use apollo_rs_fuzz::{generate_valid_document, log_gql_doc};
+use apollo_rs_fuzz::{catch_panic, generate_valid_document, log_gql_doc, log_reduced_gql_doc};
 use libfuzzer_sys::fuzz_target;
 use log::debug;
-use std::panic;
 
 fuzz_target!(|data: &[u8]| {
     let doc_generated = match generate_valid_document(data) {
@@ -12,34 +11,41 @@ fuzz_target!(|data: &[u8]| {
             return;
         }
     };
-
-    let parser = panic::catch_unwind(|| Parser::new(&doc_generated));
-
-    let parser = match parser {
-        Err(err) => {
-            panic!("error {:?}", err);
-        }
-        Ok(p) => p,
-    };
     debug!("======= DOCUMENT =======");
     debug!("{}", doc_generated);
     debug!("========================");
 
-    let tree = parser.parse();
-    // early return if the parser detected an error
-    let mut should_panic = false;
-    if tree.errors().len() > 0 {
-        should_panic = true;
-        let errors = tree
-            .errors()
-            .map(|err| err.message())
-            .collect::<Vec<&str>>()
-            .join("\n");
-        debug!("Parser errors ========== \n{:?}", errors);
+    let errors = match catch_panic(|| parse_errors(&doc_generated)) {
+        Ok(errors) => errors,
+        Err(message) => {
+            debug!("Parser panic =========== \n{}", message);
+            debug!("========================");
+            log_gql_doc(&doc_generated, &message);
+            log_reduced_gql_doc(&doc_generated, |doc| {
+                catch_panic(|| parse_errors(doc)).is_err()
+            });
+            panic!("the parser panicked: {}", message);
+        }
+    };
+    // the parser must not detect an error in a valid document
+    if let Some(first_error) = errors.first() {
+        let errors_str = errors.join("\n");
+        debug!("Parser errors ========== \n{:?}", errors_str);
         debug!("========================");
-        log_gql_doc(&doc_generated, &errors);
-    }
-    if should_panic {
+        log_gql_doc(&doc_generated, &errors_str);
+        // the reduced document fails with the same error, not just any error
+        log_reduced_gql_doc(&doc_generated, |doc| {
+            catch_panic(|| parse_errors(doc)).map_or(false, |errors| errors.contains(first_error))
+        });
         panic!("error detected");
     }
 });
+
+/// The messages of the errors the parser detects in `document`
+fn parse_errors(document: &str) -> Vec<String> {
+    Parser::new(document)
+        .parse()
+        .errors()
+        .map(|err| err.message().to_string())
+        .collect()
+}
@@ -0,0 +1,37 @@
+#![no_main]
+use apollo_parser::{ast::AstNode, Parser};
+use apollo_rs_fuzz::{generate_malformed_document, log_gql_doc};
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+fuzz_target!(|data: &[u8]| {
+    let doc_generated = match generate_malformed_document(data) {
+        Ok(d) => d,
+        Err(_) => {
+            return;
+        }
+    };
+    debug!("======= DOCUMENT =======");
+    debug!("{}", doc_generated);
+    debug!("========================");
+
+    // The parser recovers from the errors of a malformed document, which must
+    // not panic, and keeps all of its text in the tree.
+    let tree = Parser::new(&doc_generated).parse();
+    let errors = tree
+        .errors()
+        .map(|err| err.message())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    debug!("Parser errors ========== \n{:?}", errors);
+    debug!("========================");
+
+    let tree_text = tree.document().syntax().to_string();
+    if tree_text != doc_generated {
+        debug!("======= TREE TEXT =======");
+        debug!("{}", tree_text);
+        debug!("=========================");
+        log_gql_doc(&doc_generated, &errors);
+        panic!("the syntax tree does not round-trip to the input");
+    }
+});
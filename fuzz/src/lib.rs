@@ -1,5 +1,11 @@
-use apollo_smith::DocumentBuilder;
+use apollo_parser::ast;
+use apollo_smith::{Config, DefinitionKind, DocumentBuilder, GrammarError, GrammarGenerator};
 use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use serde_json::{Map, Value};
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
+/// The grammar the `apollo-parser` syntax tree is generated from
+pub const GRAPHQL_GRAMMAR: &str = include_str!("../../graphql.ungram");
 
 /// This generate an arbitrary valid GraphQL document
 pub fn generate_valid_document(input: &[u8]) -> Result<String> {
@@ -12,6 +18,86 @@ pub fn generate_valid_document(input: &[u8]) -> Result<String> {
     Ok(document.into())
 }
 
+/// This generate an arbitrary valid GraphQL document with a few definitions of every kind, small
+/// enough to be generated from most inputs
+pub fn generate_small_document(input: &[u8]) -> Result<String> {
+    drop(env_logger::try_init());
+
+    let mut config = Config::default();
+    for kind in DefinitionKind::ALL {
+        config.definition_count(kind, 0, 2);
+    }
+    config.max_selection_depth(2);
+    config.max_arguments(2);
+    config.max_name_length(8);
+
+    let mut u = Unstructured::new(input);
+    let gql_doc = DocumentBuilder::with_config(&mut u, config)?;
+    let document = gql_doc.finish();
+
+    Ok(document.into())
+}
+
+/// This generate arbitrary valid operations and fragments for the given schema
+pub fn generate_valid_operations(input: &[u8], schema: &ast::Document) -> Result<String> {
+    drop(env_logger::try_init());
+
+    let mut u = Unstructured::new(input);
+    let gql_doc = DocumentBuilder::with_document(&mut u, schema)?;
+    let document = gql_doc.finish_executable();
+
+    Ok(document.into())
+}
+
+/// The JSON variables and response data generated for an operation
+pub type OperationResponse = (Map<String, Value>, Value);
+
+/// This generate arbitrary valid operations and fragments for the given schema, along with
+/// arbitrary variables and response data for each operation, in the order of the operations
+pub fn generate_valid_operations_with_responses(
+    input: &[u8],
+    schema: &ast::Document,
+) -> Result<(String, Vec<OperationResponse>)> {
+    drop(env_logger::try_init());
+
+    let mut u = Unstructured::new(input);
+    let mut gql_doc = DocumentBuilder::with_document(&mut u, schema)?;
+    let mut responses = Vec::new();
+    for operation in gql_doc.operation_definitions().to_vec() {
+        let variables = gql_doc.variables_for_operation(&operation)?;
+        let response = gql_doc.response_for_operation(&operation, &variables)?;
+        responses.push((variables, response));
+    }
+    let document = gql_doc.finish_executable();
+
+    Ok((document.into(), responses))
+}
+
+/// This generate an arbitrary malformed GraphQL document, by mutating a valid one
+pub fn generate_malformed_document(input: &[u8]) -> Result<String> {
+    drop(env_logger::try_init());
+
+    let mut u = Unstructured::new(input);
+    let gql_doc = DocumentBuilder::new(&mut u)?;
+
+    gql_doc.finish_malformed()
+}
+
+/// This generate an arbitrary GraphQL document following the GraphQL grammar, which is not always
+/// valid
+pub fn generate_grammar_document(input: &[u8]) -> Result<String> {
+    drop(env_logger::try_init());
+
+    let mut generator = GrammarGenerator::new(GRAPHQL_GRAMMAR).expect("invalid GraphQL grammar");
+    let mut u = Unstructured::new(input);
+
+    match generator.generate(&mut u, "Document") {
+        Ok(document) => Ok(document),
+        Err(GrammarError::Arbitrary(err)) => Err(err),
+        Err(err) => panic!("cannot generate a GraphQL document: {}", err),
+    }
+}
+
 /// Log the error and the document generated for these errors
 /// Save it into files
 pub fn log_gql_doc(gql_doc: &str, errors: &str) {
@@ -19,3 +105,74 @@ pub fn log_gql_doc(gql_doc: &str, errors: &str) {
     std::fs::write("test_case.graphql", gql_doc).unwrap();
     std::fs::write("test_case_error.log", errors).unwrap();
 }
+
+/// Run `f`, returning the message of its panic if it panics
+///
+/// The panic hook of `fuzz_target!` aborts the process before unwinding, so it is replaced while
+/// `f` runs
+pub fn catch_panic<T>(f: impl FnOnce() -> T + UnwindSafe) -> std::result::Result<T, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(f);
+    panic::set_hook(hook);
+
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic without a message".to_string())
+    })
+}
+
+/// Reduce the document to a minimal one for which `predicate` still holds
+/// Save it into a file next to the test case
+pub fn log_reduced_gql_doc(gql_doc: &str, predicate: impl FnMut(&str) -> bool) {
+    log::debug!("writing reduced test case to test_case_reduced.graphql ...");
+    let reduced = reduce_gql_doc(gql_doc, predicate);
+    std::fs::write("test_case_reduced.graphql", reduced).unwrap();
+}
+
+/// Reduce the document with `apollo_smith::reduce`, which catches the panics of the parser on the
+/// documents it reduces, so the whole reduction runs with the panic hook replaced
+///
+/// A predicate that panics holds, as the panic reproduces the failure. The document is returned as
+/// is if the reduction itself fails
+fn reduce_gql_doc(gql_doc: &str, mut predicate: impl FnMut(&str) -> bool) -> String {
+    let reduced = catch_panic(AssertUnwindSafe(|| {
+        apollo_smith::reduce(gql_doc, |doc| {
+            catch_panic(AssertUnwindSafe(|| predicate(doc))).unwrap_or(true)
+        })
+    }));
+    reduced.unwrap_or_else(|message| {
+        log::debug!("could not reduce the test case: {}", message);
+        gql_doc.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn it_reduces_documents_whose_predicate_panics() {
+        // The hook of `fuzz_target!` aborts the process, this one records that it was called
+        static HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| HOOK_CALLED.store(true, Ordering::SeqCst)));
+
+        let document = "type Query { a: Int }\ntype Dog { name: String }\n";
+        let reduced = reduce_gql_doc(document, |doc| {
+            if doc.contains("Dog") {
+                panic!("the predicate panics");
+            }
+            false
+        });
+        panic::set_hook(hook);
+
+        assert!(!HOOK_CALLED.load(Ordering::SeqCst));
+        assert_eq!(reduced.trim(), "type Dog { }");
+    }
+}
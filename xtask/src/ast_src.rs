@@ -65,7 +65,7 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "INPUT_FIELD_DEFINITION",
     ],
     literals: &["INT", "FLOAT", "STRING"],
-    tokens: &["IDENT", "WHITESPACE", "COMMENT"],
+    tokens: &["IDENT", "WHITESPACE", "COMMENT", "ERROR"],
     // These are all the "DOCUMENT" items defined in the GraphQL spec --
     // https://spec.graphql.org/October2021/#sec-Appendix-Grammar-Summary.Document,
     // as well as items listed in the ungrammar file in the root directory.
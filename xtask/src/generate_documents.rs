@@ -0,0 +1,62 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use apollo_smith::{Config, DefinitionKind, Document};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct GenerateDocuments {
+    /// Number of documents to generate
+    #[structopt(long, default_value = "100")]
+    count: u64,
+
+    /// Seed of the first document, the next ones are generated from the next seeds
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+
+    /// Directory to write the documents to, as `<seed>.graphql`
+    #[structopt(long, parse(from_os_str), default_value = "target/documents")]
+    out_dir: PathBuf,
+
+    /// Maximum number of definitions of each kind
+    #[structopt(long)]
+    max_definitions: Option<usize>,
+
+    /// Maximum depth of nested selection sets
+    #[structopt(long)]
+    max_selection_depth: Option<usize>,
+
+    /// Generate a valid schema, and operations that are valid against it
+    #[structopt(long)]
+    valid_schema: bool,
+}
+
+impl GenerateDocuments {
+    pub(crate) fn run(&self, verbose: bool) -> Result<()> {
+        let mut config = Config::default();
+        if let Some(max_definitions) = self.max_definitions {
+            for kind in DefinitionKind::ALL {
+                config.definition_count(kind, 0, max_definitions);
+            }
+        }
+        if let Some(max_selection_depth) = self.max_selection_depth {
+            config.max_selection_depth(max_selection_depth);
+        }
+        config.valid_schema(self.valid_schema);
+
+        fs::create_dir_all(&self.out_dir)
+            .with_context(|| format!("cannot create {}", self.out_dir.display()))?;
+        for seed in self.seed..self.seed + self.count {
+            let document = Document::from_seed(seed, config.clone())
+                .with_context(|| format!("cannot generate a document from seed {}", seed))?;
+            let path = self.out_dir.join(format!("{}.graphql", seed));
+            fs::write(&path, String::from(document))
+                .with_context(|| format!("cannot write {}", path.display()))?;
+            if verbose {
+                println!("wrote {}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+}
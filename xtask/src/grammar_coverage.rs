@@ -0,0 +1,59 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use apollo_smith::{GrammarError, GrammarGenerator};
+use arbitrary::Unstructured;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct GrammarCoverage {
+    /// Directory of the inputs to generate from, e.g. the corpus of the `grammar` fuzz target
+    #[structopt(long, parse(from_os_str), default_value = "fuzz/corpus/grammar")]
+    corpus: PathBuf,
+
+    /// Node of `graphql.ungram` to generate from every input
+    #[structopt(long, default_value = "Document")]
+    node: String,
+
+    /// Maximum depth of nested nodes
+    #[structopt(long)]
+    max_depth: Option<usize>,
+}
+
+impl GrammarCoverage {
+    pub(crate) fn run(&self, verbose: bool) -> Result<()> {
+        let grammar_src = include_str!("../../graphql.ungram");
+        let mut generator = GrammarGenerator::new(grammar_src)?;
+        if let Some(max_depth) = self.max_depth {
+            generator.max_depth(max_depth);
+        }
+
+        let entries = fs::read_dir(&self.corpus)
+            .with_context(|| format!("cannot read {}", self.corpus.display()))?;
+        let mut inputs = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let data =
+                fs::read(&path).with_context(|| format!("cannot read {}", path.display()))?;
+            // Inputs without enough data generate what they can, like in the fuzz target
+            match generator.generate(&mut Unstructured::new(&data), &self.node) {
+                Ok(_) => (),
+                Err(GrammarError::Arbitrary(_)) => {
+                    if verbose {
+                        eprintln!("not enough data in {}", path.display());
+                    }
+                }
+                Err(err) => bail!("cannot generate {}: {}", self.node, err),
+            }
+            inputs += 1;
+        }
+
+        println!("generated {} from {} inputs", self.node, inputs);
+        print!("{}", generator.coverage());
+
+        Ok(())
+    }
+}
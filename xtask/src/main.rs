@@ -1,5 +1,8 @@
 mod ast_src;
 mod codegen;
+mod generate_documents;
+mod grammar_coverage;
+mod reduce;
 mod utils;
 
 use std::{
@@ -30,12 +33,21 @@ struct Xtask {
 pub enum Command {
     /// Perform code generation for the parser
     Codegen(codegen::Codegen),
+    /// Write documents generated by apollo-smith, e.g. for benchmarks or load testing
+    GenerateDocuments(generate_documents::GenerateDocuments),
+    /// Report the productions of graphql.ungram generated from a fuzz corpus
+    GrammarCoverage(grammar_coverage::GrammarCoverage),
+    /// Reduce a document the parser fails on to a minimal reproducer
+    Reduce(reduce::Reduce),
 }
 
 impl Xtask {
     pub fn run(&self) -> Result<()> {
         match &self.command {
             Command::Codegen(command) => command.run(self.verbose),
+            Command::GenerateDocuments(command) => command.run(self.verbose),
+            Command::GrammarCoverage(command) => command.run(self.verbose),
+            Command::Reduce(command) => command.run(self.verbose),
         }?;
 
         Ok(())
@@ -0,0 +1,77 @@
+use std::{fs, panic, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use apollo_parser::Parser;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Reduce {
+    /// Document to reduce, e.g. a `test_case.graphql` written by a fuzz target
+    #[structopt(parse(from_os_str))]
+    document: PathBuf,
+
+    /// Keep the documents that the parser panics on
+    #[structopt(long, conflicts_with = "error")]
+    panic: bool,
+
+    /// Keep the documents with a parse error containing this message
+    #[structopt(long)]
+    error: Option<String>,
+
+    /// File to write the reduced document to, it is printed if not set
+    #[structopt(long, parse(from_os_str))]
+    out: Option<PathBuf>,
+}
+
+impl Reduce {
+    pub(crate) fn run(&self, verbose: bool) -> Result<()> {
+        let document = fs::read_to_string(&self.document)
+            .with_context(|| format!("cannot read {}", self.document.display()))?;
+
+        // Every document the parser panics on would print the panic otherwise
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        if !self.keeps(&document) {
+            panic::set_hook(hook);
+            bail!(
+                "the parser does not fail on {} the way the reduced document has to",
+                self.document.display()
+            );
+        }
+        let reduced = apollo_smith::reduce(&document, |document| self.keeps(document));
+        panic::set_hook(hook);
+
+        if verbose {
+            eprintln!(
+                "reduced {} from {} to {} bytes",
+                self.document.display(),
+                document.len(),
+                reduced.len()
+            );
+        }
+        match &self.out {
+            Some(out) => fs::write(out, reduced)
+                .with_context(|| format!("cannot write {}", out.display()))?,
+            None => print!("{}", reduced),
+        }
+
+        Ok(())
+    }
+
+    /// Whether `document` still fails the way the reduced document has to, by default with a
+    /// panic or any parse error
+    fn keeps(&self, document: &str) -> bool {
+        let errors = panic::catch_unwind(|| {
+            Parser::new(document)
+                .parse()
+                .errors()
+                .map(|err| err.message().to_string())
+                .collect::<Vec<_>>()
+        });
+        match (errors, &self.error) {
+            (Err(_), _) => self.error.is_none(),
+            (Ok(errors), Some(error)) => errors.iter().any(|message| message.contains(error)),
+            (Ok(errors), None) => !self.panic && !errors.is_empty(),
+        }
+    }
+}